@@ -0,0 +1,57 @@
+extern crate cbindgen;
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_PREFIX: &str = "/usr/local";
+
+fn main() {
+    let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=argonautica.pc.in");
+    println!("cargo:rerun-if-env-changed=ARGONAUTICA_PREFIX");
+    println!("cargo:rerun-if-env-changed=ARGONAUTICA_UPDATE_HEADERS");
+
+    generate_header(&crate_dir, &out_dir);
+    generate_pkg_config(&crate_dir, &out_dir);
+}
+
+/// Generates `argonautica.h` into `OUT_DIR`. If the `ARGONAUTICA_UPDATE_HEADERS` environment
+/// variable is set, the committed copies of the header (for C and for Python) are overwritten
+/// as well
+fn generate_header(crate_dir: &Path, out_dir: &Path) {
+    let bindings = cbindgen::Builder::new()
+        .with_crate(crate_dir)
+        .with_language(cbindgen::Language::C)
+        .generate()
+        .expect("Unable to generate bindings");
+    let header = out_dir.join("argonautica.h");
+    bindings.write_to_file(&header);
+
+    if env::var_os("ARGONAUTICA_UPDATE_HEADERS").is_none() {
+        return;
+    }
+    bindings.write_to_file(crate_dir.join("include").join("argonautica.h"));
+    let dir = crate_dir.join("../argonautica-py/argonautica");
+    if dir.exists() {
+        bindings.write_to_file(dir.join("argonautica.h"));
+    }
+}
+
+/// Generates `argonautica.pc` from `argonautica.pc.in` into `OUT_DIR`, from where
+/// `make install` installs it. The prefix defaults to `/usr/local` and can be overridden with
+/// the `ARGONAUTICA_PREFIX` environment variable
+fn generate_pkg_config(crate_dir: &Path, out_dir: &Path) {
+    let prefix = env::var("ARGONAUTICA_PREFIX").unwrap_or_else(|_| DEFAULT_PREFIX.to_string());
+    let template = fs::read_to_string(crate_dir.join("argonautica.pc.in"))
+        .expect("Unable to read argonautica.pc.in");
+    let pc = template
+        .replace("@PREFIX@", &prefix)
+        .replace("@DESCRIPTION@", env!("CARGO_PKG_DESCRIPTION"))
+        .replace("@URL@", env!("CARGO_PKG_HOMEPAGE"))
+        .replace("@VERSION@", env!("CARGO_PKG_VERSION"));
+    fs::write(out_dir.join("argonautica.pc"), &pc).expect("Unable to write argonautica.pc");
+}
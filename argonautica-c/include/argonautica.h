@@ -124,6 +124,54 @@ typedef enum {
    * Utf-8 encode error. Bytes provided could not be encoded into utf-8
    */
   ARGONAUTICA_ERROR_UTF8_ENCODE = 25,
+  /*
+   * Buffer too small. A caller-provided buffer was too small to hold the output
+   */
+  ARGONAUTICA_ERROR_BUFFER_TOO_SMALL = 26,
+  /*
+   * Cancelled. The operation was cancelled before it completed
+   */
+  ARGONAUTICA_ERROR_CANCELLED = 27,
+  /*
+   * Inner digest invalid. The password provided is not a valid digest of the configured inner scheme
+   */
+  ARGONAUTICA_ERROR_INNER_DIGEST_INVALID = 28,
+  /*
+   * Password normalization error. The password contains characters that are not allowed by the configured normalization
+   */
+  ARGONAUTICA_ERROR_PASSWORD_NORMALIZATION = 29,
+  /*
+   * Additional data mismatch. The hash is bound to additional data other than the additional data provided
+   */
+  ARGONAUTICA_ERROR_ADDITIONAL_DATA_MISMATCH = 30,
+  /*
+   * Additional data missing. The hash is bound to additional data, but none was provided
+   */
+  ARGONAUTICA_ERROR_ADDITIONAL_DATA_MISSING = 31,
+  /*
+   * Secret key mismatch. The hash is bound to a secret key other than the secret key provided
+   */
+  ARGONAUTICA_ERROR_SECRET_KEY_MISMATCH = 32,
+  /*
+   * Secret key missing. Attempted to hash without a secret key (and without opting out of one) or to verify a bound hash without one
+   */
+  ARGONAUTICA_ERROR_SECRET_KEY_MISSING = 33,
+  /*
+   * I/O error. Failed to read a file or directory
+   */
+  ARGONAUTICA_ERROR_IO = 34,
+  /*
+   * Key file invalid. A key file or key ID is malformed, or a key set already contains a key with the same key ID
+   */
+  ARGONAUTICA_ERROR_KEY_FILE_INVALID = 35,
+  /*
+   * Secret key too short. Length in bytes must be at least 16
+   */
+  ARGONAUTICA_ERROR_SECRET_KEY_TOO_SHORT = 36,
+  /*
+   * Secret key permissions error. A key file is accessible by users other than its owner
+   */
+  ARGONAUTICA_ERROR_SECRET_KEY_PERMISSIONS = 37,
 } argonautica_error_t;
 
 /*
@@ -158,6 +206,222 @@ typedef enum {
   ARGONAUTICA_0x13 = 16,
 } argonautica_version_t;
 
+/*
+ * Opaque handle used to cancel non-blocking calls. Create one with
+ * `argonautica_cancel_token_new`, pass it to any number of non-blocking calls, cancel them with
+ * `argonautica_cancel_token_cancel` and release it with `argonautica_cancel_token_free`
+ */
+typedef struct argonautica_cancel_token_t argonautica_cancel_token_t;
+
+/*
+ * Opaque handle holding a reusable hasher configuration (including an optional secret key
+ * and additional data). Create one with `argonautica_hasher_new` and release it with
+ * `argonautica_hasher_free`
+ */
+typedef struct argonautica_hasher_t argonautica_hasher_t;
+
+/*
+ * Opaque handle holding a set of secret keys, each with a key ID, for key rotation (see
+ * `argonautica_verifier_verify_with_key_set`). Create one with `argonautica_key_set_new` and
+ * release it with `argonautica_key_set_free`
+ */
+typedef struct argonautica_key_set_t argonautica_key_set_t;
+
+/*
+ * Opaque handle holding a reusable verifier configuration (including an optional secret key
+ * and additional data). Create one with `argonautica_verifier_new` and release it with
+ * `argonautica_verifier_free`
+ */
+typedef struct argonautica_verifier_t argonautica_verifier_t;
+
+/*
+ * Struct representing raw hash output, i.e. the parameters of a hash together with its
+ * raw salt bytes and raw hash bytes.
+ *
+ * The `hash` and `salt` buffers are always owned by the caller:
+ * * When this struct is filled in by argonautica (`argonautica_decode` or
+ *   `argonautica_hasher_hash_raw`), `hash` and `salt` must point to caller-provided buffers
+ *   and `hash_len` and `salt_len` must hold the capacity (in bytes) of those buffers. On success,
+ *   the buffers will contain the raw hash bytes and raw salt bytes and `hash_len` and `salt_len`
+ *   will be set to the number of bytes written
+ * * When this struct is read by argonautica (`argonautica_encode`), `hash` and `salt` must point
+ *   to buffers holding `hash_len` and `salt_len` bytes respectively
+ */
+typedef struct {
+  /*
+   * A `uint8_t*` pointing to the raw hash bytes
+   */
+  uint8_t *hash;
+  /*
+   * The number of bytes in the `hash` buffer
+   */
+  uint32_t hash_len;
+  /*
+   * The iterations configuration that was used to produce this hash
+   */
+  uint32_t iterations;
+  /*
+   * The lanes configuration that was used to produce this hash
+   */
+  uint32_t lanes;
+  /*
+   * The memory size configuration (in kibibytes) that was used to produce this hash
+   */
+  uint32_t memory_size;
+  /*
+   * A `uint8_t*` pointing to the raw salt bytes
+   */
+  uint8_t *salt;
+  /*
+   * The number of bytes in the `salt` buffer
+   */
+  uint32_t salt_len;
+  /*
+   * The variant that was used to produce this hash
+   */
+  argonautica_variant_t variant;
+  /*
+   * The version that was used to produce this hash
+   */
+  argonautica_version_t version;
+} argonautica_hash_raw_t;
+
+/*
+ * Callback invoked when an `argonautica_hasher_hash_non_blocking` call completes
+ * * `err` = an `argonautica_error_t` indicating whether or not the hash was successful
+ * * `encoded` = a `char*` pointing to the NULL-terminated string-encoded hash if `err` is
+ *   `ARGONAUTICA_OK`, or `NULL` otherwise. The buffer is owned by argonautica and is only valid
+ *   until the callback returns; copy it if you need it afterwards
+ * * `user_data` = the `void*` that was passed to `argonautica_hasher_hash_non_blocking`
+ */
+typedef void (*argonautica_hash_callback_t)(argonautica_error_t err,
+                                            const char *encoded,
+                                            void *user_data);
+
+/*
+ * Struct representing the detailed outcome of verifying a password, as filled in by
+ * `argonautica_verifier_verify_detailed` or `argonautica_verifier_verify_with_key_set`.
+ * Besides whether or not the password matched, it reports the parameters decoded from the
+ * hash, whether the password should be rehashed, how long verifying took and, when verifying
+ * with a key set, the ID of the key that matched
+ */
+typedef struct {
+  /*
+   * The number of microseconds verifying took
+   */
+  uint64_t duration_micros;
+  /*
+   * The number of bytes in the raw hash
+   */
+  uint32_t hash_len;
+  /*
+   * `1` if the password matched the hash or `0` if it did not
+   */
+  int is_valid;
+  /*
+   * A `char*` pointing to the ID of the key that matched, if the password was verified with
+   * `argonautica_verifier_verify_with_key_set` and matched, or `NULL` otherwise. It is owned
+   * by the key set and stays valid until the key set is freed
+   */
+  const char *key_id;
+  /*
+   * The iterations configuration that was used to produce the hash
+   */
+  uint32_t iterations;
+  /*
+   * The lanes configuration that was used to produce the hash
+   */
+  uint32_t lanes;
+  /*
+   * The memory size configuration (in kibibytes) that was used to produce the hash
+   */
+  uint32_t memory_size;
+  /*
+   * `1` if the password matched and the hash was produced with parameters that differ from
+   * those of the hasher given to `argonautica_verifier_configure_rehash_hasher`, or `0`
+   * otherwise
+   */
+  int rehash_recommended;
+  /*
+   * The number of bytes in the raw salt
+   */
+  uint32_t salt_len;
+  /*
+   * The variant that was used to produce the hash
+   */
+  argonautica_variant_t variant;
+  /*
+   * The version that was used to produce the hash
+   */
+  argonautica_version_t version;
+} argonautica_verification_t;
+
+/*
+ * Callback invoked when an `argonautica_verifier_verify_non_blocking` call completes
+ * * `err` = an `argonautica_error_t` indicating whether or not the verification was successful
+ * * `is_valid` = `1` if the hash / password combination is valid or `0` otherwise. Only
+ *   meaningful if `err` is `ARGONAUTICA_OK`
+ * * `user_data` = the `void*` that was passed to `argonautica_verifier_verify_non_blocking`
+ */
+typedef void (*argonautica_verify_callback_t)(argonautica_error_t err, int is_valid, void *user_data);
+
+/*
+ * Function that cancels every non-blocking call that was given this `argonautica_cancel_token_t*`.
+ * It may be called from any thread and more than once.
+ *
+ * A cancelled call that has not started yet is skipped. A cancelled call that is already running
+ * cannot be interrupted (the underlying argon2 computation is not interruptible), but its result
+ * is discarded. Either way, its callback is still invoked exactly once, with
+ * `ARGONAUTICA_ERROR_CANCELLED`, unless it had already been invoked before the cancellation
+ */
+argonautica_error_t argonautica_cancel_token_cancel(const argonautica_cancel_token_t *cancel_token);
+
+/*
+ * Function that frees an `argonautica_cancel_token_t*` created by `argonautica_cancel_token_new`.
+ * Non-blocking calls that were given the token keep their own reference to it, so it may be
+ * freed before they complete (after which they can no longer be cancelled).
+ * Passing `NULL` is a no-op
+ */
+void argonautica_cancel_token_free(argonautica_cancel_token_t *cancel_token);
+
+/*
+ * Function that creates a new, not yet cancelled `argonautica_cancel_token_t*`. The handle must
+ * eventually be released with `argonautica_cancel_token_free`
+ */
+argonautica_cancel_token_t *argonautica_cancel_token_new(void);
+
+/*
+ * Function that decodes a string-encoded hash into an `argonautica_hash_raw_t`. It will modify
+ * the provided `hash_raw` struct (and the buffers it points to) and return an
+ * `argonautica_error_t` indicating whether or not decoding was successful.
+ *
+ * Arguments (from the perspective of C code):
+ * * `hash_raw` = an `argonautica_hash_raw_t*` whose `hash` and `salt` members point to
+ *   caller-provided buffers and whose `hash_len` and `salt_len` members hold the capacity of those
+ *   buffers. Buffers whose length is at least `strlen(encoded)` are always sufficient
+ * * `encoded` = a `char*` pointing to the string-encoded hash
+ *
+ * Hashes with an `inner` parameter (onion hashes) or a `data` parameter (e.g. bound hashes)
+ * cannot be represented by an `argonautica_hash_raw_t` and fail with
+ * `ARGONAUTICA_ERROR_HASH_DECODE`
+ */
+argonautica_error_t argonautica_decode(argonautica_hash_raw_t *hash_raw, const char *encoded);
+
+/*
+ * Function that encodes an `argonautica_hash_raw_t` into a string-encoded hash. It will modify
+ * the provided `encoded` buffer and return an `argonautica_error_t` indicating whether or not
+ * encoding was successful.
+ *
+ * Arguments (from the perspective of C code):
+ * * `encoded` = a `char*` that points to a buffer whose length (in bytes) is sufficient
+ *   to hold the resulting string-encoded hash (including it's NULL byte)
+ *     * To determine what the length of the string-encoded hash will be
+ *       ahead of time (including the NULL byte), use the `argonautica_encoded_len` function
+ * * `hash_raw` = an `argonautica_hash_raw_t*` whose `hash` and `salt` members point to buffers
+ *   holding `hash_len` and `salt_len` bytes respectively. This function will not modify it
+ */
+argonautica_error_t argonautica_encode(char *encoded, const argonautica_hash_raw_t *hash_raw);
+
 /*
  * Function that returns the length of a string-encoded hash (in bytes and including the NULL byte).
  * If an error occurrs, the function returns -1
@@ -169,6 +433,29 @@ int argonautica_encoded_len(uint32_t hash_len,
                             uint32_t salt_len,
                             argonautica_variant_t variant);
 
+/*
+ * Function that clears the detailed error message of the calling thread, after which
+ * `argonautica_error_detail` will return `NULL` until another error occurs
+ */
+void argonautica_error_clear(void);
+
+/*
+ * Function that returns a detailed error message (as a `char*`) describing the most recent error
+ * that occurred on the calling thread, or `NULL` if no error has occurred on the calling thread.
+ *
+ * Unlike the static message returned by `argonautica_error_msg`, the detailed message includes
+ * context such as which length was too long or which part of a hash failed to decode.
+ *
+ * The detailed message is only updated when a function returns an `argonautica_error_t` other
+ * than `ARGONAUTICA_OK` (or passes one to a callback), so call this function right after the
+ * failure. Errors from non-blocking functions are recorded on the worker thread that invokes
+ * the callback, i.e. call this function from within the callback.
+ *
+ * The returned `char*` is owned by argonautica and stays valid until the next error occurs on
+ * the calling thread or `argonautica_error_clear` is called. Do not free it
+ */
+const char *argonautica_error_detail(void);
+
 /*
  * Given an `argonautica_error_t`, this function will return an error message as a static `char*`
  */
@@ -258,6 +545,460 @@ argonautica_error_t argonautica_hash(char *encoded,
                                      argonautica_variant_t variant,
                                      argonautica_version_t version);
 
+/*
+ * Function that configures the backend of an `argonautica_hasher_t*`
+ * * `backend` = `ARGONAUTICA_C` for the C backend
+ * * `backend` = `ARGONAUTICA_RUST` for the Rust backend
+ */
+argonautica_error_t argonautica_hasher_configure_backend(argonautica_hasher_t *hasher,
+                                                         argonautica_backend_t backend);
+
+/*
+ * Function that configures the hash length (in number of bytes) of an `argonautica_hasher_t*`
+ */
+argonautica_error_t argonautica_hasher_configure_hash_len(argonautica_hasher_t *hasher,
+                                                          uint32_t hash_len);
+
+/*
+ * Function that configures the number of iterations of an `argonautica_hasher_t*`
+ */
+argonautica_error_t argonautica_hasher_configure_iterations(argonautica_hasher_t *hasher,
+                                                            uint32_t iterations);
+
+/*
+ * Function that configures the number of lanes of an `argonautica_hasher_t*`
+ */
+argonautica_error_t argonautica_hasher_configure_lanes(argonautica_hasher_t *hasher,
+                                                       uint32_t lanes);
+
+/*
+ * Function that configures the memory size (in kibibytes) of an `argonautica_hasher_t*`
+ */
+argonautica_error_t argonautica_hasher_configure_memory_size(argonautica_hasher_t *hasher,
+                                                             uint32_t memory_size);
+
+/*
+ * Function that configures password clearing for an `argonautica_hasher_t*`. If
+ * `password_clearing` is any value other than zero, `argonautica_hasher_hash` will zero out
+ * the bytes in the password buffer it is given
+ */
+argonautica_error_t argonautica_hasher_configure_password_clearing(argonautica_hasher_t *hasher,
+                                                                   int password_clearing);
+
+/*
+ * Function that configures the number of threads of an `argonautica_hasher_t*`
+ */
+argonautica_error_t argonautica_hasher_configure_threads(argonautica_hasher_t *hasher,
+                                                         uint32_t threads);
+
+/*
+ * Function that configures the variant of an `argonautica_hasher_t*`
+ * * `variant` = `ARGONAUTICA_ARGON2D` for argon2d
+ * * `variant` = `ARGONAUTICA_ARGON2I` for argon2i
+ * * `variant` = `ARGONAUTICA_ARGON2ID` for argon2id
+ */
+argonautica_error_t argonautica_hasher_configure_variant(argonautica_hasher_t *hasher,
+                                                         argonautica_variant_t variant);
+
+/*
+ * Function that configures the version of an `argonautica_hasher_t*`
+ * * `version` = `ARGONAUTICA_0x10` for 0x10
+ * * `version` = `ARGONAUTICA_0x13` for 0x13
+ */
+argonautica_error_t argonautica_hasher_configure_version(argonautica_hasher_t *hasher,
+                                                         argonautica_version_t version);
+
+/*
+ * Function that returns the length of the string-encoded hash (in bytes and including the
+ * NULL byte) that `argonautica_hasher_hash` will produce given the current configuration
+ * of an `argonautica_hasher_t*`. If an error occurrs, the function returns -1
+ */
+int argonautica_hasher_encoded_len(const argonautica_hasher_t *hasher);
+
+/*
+ * Function that frees an `argonautica_hasher_t*` created by `argonautica_hasher_new`.
+ * Any secret key held by the handle is zeroed out before its memory is released.
+ * Passing `NULL` is a no-op
+ */
+void argonautica_hasher_free(argonautica_hasher_t *hasher);
+
+/*
+ * Function that hashes a password using the configuration, salt, secret key and additional
+ * data held by an `argonautica_hasher_t*`. It will modify the provided `encoded` buffer
+ * and return an `argonautica_error_t` indicating whether or not the hash was successful.
+ * The handle itself is not modified, so it can be reused for as many hashes as you like
+ *
+ * Arguments (from the perspective of C code):
+ * * Hasher:
+ *     * `hasher` = an `argonautica_hasher_t*` created by `argonautica_hasher_new`
+ * * Encoded:
+ *     * `encoded` = a `char*` that points to a buffer whose length (in bytes) is sufficient
+ *       to hold the resulting string-encoded hash (including it's NULL byte)
+ *     * To determine what the length of the string-encoded hash will be
+ *       ahead of time (including the NULL byte), use the `argonautica_hasher_encoded_len` function
+ * * Password:
+ *     * `password` = a `uint8_t*` pointing to the password buffer
+ *     * `password_len` = a `uint32_t` indicating the number of bytes in the password buffer
+ *     * If password clearing has been configured (see `argonautica_hasher_configure_password_clearing`),
+ *       this function will zero out the bytes in the password buffer
+ */
+argonautica_error_t argonautica_hasher_hash(const argonautica_hasher_t *hasher,
+                                            char *encoded,
+                                            uint8_t *password,
+                                            uint32_t password_len);
+
+/*
+ * Function that hashes a password without blocking the calling thread. The work is scheduled on
+ * argonautica's internal thread pool (which has as many threads as your machine has logical
+ * cores) and, once it is done, `callback` is invoked with the result and `user_data`.
+ *
+ * Arguments (from the perspective of C code):
+ * * `hasher` = an `argonautica_hasher_t*` created by `argonautica_hasher_new`
+ * * `password` = a `uint8_t*` pointing to the password buffer
+ * * `password_len` = a `uint32_t` indicating the number of bytes in the password buffer
+ * * `cancel_token` = an `argonautica_cancel_token_t*` that can be used to cancel the call
+ *   (see `argonautica_cancel_token_cancel`), or `NULL` if the call need not be cancellable
+ * * `callback` = an `argonautica_hash_callback_t` to invoke upon completion
+ * * `user_data` = a `void*` that is passed, untouched, to `callback`
+ *
+ * Ownership and threading rules:
+ * * If this function returns anything other than `ARGONAUTICA_OK`, no work was scheduled and
+ *   `callback` will <b>not</b> be invoked. Otherwise `callback` will be invoked exactly once
+ * * `callback` is invoked on one of argonautica's worker threads, never on the calling thread.
+ *   It must not block for long, as it occupies a worker thread while it runs
+ * * The configuration, salt, secret key and additional data of `hasher`, as well as the
+ *   password, are copied before this function returns. You are free to modify or free `hasher`
+ *   and the password buffer as soon as this function returns. The copies are zeroed out once
+ *   the work is done
+ * * If password clearing has been configured on `hasher`, the bytes in the password buffer are
+ *   zeroed out before this function returns
+ * * `user_data` is never dereferenced by argonautica. Whatever it points to must stay valid
+ *   until `callback` has been invoked and must be safe to use from another thread
+ */
+argonautica_error_t argonautica_hasher_hash_non_blocking(const argonautica_hasher_t *hasher,
+                                                         uint8_t *password,
+                                                         uint32_t password_len,
+                                                         const argonautica_cancel_token_t *cancel_token,
+                                                         argonautica_hash_callback_t callback,
+                                                         void *user_data);
+
+/*
+ * Function that hashes a password using the configuration, salt, secret key and additional
+ * data held by an `argonautica_hasher_t*`, producing raw hash output instead of a
+ * string-encoded hash. It will modify the provided `hash_raw` struct (and the buffers it points
+ * to) and return an `argonautica_error_t` indicating whether or not the hash was successful
+ *
+ * Arguments (from the perspective of C code):
+ * * Hasher:
+ *     * `hasher` = an `argonautica_hasher_t*` created by `argonautica_hasher_new`
+ * * Hash raw:
+ *     * `hash_raw` = an `argonautica_hash_raw_t*` whose `hash` and `salt` members point to
+ *       caller-provided buffers and whose `hash_len` and `salt_len` members hold the capacity of
+ *       those buffers (at least the configured hash length and salt length respectively)
+ *     * On success, the raw hash bytes and raw salt bytes will be written to those buffers and
+ *       the remaining members will be set to the parameters that were used
+ * * Password:
+ *     * `password` = a `uint8_t*` pointing to the password buffer
+ *     * `password_len` = a `uint32_t` indicating the number of bytes in the password buffer
+ *     * If password clearing has been configured (see `argonautica_hasher_configure_password_clearing`),
+ *       this function will zero out the bytes in the password buffer
+ */
+argonautica_error_t argonautica_hasher_hash_raw(const argonautica_hasher_t *hasher,
+                                                argonautica_hash_raw_t *hash_raw,
+                                                uint8_t *password,
+                                                uint32_t password_len);
+
+/*
+ * Function that determines whether a string-encoded hash should be rehashed because it was
+ * produced with parameters (hash length, iterations, lanes, memory size, salt length, variant
+ * or version) that differ from the current configuration of an `argonautica_hasher_t*`. It will
+ * modify the provided `needs_rehash` int and return an `argonautica_error_t` indicating whether
+ * or not the hash could be decoded
+ *
+ * On success, `needs_rehash` will be modified to be `1` if the hash should be rehashed or `0`
+ * if it is up to date.
+ *
+ * Arguments (from the perspective of C code):
+ * * `hasher` = an `argonautica_hasher_t*` created by `argonautica_hasher_new`
+ * * `needs_rehash` = an `int*` that will be set to the result
+ * * `encoded` = a `char*` pointing to the string-encoded hash
+ */
+argonautica_error_t argonautica_hasher_needs_rehash(const argonautica_hasher_t *hasher,
+                                                    int *needs_rehash,
+                                                    const char *encoded);
+
+/*
+ * Function that creates a new `argonautica_hasher_t*` with the same default configuration
+ * as a Rust `Hasher`. The handle must eventually be released with `argonautica_hasher_free`
+ *
+ * Note that, as with a Rust `Hasher`, hashing will fail unless you either provide a secret key
+ * (`argonautica_hasher_with_secret_key`) or opt out of using one
+ * (`argonautica_hasher_opt_out_of_secret_key`)
+ */
+argonautica_hasher_t *argonautica_hasher_new(void);
+
+/*
+ * Function that opts an `argonautica_hasher_t*` out of using a secret key. If `boolean` is
+ * any value other than zero, hashing without a secret key will be allowed
+ */
+argonautica_error_t argonautica_hasher_opt_out_of_secret_key(argonautica_hasher_t *hasher,
+                                                             int boolean);
+
+/*
+ * Function that provides an `argonautica_hasher_t*` with additional data
+ * * To hash with additional data:
+ *     * `additional_data` = a `uint8_t*` pointing to the additional data buffer
+ *     * `additional_data_len` = a `uint32_t` indicating the number of bytes in the additional data buffer
+ *     * The bytes are copied into the handle; this function will not modify the additional data buffer
+ * * To hash <b>without</b> additional data (i.e. to remove previously provided additional data):
+ *     * `additional_data` = `NULL`
+ *     * `additional_data_len` = `0`
+ */
+argonautica_error_t argonautica_hasher_with_additional_data(argonautica_hasher_t *hasher,
+                                                            const uint8_t *additional_data,
+                                                            uint32_t additional_data_len);
+
+/*
+ * Function that provides an `argonautica_hasher_t*` with a salt
+ * * To hash with a random salt (which is recommended):
+ *     * `salt` = `NULL`
+ *     * `salt_len` = a `uint32_t` indicating the length of the salt (in number of bytes)
+ *     * A new random salt will be generated every time the handle is used to hash
+ * * To hash with a deterministic salt (which is not recommended)
+ *     * `salt` = a `uint8_t*` pointing to the salt buffer
+ *     * `salt_len` = a `uint32_t` indicating the number of bytes in the salt buffer
+ *     * The bytes are copied into the handle; this function will not modify the salt buffer
+ */
+argonautica_error_t argonautica_hasher_with_salt(argonautica_hasher_t *hasher,
+                                                 const uint8_t *salt,
+                                                 uint32_t salt_len);
+
+/*
+ * Function that provides an `argonautica_hasher_t*` with a secret key
+ * * To hash with a secret key (which is recommended):
+ *     * `secret_key` = a `uint8_t*` pointing to the secret key buffer
+ *     * `secret_key_len` = a `uint32_t` indicating the number of bytes in the secret key buffer
+ *     * The bytes are copied into the handle, so you are free to zero out and / or free the
+ *       secret key buffer as soon as this function returns. The handle's copy is zeroed out
+ *       when the handle is freed
+ * * To remove a previously provided secret key:
+ *     * `secret_key` = `NULL`
+ *     * `secret_key_len` = `0`
+ */
+argonautica_error_t argonautica_hasher_with_secret_key(argonautica_hasher_t *hasher,
+                                                       const uint8_t *secret_key,
+                                                       uint32_t secret_key_len);
+
+/*
+ * Function that frees an `argonautica_key_set_t*` created by `argonautica_key_set_new`.
+ * Passing `NULL` is a no-op
+ */
+void argonautica_key_set_free(argonautica_key_set_t *key_set);
+
+/*
+ * Function that adds a secret key to an `argonautica_key_set_t*`
+ * * `id` = a `char*` pointing to the key ID, which must be non-empty and consist only of
+ *   ASCII letters, digits, `-`, `_` and `.`, and must not already be in the set
+ * * `secret_key` = a `uint8_t*` pointing to the secret key buffer
+ * * `secret_key_len` = a `uint32_t` indicating the number of bytes in the secret key buffer
+ *   (at least `16`)
+ * * The bytes are copied into the handle, so you are free to zero out and / or free the
+ *   secret key buffer as soon as this function returns
+ * * `created` = a `uint64_t` indicating when the key was created, in seconds since the Unix
+ *   epoch. The most recently created key is the current one
+ */
+argonautica_error_t argonautica_key_set_insert(argonautica_key_set_t *key_set,
+                                               const char *id,
+                                               const uint8_t *secret_key,
+                                               uint32_t secret_key_len,
+                                               uint64_t created);
+
+/*
+ * Function that reads every key file (i.e. every file with the extension `.key`) in a
+ * directory into an `argonautica_key_set_t*`
+ * * `dir` = a `char*` pointing to the path of the directory
+ * * On Unix, the key files must not be accessible by users other than their owner
+ * * If any key file cannot be read, or has the key ID of a key already in the set, no key is
+ *   added to the set
+ */
+argonautica_error_t argonautica_key_set_load_dir(argonautica_key_set_t *key_set, const char *dir);
+
+/*
+ * Function that creates a new, empty `argonautica_key_set_t*`. The handle must eventually be
+ * released with `argonautica_key_set_free`
+ */
+argonautica_key_set_t *argonautica_key_set_new(void);
+
+/*
+ * Function that configures the backend of an `argonautica_verifier_t*`
+ * * `backend` = `ARGONAUTICA_C` for the C backend
+ * * `backend` = `ARGONAUTICA_RUST` for the Rust backend
+ */
+argonautica_error_t argonautica_verifier_configure_backend(argonautica_verifier_t *verifier,
+                                                           argonautica_backend_t backend);
+
+/*
+ * Function that configures password clearing for an `argonautica_verifier_t*`. If
+ * `password_clearing` is any value other than zero, `argonautica_verifier_verify` will zero
+ * out the bytes in the password buffer it is given
+ */
+argonautica_error_t argonautica_verifier_configure_password_clearing(argonautica_verifier_t *verifier,
+                                                                     int password_clearing);
+
+/*
+ * Function that provides an `argonautica_verifier_t*` with the configuration of the
+ * `argonautica_hasher_t*` you currently hash passwords with, so that
+ * `argonautica_verifier_verify_detailed` can recommend rehashing passwords whose hashes were
+ * produced with different parameters (hash length, iterations, lanes, memory size, variant or
+ * version; the salt length is not compared)
+ * * The configuration is copied into the verifier, so the hasher may be freed as soon as this
+ *   function returns
+ * * Pass `NULL` to stop recommending rehashes
+ */
+argonautica_error_t argonautica_verifier_configure_rehash_hasher(argonautica_verifier_t *verifier,
+                                                                 const argonautica_hasher_t *hasher);
+
+/*
+ * Function that configures the number of threads of an `argonautica_verifier_t*`
+ */
+argonautica_error_t argonautica_verifier_configure_threads(argonautica_verifier_t *verifier,
+                                                           uint32_t threads);
+
+/*
+ * Function that frees an `argonautica_verifier_t*` created by `argonautica_verifier_new`.
+ * Any secret key held by the handle is zeroed out before its memory is released.
+ * Passing `NULL` is a no-op
+ */
+void argonautica_verifier_free(argonautica_verifier_t *verifier);
+
+/*
+ * Function that creates a new `argonautica_verifier_t*` with the same default configuration
+ * as a Rust `Verifier`. The handle must eventually be released with `argonautica_verifier_free`
+ */
+argonautica_verifier_t *argonautica_verifier_new(void);
+
+/*
+ * Function that verifies a password against a hash using the configuration, secret key and
+ * additional data held by an `argonautica_verifier_t*`. It will modify the provided `is_valid`
+ * int and return an `argonautica_error_t` indicating whether or not the verification was
+ * successful. The handle itself is not modified, so it can be reused for as many
+ * verifications as you like
+ *
+ * On success, `is_valid` will be modified to be `1` if the hash / password combination is valid
+ * or `0` if the hash / password combination is not valid.
+ *
+ * Arguments (from the perspective of C code):
+ * * `verifier` = an `argonautica_verifier_t*` created by `argonautica_verifier_new`
+ * * `is_valid` = an `int*` that will be set to the result of the verification
+ * * `encoded` = a `char*` pointing to the string-encoded hash
+ * * `password` = a `uint8_t*` pointing to the password buffer
+ * * `password_len` = a `uint32_t` indicating the number of bytes in the password buffer
+ *     * If password clearing has been configured (see `argonautica_verifier_configure_password_clearing`),
+ *       this function will zero out the bytes in the password buffer
+ */
+argonautica_error_t argonautica_verifier_verify(const argonautica_verifier_t *verifier,
+                                                int *is_valid,
+                                                const char *encoded,
+                                                uint8_t *password,
+                                                uint32_t password_len);
+
+/*
+ * Function that verifies a password against a hash just like `argonautica_verifier_verify`,
+ * but reports the detailed outcome in the provided `argonautica_verification_t` instead of
+ * in an `int`. It will modify the provided `verification` struct and return an
+ * `argonautica_error_t` indicating whether or not the verification was successful
+ *
+ * Arguments (from the perspective of C code):
+ * * `verifier` = an `argonautica_verifier_t*` created by `argonautica_verifier_new`
+ * * `verification` = an `argonautica_verification_t*` that will be set to the outcome of the
+ *   verification
+ * * For a description of the other arguments, see the documentation for
+ *   `argonautica_verifier_verify`
+ */
+argonautica_error_t argonautica_verifier_verify_detailed(const argonautica_verifier_t *verifier,
+                                                         argonautica_verification_t *verification,
+                                                         const char *encoded,
+                                                         uint8_t *password,
+                                                         uint32_t password_len);
+
+/*
+ * Function that verifies a password against a hash without blocking the calling thread. The
+ * work is scheduled on argonautica's internal thread pool (which has as many threads as your
+ * machine has logical cores) and, once it is done, `callback` is invoked with the result and
+ * `user_data`.
+ *
+ * Arguments (from the perspective of C code):
+ * * `verifier` = an `argonautica_verifier_t*` created by `argonautica_verifier_new`
+ * * `encoded` = a `char*` pointing to the string-encoded hash
+ * * `password` = a `uint8_t*` pointing to the password buffer
+ * * `password_len` = a `uint32_t` indicating the number of bytes in the password buffer
+ * * `cancel_token` = an `argonautica_cancel_token_t*` that can be used to cancel the call
+ *   (see `argonautica_cancel_token_cancel`), or `NULL` if the call need not be cancellable
+ * * `callback` = an `argonautica_verify_callback_t` to invoke upon completion
+ * * `user_data` = a `void*` that is passed, untouched, to `callback`
+ *
+ * Ownership and threading rules are the same as for `argonautica_hasher_hash_non_blocking`:
+ * `verifier`, `encoded` and the password are copied before this function returns, `callback`
+ * is invoked exactly once on one of argonautica's worker threads if (and only if) this function
+ * returns `ARGONAUTICA_OK`, `user_data` must stay valid until then, and cancellation works the
+ * same way
+ */
+argonautica_error_t argonautica_verifier_verify_non_blocking(const argonautica_verifier_t *verifier,
+                                                             const char *encoded,
+                                                             uint8_t *password,
+                                                             uint32_t password_len,
+                                                             const argonautica_cancel_token_t *cancel_token,
+                                                             argonautica_verify_callback_t callback,
+                                                             void *user_data);
+
+/*
+ * Function that verifies a password against a hash just like
+ * `argonautica_verifier_verify_detailed`, except that, instead of the secret key held by the
+ * `argonautica_verifier_t*`, it tries each key of the provided `argonautica_key_set_t*`,
+ * starting with the current one, and sets the `key_id` of the `argonautica_verification_t`
+ * to the ID of the key that matched. Rehashing is also recommended if the key that matched
+ * is not the current one, i.e. after a key rotation
+ *
+ * Arguments (from the perspective of C code):
+ * * `verifier` = an `argonautica_verifier_t*` created by `argonautica_verifier_new`
+ * * `key_set` = an `argonautica_key_set_t*` created by `argonautica_key_set_new`. An empty
+ *   key set returns `ARGONAUTICA_ERROR_SECRET_KEY_MISSING`
+ * * For a description of the other arguments, see the documentation for
+ *   `argonautica_verifier_verify_detailed`
+ */
+argonautica_error_t argonautica_verifier_verify_with_key_set(const argonautica_verifier_t *verifier,
+                                                             const argonautica_key_set_t *key_set,
+                                                             argonautica_verification_t *verification,
+                                                             const char *encoded,
+                                                             uint8_t *password,
+                                                             uint32_t password_len);
+
+/*
+ * Function that provides an `argonautica_verifier_t*` with the additional data that was
+ * originally used to create the hashes it will verify
+ * * `additional_data` = a `uint8_t*` pointing to the additional data buffer
+ * * `additional_data_len` = a `uint32_t` indicating the number of bytes in the additional data buffer
+ * * The bytes are copied into the handle; this function will not modify the additional data buffer
+ * * Pass `NULL` and `0` to remove previously provided additional data
+ */
+argonautica_error_t argonautica_verifier_with_additional_data(argonautica_verifier_t *verifier,
+                                                              const uint8_t *additional_data,
+                                                              uint32_t additional_data_len);
+
+/*
+ * Function that provides an `argonautica_verifier_t*` with the secret key that was
+ * originally used to create the hashes it will verify
+ * * `secret_key` = a `uint8_t*` pointing to the secret key buffer
+ * * `secret_key_len` = a `uint32_t` indicating the number of bytes in the secret key buffer
+ * * The bytes are copied into the handle, so you are free to zero out and / or free the
+ *   secret key buffer as soon as this function returns. The handle's copy is zeroed out
+ *   when the handle is freed
+ * * Pass `NULL` and `0` to remove a previously provided secret key
+ */
+argonautica_error_t argonautica_verifier_with_secret_key(argonautica_verifier_t *verifier,
+                                                         const uint8_t *secret_key,
+                                                         uint32_t secret_key_len);
+
 /*
  * Function that verifies a password against a hash. It will modify the provided `is_valid` int
  * and return an `argonautica_error_t` indicating whether or not the verification was successful.
@@ -1,14 +1,57 @@
 #![allow(non_camel_case_types)]
 
+use std::cell::RefCell;
+use std::ffi::CString;
+
 use argonautica::Error;
 use libc::c_char;
 
+thread_local! {
+    static LAST_ERROR_DETAIL: RefCell<Option<CString>> = RefCell::new(None);
+}
+
 /// Given an `argonautica_error_t`, this function will return an error message as a static `char*`
 #[no_mangle]
 pub extern "C" fn argonautica_error_msg(err: argonautica_error_t) -> *const c_char {
     err.to_str()
 }
 
+/// Function that returns a detailed error message (as a `char*`) describing the most recent error
+/// that occurred on the calling thread, or `NULL` if no error has occurred on the calling thread.
+///
+/// Unlike the static message returned by `argonautica_error_msg`, the detailed message includes
+/// context such as which length was too long or which part of a hash failed to decode.
+///
+/// The detailed message is only updated when a function returns an `argonautica_error_t` other
+/// than `ARGONAUTICA_OK` (or passes one to a callback), so call this function right after the
+/// failure. Errors from non-blocking functions are recorded on the worker thread that invokes
+/// the callback, i.e. call this function from within the callback.
+///
+/// The returned `char*` is owned by argonautica and stays valid until the next error occurs on
+/// the calling thread or `argonautica_error_clear` is called. Do not free it
+#[no_mangle]
+pub extern "C" fn argonautica_error_detail() -> *const c_char {
+    LAST_ERROR_DETAIL.with(|detail| match *detail.borrow() {
+        Some(ref detail) => detail.as_ptr(),
+        None => ::std::ptr::null(),
+    })
+}
+
+/// Function that clears the detailed error message of the calling thread, after which
+/// `argonautica_error_detail` will return `NULL` until another error occurs
+#[no_mangle]
+pub extern "C" fn argonautica_error_clear() {
+    LAST_ERROR_DETAIL.with(|detail| *detail.borrow_mut() = None);
+}
+
+/// Records the full display string of `err` as the calling thread's detailed error message
+/// and returns the corresponding `argonautica_error_t`
+pub(crate) fn set_last_error(err: Error) -> argonautica_error_t {
+    let detail = err.to_string();
+    let code: argonautica_error_t = err.into();
+    code.with_detail(detail)
+}
+
 /// Argonautica errors
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[repr(C)]
@@ -90,9 +133,63 @@ pub enum argonautica_error_t {
 
     /// Utf-8 encode error. Bytes provided could not be encoded into utf-8
     ARGONAUTICA_ERROR_UTF8_ENCODE = 25,
+
+    /// Buffer too small. A caller-provided buffer was too small to hold the output
+    ARGONAUTICA_ERROR_BUFFER_TOO_SMALL = 26,
+
+    /// Cancelled. The operation was cancelled before it completed
+    ARGONAUTICA_ERROR_CANCELLED = 27,
+
+    /// Inner digest invalid. The password provided is not a valid digest of the configured inner scheme
+    ARGONAUTICA_ERROR_INNER_DIGEST_INVALID = 28,
+
+    /// Password normalization error. The password contains characters that are not allowed by the configured normalization
+    ARGONAUTICA_ERROR_PASSWORD_NORMALIZATION = 29,
+
+    /// Additional data mismatch. The hash is bound to additional data other than the additional data provided
+    ARGONAUTICA_ERROR_ADDITIONAL_DATA_MISMATCH = 30,
+
+    /// Additional data missing. The hash is bound to additional data, but none was provided
+    ARGONAUTICA_ERROR_ADDITIONAL_DATA_MISSING = 31,
+
+    /// Secret key mismatch. The hash is bound to a secret key other than the secret key provided
+    ARGONAUTICA_ERROR_SECRET_KEY_MISMATCH = 32,
+
+    /// Secret key missing. Attempted to hash without a secret key (and without opting out of one) or to verify a bound hash without one
+    ARGONAUTICA_ERROR_SECRET_KEY_MISSING = 33,
+
+    /// I/O error. Failed to read a file or directory
+    ARGONAUTICA_ERROR_IO = 34,
+
+    /// Key file invalid. A key file or key ID is malformed, or a key set already contains a key with the same key ID
+    ARGONAUTICA_ERROR_KEY_FILE_INVALID = 35,
+
+    /// Secret key too short. Length in bytes must be at least 16
+    ARGONAUTICA_ERROR_SECRET_KEY_TOO_SHORT = 36,
+
+    /// Secret key permissions error. A key file is accessible by users other than its owner
+    ARGONAUTICA_ERROR_SECRET_KEY_PERMISSIONS = 37,
 }
 
 impl argonautica_error_t {
+    /// Records this error's static message as the calling thread's detailed error message
+    pub(crate) fn record(self) -> argonautica_error_t {
+        let msg = unsafe { ::std::ffi::CStr::from_ptr(self.to_str()) };
+        self.with_detail(msg.to_string_lossy().into_owned())
+    }
+
+    /// Records `detail` as the calling thread's detailed error message
+    pub(crate) fn with_detail<S>(self, detail: S) -> argonautica_error_t
+    where
+        S: Into<Vec<u8>>,
+    {
+        let mut detail = detail.into();
+        detail.retain(|&b| b != 0);
+        let detail = CString::new(detail).unwrap();
+        LAST_ERROR_DETAIL.with(|last| *last.borrow_mut() = Some(detail));
+        self
+    }
+
     #[cfg_attr(rustfmt, rustfmt_skip)]
     fn to_str(&self) -> *const c_char {
         use argonautica_error_t::*;
@@ -123,6 +220,18 @@ impl argonautica_error_t {
             ARGONAUTICA_ERROR_THREADS_TOO_FEW => b"Threads too few. Threads must be greater than 0\0",
             ARGONAUTICA_ERROR_THREADS_TOO_MANY => b"Threads too many. Threads must be less than 2^24\0",
             ARGONAUTICA_ERROR_UTF8_ENCODE => b"Utf-8 encode error. Bytes provided could not be encoded into utf-8\0",
+            ARGONAUTICA_ERROR_BUFFER_TOO_SMALL => b"Buffer too small. A caller-provided buffer was too small to hold the output\0",
+            ARGONAUTICA_ERROR_CANCELLED => b"Cancelled. The operation was cancelled before it completed\0",
+            ARGONAUTICA_ERROR_INNER_DIGEST_INVALID => b"Inner digest invalid. The password provided is not a valid digest of the configured inner scheme\0",
+            ARGONAUTICA_ERROR_PASSWORD_NORMALIZATION => b"Password normalization error. The password contains characters that are not allowed by the configured normalization\0",
+            ARGONAUTICA_ERROR_ADDITIONAL_DATA_MISMATCH => b"Additional data mismatch. The hash is bound to additional data other than the additional data provided\0",
+            ARGONAUTICA_ERROR_ADDITIONAL_DATA_MISSING => b"Additional data missing. The hash is bound to additional data, but none was provided\0",
+            ARGONAUTICA_ERROR_SECRET_KEY_MISMATCH => b"Secret key mismatch. The hash is bound to a secret key other than the secret key provided\0",
+            ARGONAUTICA_ERROR_SECRET_KEY_MISSING => b"Secret key missing. Attempted to hash without a secret key (and without opting out of one) or to verify a bound hash without one\0",
+            ARGONAUTICA_ERROR_IO => b"I/O error. Failed to read a file or directory\0",
+            ARGONAUTICA_ERROR_KEY_FILE_INVALID => b"Key file invalid. A key file or key ID is malformed, or a key set already contains a key with the same key ID\0",
+            ARGONAUTICA_ERROR_SECRET_KEY_TOO_SHORT => b"Secret key too short. Length in bytes must be at least 16\0",
+            ARGONAUTICA_ERROR_SECRET_KEY_PERMISSIONS => b"Secret key permissions error. A key file is accessible by users other than its owner\0",
         };
         s.as_ptr() as *const c_char
     }
@@ -133,30 +242,45 @@ impl From<Error> for argonautica_error_t {
         use argonautica::ErrorKind::*;
         use argonautica_error_t::*;
         match err.kind() {
+            AdditionalDataMismatchError => ARGONAUTICA_ERROR_ADDITIONAL_DATA_MISMATCH,
+            AdditionalDataMissingError => ARGONAUTICA_ERROR_ADDITIONAL_DATA_MISSING,
             AdditionalDataTooLongError => ARGONAUTICA_ERROR_ADDITIONAL_DATA_TOO_LONG,
             BackendEncodeError => ARGONAUTICA_ERROR_BUG,
             BackendUnsupportedError => ARGONAUTICA_ERROR_BACKEND_UNSUPPORTED,
             Base64DecodeError => ARGONAUTICA_ERROR_BASE64_DECODE,
             Bug => ARGONAUTICA_ERROR_BUG,
+            ConfigurationError => ARGONAUTICA_ERROR_BUG,
+            EnvVarError => ARGONAUTICA_ERROR_BUG,
             HashDecodeError => ARGONAUTICA_ERROR_HASH_DECODE,
             HashLenTooShortError => ARGONAUTICA_ERROR_HASH_LEN_TOO_SHORT,
             HashMissingError => ARGONAUTICA_ERROR_HASH_MISSING,
+            HexDecodeError => ARGONAUTICA_ERROR_BUG,
+            InnerDigestInvalidError => ARGONAUTICA_ERROR_INNER_DIGEST_INVALID,
+            IoError => ARGONAUTICA_ERROR_IO,
             IterationsTooFewError => ARGONAUTICA_ERROR_ITERATIONS_TOO_FEW,
+            KeyFileInvalidError => ARGONAUTICA_ERROR_KEY_FILE_INVALID,
             LanesTooFewError => ARGONAUTICA_ERROR_LANES_TOO_FEW,
             LanesTooManyError => ARGONAUTICA_ERROR_LANES_TOO_MANY,
             MemoryAllocationError => ARGONAUTICA_ERROR_MEMORY_ALLOCATION,
             MemorySizeInvalidError => ARGONAUTICA_ERROR_MEMORY_SIZE_INVALID,
             MemorySizeTooSmallError => ARGONAUTICA_ERROR_MEMORY_SIZE_TOO_SMALL,
             OsRngError => ARGONAUTICA_ERROR_OS_RNG,
+            PasswordBreachedError(_) => ARGONAUTICA_ERROR_BUG,
             PasswordImmutableError => ARGONAUTICA_ERROR_BUG,
             PasswordMissingError => ARGONAUTICA_ERROR_PASSWORD_MISSING,
+            PasswordNormalizationError => ARGONAUTICA_ERROR_PASSWORD_NORMALIZATION,
+            PasswordPolicyError(_) => ARGONAUTICA_ERROR_BUG,
             PasswordTooLongError => ARGONAUTICA_ERROR_PASSWORD_TOO_LONG,
             PasswordTooShortError => ARGONAUTICA_ERROR_PASSWORD_TOO_SHORT,
+            RngError => ARGONAUTICA_ERROR_BUG,
             SaltTooLongError => ARGONAUTICA_ERROR_SALT_TOO_LONG,
             SaltTooShortError => ARGONAUTICA_ERROR_SALT_TOO_SHORT,
             SecretKeyImmutableError => ARGONAUTICA_ERROR_BUG,
-            SecretKeyMissingError => ARGONAUTICA_ERROR_BUG,
+            SecretKeyMismatchError => ARGONAUTICA_ERROR_SECRET_KEY_MISMATCH,
+            SecretKeyMissingError => ARGONAUTICA_ERROR_SECRET_KEY_MISSING,
+            SecretKeyPermissionsError => ARGONAUTICA_ERROR_SECRET_KEY_PERMISSIONS,
             SecretKeyTooLongError => ARGONAUTICA_ERROR_SECRET_KEY_TOO_LONG,
+            SecretKeyTooShortError => ARGONAUTICA_ERROR_SECRET_KEY_TOO_SHORT,
             ThreadError => ARGONAUTICA_ERROR_THREAD,
             ThreadsTooFewError => ARGONAUTICA_ERROR_THREADS_TOO_FEW,
             ThreadsTooManyError => ARGONAUTICA_ERROR_THREADS_TOO_MANY,
@@ -167,3 +291,68 @@ impl From<Error> for argonautica_error_t {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+
+    use super::*;
+    use {
+        argonautica_decode, argonautica_hasher_configure_iterations, argonautica_hasher_free,
+        argonautica_hasher_hash, argonautica_hasher_new, argonautica_hasher_opt_out_of_secret_key,
+    };
+
+    fn detail() -> Option<String> {
+        let ptr = argonautica_error_detail();
+        if ptr.is_null() {
+            return None;
+        }
+        let detail = unsafe { CStr::from_ptr(ptr) };
+        Some(detail.to_str().unwrap().to_string())
+    }
+
+    #[test]
+    fn test_error_detail() {
+        argonautica_error_clear();
+        assert_eq!(detail(), None);
+
+        let err = argonautica_decode(::std::ptr::null_mut(), ::std::ptr::null());
+        assert_eq!(err, argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR);
+        assert_eq!(
+            detail().unwrap(),
+            "Null pointer error. Passed a null pointer as an argument where that is not allowed"
+        );
+
+        let hasher = argonautica_hasher_new();
+        argonautica_hasher_configure_iterations(hasher, 0);
+        argonautica_hasher_opt_out_of_secret_key(hasher, 1);
+        let mut encoded = vec![0 as c_char; 256];
+        let mut password = b"P@ssw0rd".to_vec();
+        let err = argonautica_hasher_hash(
+            hasher,
+            encoded.as_mut_ptr(),
+            password.as_mut_ptr(),
+            password.len() as u32,
+        );
+        assert_eq!(
+            err,
+            argonautica_error_t::ARGONAUTICA_ERROR_ITERATIONS_TOO_FEW
+        );
+        assert_eq!(
+            detail().unwrap(),
+            "Iterations must be greater than 0: Iterations: 0"
+        );
+        argonautica_hasher_free(hasher);
+
+        argonautica_error_clear();
+        assert_eq!(detail(), None);
+    }
+
+    #[test]
+    fn test_error_detail_strips_nul_bytes() {
+        let err = argonautica_error_t::ARGONAUTICA_ERROR_BUG.with_detail("a\0b");
+        assert_eq!(err, argonautica_error_t::ARGONAUTICA_ERROR_BUG);
+        assert_eq!(detail().unwrap(), "ab");
+        argonautica_error_clear();
+    }
+}
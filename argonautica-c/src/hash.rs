@@ -7,6 +7,7 @@ use argonautica::input::Salt;
 use argonautica::Hasher;
 use libc::{c_char, c_int};
 
+use error::set_last_error;
 use {argonautica_backend_t, argonautica_error_t, argonautica_variant_t, argonautica_version_t};
 
 /// Function that hashes a password. It will modify the provided `encoded` buffer
@@ -94,7 +95,7 @@ pub extern "C" fn argonautica_hash(
     version: argonautica_version_t,
 ) -> argonautica_error_t {
     if encoded.is_null() || password.is_null() {
-        return argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR;
+        return argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR.record();
     }
 
     let backend: Backend = backend.into();
@@ -150,7 +151,7 @@ pub extern "C" fn argonautica_hash(
 
     let hash = match hasher.hash() {
         Ok(hash) => hash,
-        Err(e) => return e.into(),
+        Err(e) => return set_last_error(e),
     };
 
     let hash_cstring = CString::new(hash.as_bytes()).unwrap();
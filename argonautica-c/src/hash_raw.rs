@@ -0,0 +1,300 @@
+#![allow(non_camel_case_types)]
+
+use std::ffi::{CStr, CString};
+
+use argonautica::output::HashRaw;
+use libc::c_char;
+
+use error::set_last_error;
+use {argonautica_error_t, argonautica_variant_t, argonautica_version_t};
+
+/// Struct representing raw hash output, i.e. the parameters of a hash together with its
+/// raw salt bytes and raw hash bytes.
+///
+/// The `hash` and `salt` buffers are always owned by the caller:
+/// * When this struct is filled in by argonautica (`argonautica_decode` or
+///   `argonautica_hasher_hash_raw`), `hash` and `salt` must point to caller-provided buffers
+///   and `hash_len` and `salt_len` must hold the capacity (in bytes) of those buffers. On success,
+///   the buffers will contain the raw hash bytes and raw salt bytes and `hash_len` and `salt_len`
+///   will be set to the number of bytes written
+/// * When this struct is read by argonautica (`argonautica_encode`), `hash` and `salt` must point
+///   to buffers holding `hash_len` and `salt_len` bytes respectively
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct argonautica_hash_raw_t {
+    /// A `uint8_t*` pointing to the raw hash bytes
+    pub hash: *mut u8,
+
+    /// The number of bytes in the `hash` buffer
+    pub hash_len: u32,
+
+    /// The iterations configuration that was used to produce this hash
+    pub iterations: u32,
+
+    /// The lanes configuration that was used to produce this hash
+    pub lanes: u32,
+
+    /// The memory size configuration (in kibibytes) that was used to produce this hash
+    pub memory_size: u32,
+
+    /// A `uint8_t*` pointing to the raw salt bytes
+    pub salt: *mut u8,
+
+    /// The number of bytes in the `salt` buffer
+    pub salt_len: u32,
+
+    /// The variant that was used to produce this hash
+    pub variant: argonautica_variant_t,
+
+    /// The version that was used to produce this hash
+    pub version: argonautica_version_t,
+}
+
+impl argonautica_hash_raw_t {
+    /// Copies a `HashRaw` into the caller-provided buffers of this struct, failing with
+    /// `ARGONAUTICA_ERROR_BUFFER_TOO_SMALL` if either of them cannot hold the bytes and with
+    /// `ARGONAUTICA_ERROR_HASH_DECODE` if the `HashRaw` has an `inner` or `data` parameter,
+    /// which this struct cannot represent
+    pub(crate) fn fill(&mut self, hash_raw: &HashRaw) -> argonautica_error_t {
+        if self.hash.is_null() || self.salt.is_null() {
+            return argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR.record();
+        }
+        if let Some(inner) = hash_raw.inner() {
+            return argonautica_error_t::ARGONAUTICA_ERROR_HASH_DECODE.with_detail(format!(
+                "Hash decode error. The inner parameter is not supported: inner={}",
+                inner,
+            ));
+        }
+        if hash_raw.data().is_some() {
+            return argonautica_error_t::ARGONAUTICA_ERROR_HASH_DECODE
+                .with_detail("Hash decode error. The data parameter is not supported");
+        }
+        let hash_bytes = hash_raw.raw_hash_bytes();
+        let salt_bytes = hash_raw.raw_salt_bytes();
+        if hash_bytes.len() > self.hash_len as usize {
+            return argonautica_error_t::ARGONAUTICA_ERROR_BUFFER_TOO_SMALL.with_detail(format!(
+                "Buffer too small. Hash buffer holds {} bytes, but {} are required",
+                self.hash_len,
+                hash_bytes.len(),
+            ));
+        }
+        if salt_bytes.len() > self.salt_len as usize {
+            return argonautica_error_t::ARGONAUTICA_ERROR_BUFFER_TOO_SMALL.with_detail(format!(
+                "Buffer too small. Salt buffer holds {} bytes, but {} are required",
+                self.salt_len,
+                salt_bytes.len(),
+            ));
+        }
+        unsafe {
+            ::std::ptr::copy_nonoverlapping(hash_bytes.as_ptr(), self.hash, hash_bytes.len());
+            ::std::ptr::copy_nonoverlapping(salt_bytes.as_ptr(), self.salt, salt_bytes.len());
+        }
+        self.hash_len = hash_bytes.len() as u32;
+        self.iterations = hash_raw.iterations();
+        self.lanes = hash_raw.lanes();
+        self.memory_size = hash_raw.memory_size();
+        self.salt_len = salt_bytes.len() as u32;
+        self.variant = hash_raw.variant().into();
+        self.version = hash_raw.version().into();
+        argonautica_error_t::ARGONAUTICA_OK
+    }
+}
+
+/// Function that decodes a string-encoded hash into an `argonautica_hash_raw_t`. It will modify
+/// the provided `hash_raw` struct (and the buffers it points to) and return an
+/// `argonautica_error_t` indicating whether or not decoding was successful.
+///
+/// Arguments (from the perspective of C code):
+/// * `hash_raw` = an `argonautica_hash_raw_t*` whose `hash` and `salt` members point to
+///   caller-provided buffers and whose `hash_len` and `salt_len` members hold the capacity of those
+///   buffers. Buffers whose length is at least `strlen(encoded)` are always sufficient
+/// * `encoded` = a `char*` pointing to the string-encoded hash
+///
+/// Hashes with an `inner` parameter (onion hashes) or a `data` parameter (e.g. bound hashes)
+/// cannot be represented by an `argonautica_hash_raw_t` and fail with
+/// `ARGONAUTICA_ERROR_HASH_DECODE`
+#[no_mangle]
+pub extern "C" fn argonautica_decode(
+    hash_raw: *mut argonautica_hash_raw_t,
+    encoded: *const c_char,
+) -> argonautica_error_t {
+    if hash_raw.is_null() || encoded.is_null() {
+        return argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR.record();
+    }
+    let encoded_cstr = unsafe { CStr::from_ptr(encoded) };
+    let encoded = match encoded_cstr.to_str() {
+        Ok(encoded) => encoded,
+        Err(_) => return argonautica_error_t::ARGONAUTICA_ERROR_UTF8_ENCODE.record(),
+    };
+    let decoded = match encoded.parse::<HashRaw>() {
+        Ok(decoded) => decoded,
+        Err(e) => return set_last_error(e),
+    };
+    let hash_raw = unsafe { &mut *hash_raw };
+    hash_raw.fill(&decoded)
+}
+
+/// Function that encodes an `argonautica_hash_raw_t` into a string-encoded hash. It will modify
+/// the provided `encoded` buffer and return an `argonautica_error_t` indicating whether or not
+/// encoding was successful.
+///
+/// Arguments (from the perspective of C code):
+/// * `encoded` = a `char*` that points to a buffer whose length (in bytes) is sufficient
+///   to hold the resulting string-encoded hash (including it's NULL byte)
+///     * To determine what the length of the string-encoded hash will be
+///       ahead of time (including the NULL byte), use the `argonautica_encoded_len` function
+/// * `hash_raw` = an `argonautica_hash_raw_t*` whose `hash` and `salt` members point to buffers
+///   holding `hash_len` and `salt_len` bytes respectively. This function will not modify it
+#[no_mangle]
+pub extern "C" fn argonautica_encode(
+    encoded: *mut c_char,
+    hash_raw: *const argonautica_hash_raw_t,
+) -> argonautica_error_t {
+    if encoded.is_null() || hash_raw.is_null() {
+        return argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR.record();
+    }
+    let hash_raw = unsafe { &*hash_raw };
+    if hash_raw.hash.is_null() || hash_raw.salt.is_null() {
+        return argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR.record();
+    }
+    let hash_bytes =
+        unsafe { ::std::slice::from_raw_parts(hash_raw.hash, hash_raw.hash_len as usize) };
+    let salt_bytes =
+        unsafe { ::std::slice::from_raw_parts(hash_raw.salt, hash_raw.salt_len as usize) };
+    let hash = HashRaw::new(
+        /* iterations */ hash_raw.iterations,
+        /* lanes */ hash_raw.lanes,
+        /* memory_size */ hash_raw.memory_size,
+        /* raw_hash_bytes */ hash_bytes.to_vec(),
+        /* raw_salt_bytes */ salt_bytes.to_vec(),
+        /* variant */ hash_raw.variant.into(),
+        /* version */ hash_raw.version.into(),
+    )
+    .to_string();
+
+    let hash_cstring = CString::new(hash.as_bytes()).unwrap();
+    let hash_cstring_len = hash_cstring.as_bytes_with_nul().len();
+    let hash_ptr = hash_cstring.as_ptr();
+
+    unsafe {
+        ::std::ptr::copy_nonoverlapping(hash_ptr, encoded, hash_cstring_len);
+    }
+
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use argonautica::Hasher;
+
+    use super::*;
+    use {argonautica_encoded_len, argonautica_error_detail};
+
+    fn empty_hash_raw(hash: &mut [u8], salt: &mut [u8]) -> argonautica_hash_raw_t {
+        argonautica_hash_raw_t {
+            hash: hash.as_mut_ptr(),
+            hash_len: hash.len() as u32,
+            iterations: 0,
+            lanes: 0,
+            memory_size: 0,
+            salt: salt.as_mut_ptr(),
+            salt_len: salt.len() as u32,
+            variant: argonautica_variant_t::ARGONAUTICA_ARGON2ID,
+            version: argonautica_version_t::ARGONAUTICA_0x13,
+        }
+    }
+
+    #[test]
+    fn test_decode_encode_round_trip() {
+        let hash = Hasher::default()
+            .configure_iterations(8)
+            .configure_lanes(2)
+            .configure_memory_size(64)
+            .configure_variant(argonautica::config::Variant::Argon2i)
+            .opt_out_of_secret_key(true)
+            .with_password("P@ssw0rd")
+            .hash()
+            .unwrap();
+        let expected = hash.parse::<HashRaw>().unwrap();
+        let encoded = CString::new(hash.clone()).unwrap();
+
+        let mut hash_buf = vec![0u8; hash.len()];
+        let mut salt_buf = vec![0u8; hash.len()];
+        let mut hash_raw = empty_hash_raw(&mut hash_buf, &mut salt_buf);
+        let err = argonautica_decode(&mut hash_raw, encoded.as_ptr());
+        assert_eq!(err, argonautica_error_t::ARGONAUTICA_OK);
+        assert_eq!(hash_raw.iterations, 8);
+        assert_eq!(hash_raw.lanes, 2);
+        assert_eq!(hash_raw.memory_size, 64);
+        assert_eq!(hash_raw.variant, argonautica_variant_t::ARGONAUTICA_ARGON2I);
+        assert_eq!(hash_raw.version, argonautica_version_t::ARGONAUTICA_0x13);
+        assert_eq!(
+            &hash_buf[..hash_raw.hash_len as usize],
+            expected.raw_hash_bytes()
+        );
+        assert_eq!(
+            &salt_buf[..hash_raw.salt_len as usize],
+            expected.raw_salt_bytes()
+        );
+
+        let encoded_len = argonautica_encoded_len(
+            hash_raw.hash_len,
+            hash_raw.iterations,
+            hash_raw.lanes,
+            hash_raw.memory_size,
+            hash_raw.salt_len,
+            hash_raw.variant,
+        );
+        let mut encoded2 = vec![0 as c_char; encoded_len as usize];
+        let err = argonautica_encode(encoded2.as_mut_ptr(), &hash_raw);
+        assert_eq!(err, argonautica_error_t::ARGONAUTICA_OK);
+        let encoded2 = unsafe { CStr::from_ptr(encoded2.as_ptr()) };
+        assert_eq!(encoded2.to_str().unwrap(), hash.as_str());
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let mut hash_buf = vec![0u8; 4];
+        let mut salt_buf = vec![0u8; 4];
+        let mut hash_raw = empty_hash_raw(&mut hash_buf, &mut salt_buf);
+
+        let encoded = CString::new("$argon2id$v=19$m=4096,t=128,p=2$c29tZXNhbHQ$iWh06vD8Fy27wf9npn6FXWiCX4K6pW6Ue1Bnzz07Z8A").unwrap();
+        let err = argonautica_decode(&mut hash_raw, encoded.as_ptr());
+        assert_eq!(err, argonautica_error_t::ARGONAUTICA_ERROR_BUFFER_TOO_SMALL);
+        let detail = unsafe { CStr::from_ptr(argonautica_error_detail()) };
+        assert_eq!(
+            detail.to_str().unwrap(),
+            "Buffer too small. Hash buffer holds 4 bytes, but 32 are required"
+        );
+
+        let encoded = CString::new("$argon2id$v=19$m=4096,t=128").unwrap();
+        let err = argonautica_decode(&mut hash_raw, encoded.as_ptr());
+        assert_eq!(err, argonautica_error_t::ARGONAUTICA_ERROR_HASH_DECODE);
+        let detail = unsafe { CStr::from_ptr(argonautica_error_detail()) };
+        assert!(detail.to_str().unwrap().starts_with("Hash decode error"));
+    }
+
+    #[test]
+    fn test_decode_unsupported_params() {
+        let mut hash_buf = vec![0u8; 64];
+        let mut salt_buf = vec![0u8; 64];
+        let mut hash_raw = empty_hash_raw(&mut hash_buf, &mut salt_buf);
+
+        for &(encoded, expected) in &[
+            (
+                "$argon2id$v=19$m=64,t=8,p=2,inner=md5$c29tZXNhbHQ$rYR0S4u1lTvIvtrt9IDtzCAplumWAwyYrbwb7hNnbl0",
+                "Hash decode error. The inner parameter is not supported: inner=md5",
+            ),
+            (
+                "$argon2id$v=19$m=64,t=8,p=2,data=c29tZWRhdGE$c29tZXNhbHQ$rYR0S4u1lTvIvtrt9IDtzCAplumWAwyYrbwb7hNnbl0",
+                "Hash decode error. The data parameter is not supported",
+            ),
+        ] {
+            let encoded = CString::new(encoded).unwrap();
+            let err = argonautica_decode(&mut hash_raw, encoded.as_ptr());
+            assert_eq!(err, argonautica_error_t::ARGONAUTICA_ERROR_HASH_DECODE);
+            let detail = unsafe { CStr::from_ptr(argonautica_error_detail()) };
+            assert_eq!(detail.to_str().unwrap(), expected);
+        }
+    }
+}
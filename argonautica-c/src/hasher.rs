@@ -0,0 +1,605 @@
+#![allow(non_camel_case_types)]
+
+use std::ffi::{CStr, CString};
+use std::fmt;
+
+use argonautica::config::{Backend, Variant, Version};
+use argonautica::input::Salt;
+use argonautica::Hasher;
+use libc::{c_char, c_int};
+
+use error::set_last_error;
+use {
+    argonautica_backend_t, argonautica_encoded_len, argonautica_error_t, argonautica_hash_raw_t,
+    argonautica_variant_t, argonautica_version_t,
+};
+
+/// Opaque handle holding a reusable hasher configuration (including an optional secret key
+/// and additional data). Create one with `argonautica_hasher_new` and release it with
+/// `argonautica_hasher_free`
+pub struct argonautica_hasher_t {
+    pub(crate) additional_data: Option<Vec<u8>>,
+    pub(crate) hasher: Hasher<'static>,
+    pub(crate) secret_key: Option<Vec<u8>>,
+}
+
+impl Drop for argonautica_hasher_t {
+    fn drop(&mut self) {
+        if let Some(ref mut secret_key) = self.secret_key {
+            for b in secret_key.iter_mut() {
+                *b = 0;
+            }
+        }
+    }
+}
+
+impl fmt::Debug for argonautica_hasher_t {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "argonautica_hasher_t {{ hasher: {:?} }}", self.hasher)
+    }
+}
+
+impl argonautica_hasher_t {
+    /// Returns a fresh `Hasher` carrying this handle's configuration, additional data,
+    /// salt and secret key, ready to be given a password
+    pub(crate) fn hasher<'a>(&'a self) -> Hasher<'a> {
+        let mut hasher = self.hasher.to_owned();
+        if let Some(ref additional_data) = self.additional_data {
+            hasher.with_additional_data(&additional_data[..]);
+        }
+        if let Some(ref secret_key) = self.secret_key {
+            hasher.with_secret_key(&secret_key[..]);
+        }
+        hasher
+    }
+}
+
+macro_rules! hasher_mut {
+    ($hasher:expr) => {
+        match unsafe { $hasher.as_mut() } {
+            Some(hasher) => hasher,
+            None => return argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR.record(),
+        }
+    };
+}
+
+/// Function that creates a new `argonautica_hasher_t*` with the same default configuration
+/// as a Rust `Hasher`. The handle must eventually be released with `argonautica_hasher_free`
+///
+/// Note that, as with a Rust `Hasher`, hashing will fail unless you either provide a secret key
+/// (`argonautica_hasher_with_secret_key`) or opt out of using one
+/// (`argonautica_hasher_opt_out_of_secret_key`)
+#[no_mangle]
+pub extern "C" fn argonautica_hasher_new() -> *mut argonautica_hasher_t {
+    let hasher = argonautica_hasher_t {
+        additional_data: None,
+        hasher: Hasher::default(),
+        secret_key: None,
+    };
+    Box::into_raw(Box::new(hasher))
+}
+
+/// Function that frees an `argonautica_hasher_t*` created by `argonautica_hasher_new`.
+/// Any secret key held by the handle is zeroed out before its memory is released.
+/// Passing `NULL` is a no-op
+#[no_mangle]
+pub extern "C" fn argonautica_hasher_free(hasher: *mut argonautica_hasher_t) {
+    if hasher.is_null() {
+        return;
+    }
+    let _ = unsafe { Box::from_raw(hasher) };
+}
+
+/// Function that configures the backend of an `argonautica_hasher_t*`
+/// * `backend` = `ARGONAUTICA_C` for the C backend
+/// * `backend` = `ARGONAUTICA_RUST` for the Rust backend
+#[no_mangle]
+pub extern "C" fn argonautica_hasher_configure_backend(
+    hasher: *mut argonautica_hasher_t,
+    backend: argonautica_backend_t,
+) -> argonautica_error_t {
+    let handle = hasher_mut!(hasher);
+    let backend: Backend = backend.into();
+    handle.hasher.configure_backend(backend);
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+/// Function that configures the hash length (in number of bytes) of an `argonautica_hasher_t*`
+#[no_mangle]
+pub extern "C" fn argonautica_hasher_configure_hash_len(
+    hasher: *mut argonautica_hasher_t,
+    hash_len: u32,
+) -> argonautica_error_t {
+    let handle = hasher_mut!(hasher);
+    handle.hasher.configure_hash_len(hash_len);
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+/// Function that configures the number of iterations of an `argonautica_hasher_t*`
+#[no_mangle]
+pub extern "C" fn argonautica_hasher_configure_iterations(
+    hasher: *mut argonautica_hasher_t,
+    iterations: u32,
+) -> argonautica_error_t {
+    let handle = hasher_mut!(hasher);
+    handle.hasher.configure_iterations(iterations);
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+/// Function that configures the number of lanes of an `argonautica_hasher_t*`
+#[no_mangle]
+pub extern "C" fn argonautica_hasher_configure_lanes(
+    hasher: *mut argonautica_hasher_t,
+    lanes: u32,
+) -> argonautica_error_t {
+    let handle = hasher_mut!(hasher);
+    handle.hasher.configure_lanes(lanes);
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+/// Function that configures the memory size (in kibibytes) of an `argonautica_hasher_t*`
+#[no_mangle]
+pub extern "C" fn argonautica_hasher_configure_memory_size(
+    hasher: *mut argonautica_hasher_t,
+    memory_size: u32,
+) -> argonautica_error_t {
+    let handle = hasher_mut!(hasher);
+    handle.hasher.configure_memory_size(memory_size);
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+/// Function that configures password clearing for an `argonautica_hasher_t*`. If
+/// `password_clearing` is any value other than zero, `argonautica_hasher_hash` will zero out
+/// the bytes in the password buffer it is given
+#[no_mangle]
+pub extern "C" fn argonautica_hasher_configure_password_clearing(
+    hasher: *mut argonautica_hasher_t,
+    password_clearing: c_int,
+) -> argonautica_error_t {
+    let handle = hasher_mut!(hasher);
+    handle
+        .hasher
+        .configure_password_clearing(password_clearing != 0);
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+/// Function that configures the number of threads of an `argonautica_hasher_t*`
+#[no_mangle]
+pub extern "C" fn argonautica_hasher_configure_threads(
+    hasher: *mut argonautica_hasher_t,
+    threads: u32,
+) -> argonautica_error_t {
+    let handle = hasher_mut!(hasher);
+    handle.hasher.configure_threads(threads);
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+/// Function that configures the variant of an `argonautica_hasher_t*`
+/// * `variant` = `ARGONAUTICA_ARGON2D` for argon2d
+/// * `variant` = `ARGONAUTICA_ARGON2I` for argon2i
+/// * `variant` = `ARGONAUTICA_ARGON2ID` for argon2id
+#[no_mangle]
+pub extern "C" fn argonautica_hasher_configure_variant(
+    hasher: *mut argonautica_hasher_t,
+    variant: argonautica_variant_t,
+) -> argonautica_error_t {
+    let handle = hasher_mut!(hasher);
+    let variant: Variant = variant.into();
+    handle.hasher.configure_variant(variant);
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+/// Function that configures the version of an `argonautica_hasher_t*`
+/// * `version` = `ARGONAUTICA_0x10` for 0x10
+/// * `version` = `ARGONAUTICA_0x13` for 0x13
+#[no_mangle]
+pub extern "C" fn argonautica_hasher_configure_version(
+    hasher: *mut argonautica_hasher_t,
+    version: argonautica_version_t,
+) -> argonautica_error_t {
+    let handle = hasher_mut!(hasher);
+    let version: Version = version.into();
+    handle.hasher.configure_version(version);
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+/// Function that opts an `argonautica_hasher_t*` out of using a secret key. If `boolean` is
+/// any value other than zero, hashing without a secret key will be allowed
+#[no_mangle]
+pub extern "C" fn argonautica_hasher_opt_out_of_secret_key(
+    hasher: *mut argonautica_hasher_t,
+    boolean: c_int,
+) -> argonautica_error_t {
+    let handle = hasher_mut!(hasher);
+    handle.hasher.opt_out_of_secret_key(boolean != 0);
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+/// Function that provides an `argonautica_hasher_t*` with additional data
+/// * To hash with additional data:
+///     * `additional_data` = a `uint8_t*` pointing to the additional data buffer
+///     * `additional_data_len` = a `uint32_t` indicating the number of bytes in the additional data buffer
+///     * The bytes are copied into the handle; this function will not modify the additional data buffer
+/// * To hash <b>without</b> additional data (i.e. to remove previously provided additional data):
+///     * `additional_data` = `NULL`
+///     * `additional_data_len` = `0`
+#[no_mangle]
+pub extern "C" fn argonautica_hasher_with_additional_data(
+    hasher: *mut argonautica_hasher_t,
+    additional_data: *const u8,
+    additional_data_len: u32,
+) -> argonautica_error_t {
+    let handle = hasher_mut!(hasher);
+    handle.additional_data = if additional_data.is_null() {
+        None
+    } else {
+        let additional_data =
+            unsafe { ::std::slice::from_raw_parts(additional_data, additional_data_len as usize) };
+        Some(additional_data.to_vec())
+    };
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+/// Function that provides an `argonautica_hasher_t*` with a salt
+/// * To hash with a random salt (which is recommended):
+///     * `salt` = `NULL`
+///     * `salt_len` = a `uint32_t` indicating the length of the salt (in number of bytes)
+///     * A new random salt will be generated every time the handle is used to hash
+/// * To hash with a deterministic salt (which is not recommended)
+///     * `salt` = a `uint8_t*` pointing to the salt buffer
+///     * `salt_len` = a `uint32_t` indicating the number of bytes in the salt buffer
+///     * The bytes are copied into the handle; this function will not modify the salt buffer
+#[no_mangle]
+pub extern "C" fn argonautica_hasher_with_salt(
+    hasher: *mut argonautica_hasher_t,
+    salt: *const u8,
+    salt_len: u32,
+) -> argonautica_error_t {
+    let handle = hasher_mut!(hasher);
+    if salt.is_null() {
+        handle.hasher.with_salt(Salt::random(salt_len));
+    } else {
+        let salt = unsafe { ::std::slice::from_raw_parts(salt, salt_len as usize) };
+        handle.hasher.with_salt(salt);
+    }
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+/// Function that provides an `argonautica_hasher_t*` with a secret key
+/// * To hash with a secret key (which is recommended):
+///     * `secret_key` = a `uint8_t*` pointing to the secret key buffer
+///     * `secret_key_len` = a `uint32_t` indicating the number of bytes in the secret key buffer
+///     * The bytes are copied into the handle, so you are free to zero out and / or free the
+///       secret key buffer as soon as this function returns. The handle's copy is zeroed out
+///       when the handle is freed
+/// * To remove a previously provided secret key:
+///     * `secret_key` = `NULL`
+///     * `secret_key_len` = `0`
+#[no_mangle]
+pub extern "C" fn argonautica_hasher_with_secret_key(
+    hasher: *mut argonautica_hasher_t,
+    secret_key: *const u8,
+    secret_key_len: u32,
+) -> argonautica_error_t {
+    let handle = hasher_mut!(hasher);
+    if let Some(ref mut old) = handle.secret_key {
+        for b in old.iter_mut() {
+            *b = 0;
+        }
+    }
+    handle.secret_key = if secret_key.is_null() {
+        None
+    } else {
+        let secret_key =
+            unsafe { ::std::slice::from_raw_parts(secret_key, secret_key_len as usize) };
+        Some(secret_key.to_vec())
+    };
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+/// Function that returns the length of the string-encoded hash (in bytes and including the
+/// NULL byte) that `argonautica_hasher_hash` will produce given the current configuration
+/// of an `argonautica_hasher_t*`. If an error occurrs, the function returns -1
+#[no_mangle]
+pub extern "C" fn argonautica_hasher_encoded_len(hasher: *const argonautica_hasher_t) -> c_int {
+    let handle = match unsafe { hasher.as_ref() } {
+        Some(handle) => handle,
+        None => return -1,
+    };
+    let config = handle.hasher.config();
+    argonautica_encoded_len(
+        /* hash_len */ config.hash_len(),
+        /* iterations */ config.iterations(),
+        /* lanes */ config.lanes(),
+        /* memory_size */ config.memory_size(),
+        /* salt_len */ handle.hasher.salt().len() as u32,
+        /* variant */ config.variant().into(),
+    )
+}
+
+/// Function that hashes a password using the configuration, salt, secret key and additional
+/// data held by an `argonautica_hasher_t*`. It will modify the provided `encoded` buffer
+/// and return an `argonautica_error_t` indicating whether or not the hash was successful.
+/// The handle itself is not modified, so it can be reused for as many hashes as you like
+///
+/// Arguments (from the perspective of C code):
+/// * Hasher:
+///     * `hasher` = an `argonautica_hasher_t*` created by `argonautica_hasher_new`
+/// * Encoded:
+///     * `encoded` = a `char*` that points to a buffer whose length (in bytes) is sufficient
+///       to hold the resulting string-encoded hash (including it's NULL byte)
+///     * To determine what the length of the string-encoded hash will be
+///       ahead of time (including the NULL byte), use the `argonautica_hasher_encoded_len` function
+/// * Password:
+///     * `password` = a `uint8_t*` pointing to the password buffer
+///     * `password_len` = a `uint32_t` indicating the number of bytes in the password buffer
+///     * If password clearing has been configured (see `argonautica_hasher_configure_password_clearing`),
+///       this function will zero out the bytes in the password buffer
+#[no_mangle]
+pub extern "C" fn argonautica_hasher_hash(
+    hasher: *const argonautica_hasher_t,
+    encoded: *mut c_char,
+    password: *mut u8,
+    password_len: u32,
+) -> argonautica_error_t {
+    if hasher.is_null() || encoded.is_null() || password.is_null() {
+        return argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR.record();
+    }
+    let handle = unsafe { &*hasher };
+
+    let mut hasher = handle.hasher();
+    let password = unsafe { ::std::slice::from_raw_parts_mut(password, password_len as usize) };
+    hasher.with_password(password);
+
+    let hash = match hasher.hash() {
+        Ok(hash) => hash,
+        Err(e) => return set_last_error(e),
+    };
+
+    let hash_cstring = CString::new(hash.as_bytes()).unwrap();
+    let hash_cstring_len = hash_cstring.as_bytes_with_nul().len();
+    let hash_ptr = hash_cstring.as_ptr();
+
+    unsafe {
+        ::std::ptr::copy_nonoverlapping(hash_ptr, encoded, hash_cstring_len);
+    }
+
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+/// Function that hashes a password using the configuration, salt, secret key and additional
+/// data held by an `argonautica_hasher_t*`, producing raw hash output instead of a
+/// string-encoded hash. It will modify the provided `hash_raw` struct (and the buffers it points
+/// to) and return an `argonautica_error_t` indicating whether or not the hash was successful
+///
+/// Arguments (from the perspective of C code):
+/// * Hasher:
+///     * `hasher` = an `argonautica_hasher_t*` created by `argonautica_hasher_new`
+/// * Hash raw:
+///     * `hash_raw` = an `argonautica_hash_raw_t*` whose `hash` and `salt` members point to
+///       caller-provided buffers and whose `hash_len` and `salt_len` members hold the capacity of
+///       those buffers (at least the configured hash length and salt length respectively)
+///     * On success, the raw hash bytes and raw salt bytes will be written to those buffers and
+///       the remaining members will be set to the parameters that were used
+/// * Password:
+///     * `password` = a `uint8_t*` pointing to the password buffer
+///     * `password_len` = a `uint32_t` indicating the number of bytes in the password buffer
+///     * If password clearing has been configured (see `argonautica_hasher_configure_password_clearing`),
+///       this function will zero out the bytes in the password buffer
+#[no_mangle]
+pub extern "C" fn argonautica_hasher_hash_raw(
+    hasher: *const argonautica_hasher_t,
+    hash_raw: *mut argonautica_hash_raw_t,
+    password: *mut u8,
+    password_len: u32,
+) -> argonautica_error_t {
+    if hasher.is_null() || hash_raw.is_null() || password.is_null() {
+        return argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR.record();
+    }
+    let handle = unsafe { &*hasher };
+    let out = unsafe { &mut *hash_raw };
+
+    let mut hasher = handle.hasher();
+    let password = unsafe { ::std::slice::from_raw_parts_mut(password, password_len as usize) };
+    hasher.with_password(password);
+
+    let hash_raw = match hasher.hash_raw() {
+        Ok(hash_raw) => hash_raw,
+        Err(e) => return set_last_error(e),
+    };
+
+    out.fill(&hash_raw)
+}
+
+/// Function that determines whether a string-encoded hash should be rehashed because it was
+/// produced with parameters (hash length, iterations, lanes, memory size, salt length, variant
+/// or version) that differ from the current configuration of an `argonautica_hasher_t*`. It will
+/// modify the provided `needs_rehash` int and return an `argonautica_error_t` indicating whether
+/// or not the hash could be decoded
+///
+/// On success, `needs_rehash` will be modified to be `1` if the hash should be rehashed or `0`
+/// if it is up to date.
+///
+/// Arguments (from the perspective of C code):
+/// * `hasher` = an `argonautica_hasher_t*` created by `argonautica_hasher_new`
+/// * `needs_rehash` = an `int*` that will be set to the result
+/// * `encoded` = a `char*` pointing to the string-encoded hash
+#[no_mangle]
+pub extern "C" fn argonautica_hasher_needs_rehash(
+    hasher: *const argonautica_hasher_t,
+    needs_rehash: *mut c_int,
+    encoded: *const c_char,
+) -> argonautica_error_t {
+    if hasher.is_null() || needs_rehash.is_null() || encoded.is_null() {
+        return argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR.record();
+    }
+    let handle = unsafe { &*hasher };
+
+    let encoded_cstr = unsafe { CStr::from_ptr(encoded) };
+    let encoded = match encoded_cstr.to_str() {
+        Ok(encoded) => encoded,
+        Err(_) => return argonautica_error_t::ARGONAUTICA_ERROR_UTF8_ENCODE.record(),
+    };
+
+    let result = match handle.hasher.needs_rehash(encoded) {
+        Ok(result) => result,
+        Err(e) => return set_last_error(e),
+    };
+
+    unsafe {
+        *needs_rehash = if result { 1 } else { 0 };
+    }
+
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use argonautica::{Hasher, Verifier};
+
+    use super::*;
+
+    #[test]
+    fn test_hasher_handle() {
+        let hasher = argonautica_hasher_new();
+        assert_eq!(
+            argonautica_hasher_configure_iterations(hasher, 8),
+            argonautica_error_t::ARGONAUTICA_OK
+        );
+        argonautica_hasher_configure_memory_size(hasher, 128);
+        argonautica_hasher_configure_password_clearing(hasher, 1);
+        let secret_key = b"secret".to_vec();
+        argonautica_hasher_with_secret_key(hasher, secret_key.as_ptr(), secret_key.len() as u32);
+        drop(secret_key);
+
+        let encoded_len = argonautica_hasher_encoded_len(hasher);
+        assert!(encoded_len > 0);
+
+        let mut hashes = Vec::new();
+        for _ in 0..2 {
+            let mut encoded = vec![0 as c_char; encoded_len as usize];
+            let mut password = b"P@ssw0rd".to_vec();
+            let err = argonautica_hasher_hash(
+                hasher,
+                encoded.as_mut_ptr(),
+                password.as_mut_ptr(),
+                password.len() as u32,
+            );
+            assert_eq!(err, argonautica_error_t::ARGONAUTICA_OK);
+            assert_eq!(&password[..], &[0u8; 8][..]);
+            let encoded = unsafe { CStr::from_ptr(encoded.as_ptr()) };
+            hashes.push(encoded.to_str().unwrap().to_string());
+        }
+        assert_ne!(hashes[0], hashes[1]);
+
+        for hash in &hashes {
+            let is_valid = Verifier::default()
+                .with_hash(hash)
+                .with_password("P@ssw0rd")
+                .with_secret_key("secret")
+                .verify()
+                .unwrap();
+            assert!(is_valid);
+        }
+
+        argonautica_hasher_free(hasher);
+    }
+
+    #[test]
+    fn test_hasher_handle_hash_raw() {
+        let hasher = argonautica_hasher_new();
+        argonautica_hasher_configure_hash_len(hasher, 16);
+        argonautica_hasher_configure_iterations(hasher, 8);
+        argonautica_hasher_configure_memory_size(hasher, 128);
+        argonautica_hasher_opt_out_of_secret_key(hasher, 1);
+        let salt = b"somesalt";
+        argonautica_hasher_with_salt(hasher, salt.as_ptr(), salt.len() as u32);
+
+        let mut hash_buf = [0u8; 32];
+        let mut salt_buf = [0u8; 32];
+        let mut hash_raw = argonautica_hash_raw_t {
+            hash: hash_buf.as_mut_ptr(),
+            hash_len: hash_buf.len() as u32,
+            iterations: 0,
+            lanes: 0,
+            memory_size: 0,
+            salt: salt_buf.as_mut_ptr(),
+            salt_len: salt_buf.len() as u32,
+            variant: argonautica_variant_t::ARGONAUTICA_ARGON2D,
+            version: argonautica_version_t::ARGONAUTICA_0x10,
+        };
+        let mut password = b"P@ssw0rd".to_vec();
+        let err = argonautica_hasher_hash_raw(
+            hasher,
+            &mut hash_raw,
+            password.as_mut_ptr(),
+            password.len() as u32,
+        );
+        assert_eq!(err, argonautica_error_t::ARGONAUTICA_OK);
+
+        let expected = Hasher::default()
+            .configure_hash_len(16)
+            .configure_iterations(8)
+            .configure_memory_size(128)
+            .opt_out_of_secret_key(true)
+            .with_password("P@ssw0rd")
+            .with_salt("somesalt")
+            .hash_raw()
+            .unwrap();
+        assert_eq!(hash_raw.hash_len, 16);
+        assert_eq!(&hash_buf[..16], expected.raw_hash_bytes());
+        assert_eq!(hash_raw.salt_len, 8);
+        assert_eq!(&salt_buf[..8], &salt[..]);
+        assert_eq!(hash_raw.iterations, 8);
+        assert_eq!(hash_raw.memory_size, 128);
+        assert_eq!(
+            hash_raw.variant,
+            argonautica_variant_t::ARGONAUTICA_ARGON2ID
+        );
+        assert_eq!(hash_raw.version, argonautica_version_t::ARGONAUTICA_0x13);
+
+        argonautica_hasher_free(hasher);
+    }
+
+    #[test]
+    fn test_hasher_handle_needs_rehash() {
+        let hash = Hasher::default()
+            .configure_iterations(8)
+            .configure_lanes(2)
+            .configure_memory_size(64)
+            .opt_out_of_secret_key(true)
+            .with_password("P@ssw0rd")
+            .hash()
+            .unwrap();
+        let encoded = CString::new(hash).unwrap();
+
+        let hasher = argonautica_hasher_new();
+        argonautica_hasher_configure_iterations(hasher, 8);
+        argonautica_hasher_configure_lanes(hasher, 2);
+        argonautica_hasher_configure_memory_size(hasher, 64);
+
+        let mut needs_rehash = -1;
+        let err = argonautica_hasher_needs_rehash(hasher, &mut needs_rehash, encoded.as_ptr());
+        assert_eq!(err, argonautica_error_t::ARGONAUTICA_OK);
+        assert_eq!(needs_rehash, 0);
+
+        argonautica_hasher_configure_memory_size(hasher, 128);
+        let err = argonautica_hasher_needs_rehash(hasher, &mut needs_rehash, encoded.as_ptr());
+        assert_eq!(err, argonautica_error_t::ARGONAUTICA_OK);
+        assert_eq!(needs_rehash, 1);
+
+        let encoded = CString::new("$argon2id$v=19$m=64").unwrap();
+        let err = argonautica_hasher_needs_rehash(hasher, &mut needs_rehash, encoded.as_ptr());
+        assert_eq!(err, argonautica_error_t::ARGONAUTICA_ERROR_HASH_DECODE);
+
+        argonautica_hasher_free(hasher);
+    }
+
+    #[test]
+    fn test_hasher_handle_null() {
+        let err = argonautica_hasher_configure_lanes(::std::ptr::null_mut(), 1);
+        assert_eq!(err, argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR);
+        assert_eq!(argonautica_hasher_encoded_len(::std::ptr::null()), -1);
+        argonautica_hasher_free(::std::ptr::null_mut());
+    }
+}
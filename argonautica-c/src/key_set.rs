@@ -0,0 +1,193 @@
+#![allow(non_camel_case_types)]
+
+use std::ffi::{CStr, CString};
+
+use argonautica::input::SecretKey;
+use argonautica::keys::{KeyFile, KeySet};
+use argonautica::{Error, ErrorKind};
+use libc::c_char;
+
+use argonautica_error_t;
+use error::set_last_error;
+
+/// Opaque handle holding a set of secret keys, each with a key ID, for key rotation (see
+/// `argonautica_verifier_verify_with_key_set`). Create one with `argonautica_key_set_new` and
+/// release it with `argonautica_key_set_free`
+#[derive(Debug)]
+pub struct argonautica_key_set_t {
+    pub(crate) ids: Vec<CString>,
+    pub(crate) key_set: KeySet,
+}
+
+impl argonautica_key_set_t {
+    /// Returns a pointer to the nul-terminated copy of the provided key ID held by this handle,
+    /// or `NULL` if there is none. The pointer stays valid until the handle is freed, since
+    /// keys are never removed from a key set
+    pub(crate) fn id_ptr(&self, id: Option<&str>) -> *const c_char {
+        id.and_then(|id| self.ids.iter().find(|c| c.as_bytes() == id.as_bytes()))
+            .map_or(::std::ptr::null(), |c| c.as_ptr())
+    }
+
+    fn insert(&mut self, key: KeyFile) -> Result<(), Error> {
+        let id = CString::new(key.id()).expect("key ids are ASCII without nul bytes");
+        self.key_set.insert(key)?;
+        self.ids.push(id);
+        Ok(())
+    }
+}
+
+macro_rules! key_set_mut {
+    ($key_set:expr) => {
+        match unsafe { $key_set.as_mut() } {
+            Some(key_set) => key_set,
+            None => return argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR.record(),
+        }
+    };
+}
+
+/// Function that creates a new, empty `argonautica_key_set_t*`. The handle must eventually be
+/// released with `argonautica_key_set_free`
+#[no_mangle]
+pub extern "C" fn argonautica_key_set_new() -> *mut argonautica_key_set_t {
+    let key_set = argonautica_key_set_t {
+        ids: Vec::new(),
+        key_set: KeySet::new(),
+    };
+    Box::into_raw(Box::new(key_set))
+}
+
+/// Function that frees an `argonautica_key_set_t*` created by `argonautica_key_set_new`.
+/// Passing `NULL` is a no-op
+#[no_mangle]
+pub extern "C" fn argonautica_key_set_free(key_set: *mut argonautica_key_set_t) {
+    if key_set.is_null() {
+        return;
+    }
+    let _ = unsafe { Box::from_raw(key_set) };
+}
+
+/// Function that adds a secret key to an `argonautica_key_set_t*`
+/// * `id` = a `char*` pointing to the key ID, which must be non-empty and consist only of
+///   ASCII letters, digits, `-`, `_` and `.`, and must not already be in the set
+/// * `secret_key` = a `uint8_t*` pointing to the secret key buffer
+/// * `secret_key_len` = a `uint32_t` indicating the number of bytes in the secret key buffer
+///   (at least `16`)
+/// * The bytes are copied into the handle, so you are free to zero out and / or free the
+///   secret key buffer as soon as this function returns
+/// * `created` = a `uint64_t` indicating when the key was created, in seconds since the Unix
+///   epoch. The most recently created key is the current one
+#[no_mangle]
+pub extern "C" fn argonautica_key_set_insert(
+    key_set: *mut argonautica_key_set_t,
+    id: *const c_char,
+    secret_key: *const u8,
+    secret_key_len: u32,
+    created: u64,
+) -> argonautica_error_t {
+    let handle = key_set_mut!(key_set);
+    if id.is_null() || secret_key.is_null() {
+        return argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR.record();
+    }
+    let id = match unsafe { CStr::from_ptr(id) }.to_str() {
+        Ok(id) => id,
+        Err(_) => return argonautica_error_t::ARGONAUTICA_ERROR_UTF8_ENCODE.record(),
+    };
+    let secret_key = unsafe { ::std::slice::from_raw_parts(secret_key, secret_key_len as usize) };
+    match KeyFile::new(id, SecretKey::from(secret_key), created).and_then(|key| handle.insert(key))
+    {
+        Ok(()) => argonautica_error_t::ARGONAUTICA_OK,
+        Err(e) => set_last_error(e),
+    }
+}
+
+/// Function that reads every key file (i.e. every file with the extension `.key`) in a
+/// directory into an `argonautica_key_set_t*`
+/// * `dir` = a `char*` pointing to the path of the directory
+/// * On Unix, the key files must not be accessible by users other than their owner
+/// * If any key file cannot be read, or has the key ID of a key already in the set, no key is
+///   added to the set
+#[no_mangle]
+pub extern "C" fn argonautica_key_set_load_dir(
+    key_set: *mut argonautica_key_set_t,
+    dir: *const c_char,
+) -> argonautica_error_t {
+    let handle = key_set_mut!(key_set);
+    if dir.is_null() {
+        return argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR.record();
+    }
+    let dir = match unsafe { CStr::from_ptr(dir) }.to_str() {
+        Ok(dir) => dir,
+        Err(_) => return argonautica_error_t::ARGONAUTICA_ERROR_UTF8_ENCODE.record(),
+    };
+    let loaded = match KeySet::load_dir(dir) {
+        Ok(loaded) => loaded,
+        Err(e) => return set_last_error(e),
+    };
+    if let Some(key) = loaded
+        .iter()
+        .find(|key| handle.key_set.get(key.id()).is_some())
+    {
+        return set_last_error(
+            Error::new(ErrorKind::KeyFileInvalidError)
+                .add_context(format!("Duplicate key id: {}", key.id())),
+        );
+    }
+    for key in loaded.iter() {
+        if let Err(e) = handle.insert(key.clone()) {
+            return set_last_error(e);
+        }
+    }
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_set_handle() {
+        let key_set = argonautica_key_set_new();
+        let secret_key = b"old secret key, long enough";
+        let err = argonautica_key_set_insert(
+            key_set,
+            b"old\0".as_ptr() as *const c_char,
+            secret_key.as_ptr(),
+            secret_key.len() as u32,
+            1,
+        );
+        assert_eq!(err, argonautica_error_t::ARGONAUTICA_OK);
+        let err = argonautica_key_set_insert(
+            key_set,
+            b"old\0".as_ptr() as *const c_char,
+            secret_key.as_ptr(),
+            secret_key.len() as u32,
+            2,
+        );
+        assert_eq!(err, argonautica_error_t::ARGONAUTICA_ERROR_KEY_FILE_INVALID);
+        let err = argonautica_key_set_insert(
+            key_set,
+            b"new\0".as_ptr() as *const c_char,
+            b"short".as_ptr(),
+            5,
+            2,
+        );
+        assert_eq!(
+            err,
+            argonautica_error_t::ARGONAUTICA_ERROR_SECRET_KEY_TOO_SHORT
+        );
+
+        let handle = unsafe { &*key_set };
+        assert_eq!(handle.key_set.len(), 1);
+        let ptr = handle.id_ptr(Some("old"));
+        assert_eq!(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap(), "old");
+        assert!(handle.id_ptr(Some("new")).is_null());
+        assert!(handle.id_ptr(None).is_null());
+
+        let err = argonautica_key_set_load_dir(
+            key_set,
+            b"/nonexistent/argonautica\0".as_ptr() as *const c_char,
+        );
+        assert_eq!(err, argonautica_error_t::ARGONAUTICA_ERROR_IO);
+        argonautica_key_set_free(key_set);
+    }
+}
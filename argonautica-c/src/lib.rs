@@ -30,6 +30,10 @@
 //! * Use the library
 //!     * The library, which will be called `libargonautica_c.dylib` or something similar (depending on your OS), will be in the `./target/release` directory
 //!     * The header file, which will be called `argonautica.h`, will be in the `./argonautica-c/include` directory
+//! * Install the library (optional)
+//!     * `cd argonautica-c && make install` builds the library and installs it, the header file and a [pkg-config](https://www.freedesktop.org/wiki/Software/pkg-config/) file, called `argonautica.pc`, under `/usr/local`. Set `PREFIX` to install somewhere else and `DESTDIR` to stage the installation, e.g. `make install PREFIX=/usr DESTDIR=/tmp/stage`
+//! * Regenerate the header file (only necessary if you are changing argonautica-c itself)
+//!     * The header file is generated by the build script. Building with the `ARGONAUTICA_UPDATE_HEADERS` environment variable set (e.g. `ARGONAUTICA_UPDATE_HEADERS=1 cargo build`) will overwrite both `./argonautica-c/include/argonautica.h` and the copy used by the Python package, `./argonautica-py/argonautica/argonautica.h`. `cargo test` will fail if either copy is out of date
 //!
 //! # License
 //!
@@ -48,17 +52,57 @@
 #![doc(html_root_url = "https://docs.rs/argonautica-c/0.2.1")]
 
 extern crate argonautica;
+extern crate futures_cpupool;
 extern crate itoa;
 extern crate libc;
 
 mod error;
 mod hash;
+mod hash_raw;
+mod hasher;
+mod key_set;
+mod non_blocking;
 mod types;
 mod utils;
+mod verification;
+mod verifier;
 mod verify;
 
 pub use error::*;
 pub use hash::*;
+pub use hash_raw::*;
+pub use hasher::*;
+pub use key_set::*;
+pub use non_blocking::*;
 pub use types::*;
 pub use utils::*;
+pub use verification::*;
+pub use verifier::*;
 pub use verify::*;
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    const GENERATED_HEADER: &str = include_str!(concat!(env!("OUT_DIR"), "/argonautica.h"));
+
+    #[test]
+    fn test_headers_up_to_date() {
+        let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let mut paths = vec![crate_dir.join("include/argonautica.h")];
+        // The Python copy is only checked if argonautica-py is checked out next to this crate
+        let py_dir = crate_dir.join("../argonautica-py/argonautica");
+        if py_dir.exists() {
+            paths.push(py_dir.join("argonautica.h"));
+        }
+        for path in &paths {
+            let committed = fs::read_to_string(path).unwrap();
+            assert!(
+                committed == GENERATED_HEADER,
+                "{} is out of date. Rebuild with ARGONAUTICA_UPDATE_HEADERS=1 to regenerate it",
+                path.display(),
+            );
+        }
+    }
+}
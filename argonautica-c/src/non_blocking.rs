@@ -0,0 +1,446 @@
+#![allow(non_camel_case_types)]
+
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Once};
+
+use argonautica::config::default_cpu_pool;
+use futures_cpupool::CpuPool;
+use libc::{c_char, c_int, c_void};
+
+use error::set_last_error;
+use {argonautica_error_t, argonautica_hasher_t, argonautica_verifier_t};
+
+/// Callback invoked when an `argonautica_hasher_hash_non_blocking` call completes
+/// * `err` = an `argonautica_error_t` indicating whether or not the hash was successful
+/// * `encoded` = a `char*` pointing to the NULL-terminated string-encoded hash if `err` is
+///   `ARGONAUTICA_OK`, or `NULL` otherwise. The buffer is owned by argonautica and is only valid
+///   until the callback returns; copy it if you need it afterwards
+/// * `user_data` = the `void*` that was passed to `argonautica_hasher_hash_non_blocking`
+pub type argonautica_hash_callback_t =
+    Option<extern "C" fn(err: argonautica_error_t, encoded: *const c_char, user_data: *mut c_void)>;
+
+/// Callback invoked when an `argonautica_verifier_verify_non_blocking` call completes
+/// * `err` = an `argonautica_error_t` indicating whether or not the verification was successful
+/// * `is_valid` = `1` if the hash / password combination is valid or `0` otherwise. Only
+///   meaningful if `err` is `ARGONAUTICA_OK`
+/// * `user_data` = the `void*` that was passed to `argonautica_verifier_verify_non_blocking`
+pub type argonautica_verify_callback_t =
+    Option<extern "C" fn(err: argonautica_error_t, is_valid: c_int, user_data: *mut c_void)>;
+
+/// Opaque handle used to cancel non-blocking calls. Create one with
+/// `argonautica_cancel_token_new`, pass it to any number of non-blocking calls, cancel them with
+/// `argonautica_cancel_token_cancel` and release it with `argonautica_cancel_token_free`
+pub struct argonautica_cancel_token_t {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl fmt::Debug for argonautica_cancel_token_t {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "argonautica_cancel_token_t {{ cancelled: {:?} }}",
+            self.cancelled.load(Ordering::SeqCst)
+        )
+    }
+}
+
+/// Function that creates a new, not yet cancelled `argonautica_cancel_token_t*`. The handle must
+/// eventually be released with `argonautica_cancel_token_free`
+#[no_mangle]
+pub extern "C" fn argonautica_cancel_token_new() -> *mut argonautica_cancel_token_t {
+    let cancel_token = argonautica_cancel_token_t {
+        cancelled: Arc::new(AtomicBool::new(false)),
+    };
+    Box::into_raw(Box::new(cancel_token))
+}
+
+/// Function that cancels every non-blocking call that was given this `argonautica_cancel_token_t*`.
+/// It may be called from any thread and more than once.
+///
+/// A cancelled call that has not started yet is skipped. A cancelled call that is already running
+/// cannot be interrupted (the underlying argon2 computation is not interruptible), but its result
+/// is discarded. Either way, its callback is still invoked exactly once, with
+/// `ARGONAUTICA_ERROR_CANCELLED`, unless it had already been invoked before the cancellation
+#[no_mangle]
+pub extern "C" fn argonautica_cancel_token_cancel(
+    cancel_token: *const argonautica_cancel_token_t,
+) -> argonautica_error_t {
+    let cancel_token = match unsafe { cancel_token.as_ref() } {
+        Some(cancel_token) => cancel_token,
+        None => return argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR.record(),
+    };
+    cancel_token.cancelled.store(true, Ordering::SeqCst);
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+/// Function that frees an `argonautica_cancel_token_t*` created by `argonautica_cancel_token_new`.
+/// Non-blocking calls that were given the token keep their own reference to it, so it may be
+/// freed before they complete (after which they can no longer be cancelled).
+/// Passing `NULL` is a no-op
+#[no_mangle]
+pub extern "C" fn argonautica_cancel_token_free(cancel_token: *mut argonautica_cancel_token_t) {
+    if cancel_token.is_null() {
+        return;
+    }
+    let _ = unsafe { Box::from_raw(cancel_token) };
+}
+
+/// Returns a shared reference to the cancellation flag of a (possibly `NULL`) cancel token
+fn cancelled_flag(cancel_token: *const argonautica_cancel_token_t) -> Arc<AtomicBool> {
+    match unsafe { cancel_token.as_ref() } {
+        Some(cancel_token) => cancel_token.cancelled.clone(),
+        None => Arc::new(AtomicBool::new(false)),
+    }
+}
+
+struct UserData(*mut c_void);
+
+// The user data pointer is never dereferenced by argonautica; it is only handed back to the
+// caller's callback, so it is up to the caller to make sure it may be used from another thread
+unsafe impl Send for UserData {}
+
+/// Returns the `CpuPool` shared by all non-blocking calls, creating it on first use
+fn cpu_pool() -> CpuPool {
+    static INIT: Once = Once::new();
+    static mut CPU_POOL: *const CpuPool = 0 as *const CpuPool;
+    unsafe {
+        INIT.call_once(|| {
+            CPU_POOL = Box::into_raw(Box::new(default_cpu_pool()));
+        });
+        (*CPU_POOL).clone()
+    }
+}
+
+/// Function that hashes a password without blocking the calling thread. The work is scheduled on
+/// argonautica's internal thread pool (which has as many threads as your machine has logical
+/// cores) and, once it is done, `callback` is invoked with the result and `user_data`.
+///
+/// Arguments (from the perspective of C code):
+/// * `hasher` = an `argonautica_hasher_t*` created by `argonautica_hasher_new`
+/// * `password` = a `uint8_t*` pointing to the password buffer
+/// * `password_len` = a `uint32_t` indicating the number of bytes in the password buffer
+/// * `cancel_token` = an `argonautica_cancel_token_t*` that can be used to cancel the call
+///   (see `argonautica_cancel_token_cancel`), or `NULL` if the call need not be cancellable
+/// * `callback` = an `argonautica_hash_callback_t` to invoke upon completion
+/// * `user_data` = a `void*` that is passed, untouched, to `callback`
+///
+/// Ownership and threading rules:
+/// * If this function returns anything other than `ARGONAUTICA_OK`, no work was scheduled and
+///   `callback` will <b>not</b> be invoked. Otherwise `callback` will be invoked exactly once
+/// * `callback` is invoked on one of argonautica's worker threads, never on the calling thread.
+///   It must not block for long, as it occupies a worker thread while it runs
+/// * The configuration, salt, secret key and additional data of `hasher`, as well as the
+///   password, are copied before this function returns. You are free to modify or free `hasher`
+///   and the password buffer as soon as this function returns. The copies are zeroed out once
+///   the work is done
+/// * If password clearing has been configured on `hasher`, the bytes in the password buffer are
+///   zeroed out before this function returns
+/// * `user_data` is never dereferenced by argonautica. Whatever it points to must stay valid
+///   until `callback` has been invoked and must be safe to use from another thread
+#[no_mangle]
+pub extern "C" fn argonautica_hasher_hash_non_blocking(
+    hasher: *const argonautica_hasher_t,
+    password: *mut u8,
+    password_len: u32,
+    cancel_token: *const argonautica_cancel_token_t,
+    callback: argonautica_hash_callback_t,
+    user_data: *mut c_void,
+) -> argonautica_error_t {
+    let callback = match callback {
+        Some(callback) => callback,
+        None => return argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR.record(),
+    };
+    if hasher.is_null() || password.is_null() {
+        return argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR.record();
+    }
+    let handle = unsafe { &*hasher };
+
+    let password = unsafe { ::std::slice::from_raw_parts_mut(password, password_len as usize) };
+    let mut hasher = handle.hasher().to_owned();
+    hasher
+        .configure_password_clearing(true)
+        .configure_secret_key_clearing(true)
+        .with_password(password.to_vec());
+    if handle.hasher.config().password_clearing() {
+        for b in password.iter_mut() {
+            *b = 0;
+        }
+    }
+
+    let cancelled = cancelled_flag(cancel_token);
+    let user_data = UserData(user_data);
+    cpu_pool()
+        .spawn_fn(move || {
+            let user_data = user_data;
+            if cancelled.load(Ordering::SeqCst) {
+                let err = argonautica_error_t::ARGONAUTICA_ERROR_CANCELLED.record();
+                callback(err, ::std::ptr::null(), user_data.0);
+                return Ok(());
+            }
+            let result = hasher.hash();
+            if cancelled.load(Ordering::SeqCst) {
+                let err = argonautica_error_t::ARGONAUTICA_ERROR_CANCELLED.record();
+                callback(err, ::std::ptr::null(), user_data.0);
+                return Ok(());
+            }
+            match result {
+                Ok(hash) => {
+                    let hash_cstring = CString::new(hash.as_bytes()).unwrap();
+                    callback(
+                        argonautica_error_t::ARGONAUTICA_OK,
+                        hash_cstring.as_ptr(),
+                        user_data.0,
+                    );
+                }
+                Err(e) => callback(set_last_error(e), ::std::ptr::null(), user_data.0),
+            }
+            Ok::<(), ()>(())
+        })
+        .forget();
+
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+/// Function that verifies a password against a hash without blocking the calling thread. The
+/// work is scheduled on argonautica's internal thread pool (which has as many threads as your
+/// machine has logical cores) and, once it is done, `callback` is invoked with the result and
+/// `user_data`.
+///
+/// Arguments (from the perspective of C code):
+/// * `verifier` = an `argonautica_verifier_t*` created by `argonautica_verifier_new`
+/// * `encoded` = a `char*` pointing to the string-encoded hash
+/// * `password` = a `uint8_t*` pointing to the password buffer
+/// * `password_len` = a `uint32_t` indicating the number of bytes in the password buffer
+/// * `cancel_token` = an `argonautica_cancel_token_t*` that can be used to cancel the call
+///   (see `argonautica_cancel_token_cancel`), or `NULL` if the call need not be cancellable
+/// * `callback` = an `argonautica_verify_callback_t` to invoke upon completion
+/// * `user_data` = a `void*` that is passed, untouched, to `callback`
+///
+/// Ownership and threading rules are the same as for `argonautica_hasher_hash_non_blocking`:
+/// `verifier`, `encoded` and the password are copied before this function returns, `callback`
+/// is invoked exactly once on one of argonautica's worker threads if (and only if) this function
+/// returns `ARGONAUTICA_OK`, `user_data` must stay valid until then, and cancellation works the
+/// same way
+#[no_mangle]
+pub extern "C" fn argonautica_verifier_verify_non_blocking(
+    verifier: *const argonautica_verifier_t,
+    encoded: *const c_char,
+    password: *mut u8,
+    password_len: u32,
+    cancel_token: *const argonautica_cancel_token_t,
+    callback: argonautica_verify_callback_t,
+    user_data: *mut c_void,
+) -> argonautica_error_t {
+    let callback = match callback {
+        Some(callback) => callback,
+        None => return argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR.record(),
+    };
+    if verifier.is_null() || encoded.is_null() || password.is_null() {
+        return argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR.record();
+    }
+    let handle = unsafe { &*verifier };
+
+    let encoded_cstr = unsafe { CStr::from_ptr(encoded) };
+    let encoded = match encoded_cstr.to_str() {
+        Ok(encoded) => encoded,
+        Err(_) => return argonautica_error_t::ARGONAUTICA_ERROR_UTF8_ENCODE.record(),
+    };
+
+    let password = unsafe { ::std::slice::from_raw_parts_mut(password, password_len as usize) };
+    let mut verifier = handle.verifier().to_owned();
+    verifier
+        .configure_password_clearing(true)
+        .configure_secret_key_clearing(true)
+        .with_hash(encoded)
+        .with_password(password.to_vec());
+    if handle.verifier.config().password_clearing() {
+        for b in password.iter_mut() {
+            *b = 0;
+        }
+    }
+
+    let cancelled = cancelled_flag(cancel_token);
+    let user_data = UserData(user_data);
+    cpu_pool()
+        .spawn_fn(move || {
+            let user_data = user_data;
+            if cancelled.load(Ordering::SeqCst) {
+                let err = argonautica_error_t::ARGONAUTICA_ERROR_CANCELLED.record();
+                callback(err, 0, user_data.0);
+                return Ok(());
+            }
+            let result = verifier.verify();
+            if cancelled.load(Ordering::SeqCst) {
+                let err = argonautica_error_t::ARGONAUTICA_ERROR_CANCELLED.record();
+                callback(err, 0, user_data.0);
+                return Ok(());
+            }
+            match result {
+                Ok(is_valid) => callback(
+                    argonautica_error_t::ARGONAUTICA_OK,
+                    if is_valid { 1 } else { 0 },
+                    user_data.0,
+                ),
+                Err(e) => callback(set_last_error(e), 0, user_data.0),
+            }
+            Ok::<(), ()>(())
+        })
+        .forget();
+
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::{channel, Sender};
+
+    use super::*;
+    use {
+        argonautica_hasher_configure_iterations, argonautica_hasher_configure_memory_size,
+        argonautica_hasher_configure_password_clearing, argonautica_hasher_free,
+        argonautica_hasher_new, argonautica_hasher_opt_out_of_secret_key,
+        argonautica_verifier_free, argonautica_verifier_new,
+    };
+
+    extern "C" fn hash_callback(
+        err: argonautica_error_t,
+        encoded: *const c_char,
+        user_data: *mut c_void,
+    ) {
+        let sender =
+            unsafe { Box::from_raw(user_data as *mut Sender<Result<String, argonautica_error_t>>) };
+        let result = if err == argonautica_error_t::ARGONAUTICA_OK {
+            let encoded = unsafe { CStr::from_ptr(encoded) };
+            Ok(encoded.to_str().unwrap().to_string())
+        } else {
+            Err(err)
+        };
+        sender.send(result).unwrap();
+    }
+
+    extern "C" fn verify_callback(
+        err: argonautica_error_t,
+        is_valid: c_int,
+        user_data: *mut c_void,
+    ) {
+        let sender =
+            unsafe { Box::from_raw(user_data as *mut Sender<(argonautica_error_t, c_int)>) };
+        sender.send((err, is_valid)).unwrap();
+    }
+
+    #[test]
+    fn test_non_blocking() {
+        let hasher = argonautica_hasher_new();
+        argonautica_hasher_configure_iterations(hasher, 8);
+        argonautica_hasher_configure_memory_size(hasher, 128);
+        argonautica_hasher_configure_password_clearing(hasher, 1);
+        argonautica_hasher_opt_out_of_secret_key(hasher, 1);
+
+        let (sender, receiver) = channel::<Result<String, argonautica_error_t>>();
+        let mut password = b"P@ssw0rd".to_vec();
+        let err = argonautica_hasher_hash_non_blocking(
+            hasher,
+            password.as_mut_ptr(),
+            password.len() as u32,
+            ::std::ptr::null(),
+            Some(hash_callback),
+            Box::into_raw(Box::new(sender)) as *mut c_void,
+        );
+        assert_eq!(err, argonautica_error_t::ARGONAUTICA_OK);
+        assert_eq!(&password[..], &[0u8; 8][..]);
+        argonautica_hasher_free(hasher);
+        let encoded = receiver.recv().unwrap().unwrap();
+        let encoded = CString::new(encoded).unwrap();
+
+        let verifier = argonautica_verifier_new();
+        for &(password, expected) in &[(&b"P@ssw0rd"[..], 1), (&b"wrong"[..], 0)] {
+            let (sender, receiver) = channel::<(argonautica_error_t, c_int)>();
+            let mut password = password.to_vec();
+            let err = argonautica_verifier_verify_non_blocking(
+                verifier,
+                encoded.as_ptr(),
+                password.as_mut_ptr(),
+                password.len() as u32,
+                ::std::ptr::null(),
+                Some(verify_callback),
+                Box::into_raw(Box::new(sender)) as *mut c_void,
+            );
+            assert_eq!(err, argonautica_error_t::ARGONAUTICA_OK);
+            let (err, is_valid) = receiver.recv().unwrap();
+            assert_eq!(err, argonautica_error_t::ARGONAUTICA_OK);
+            assert_eq!(is_valid, expected);
+        }
+        argonautica_verifier_free(verifier);
+    }
+
+    #[test]
+    fn test_non_blocking_error() {
+        let hasher = argonautica_hasher_new();
+        argonautica_hasher_configure_iterations(hasher, 0);
+
+        let mut password = b"P@ssw0rd".to_vec();
+        let err = argonautica_hasher_hash_non_blocking(
+            hasher,
+            password.as_mut_ptr(),
+            password.len() as u32,
+            ::std::ptr::null(),
+            None,
+            ::std::ptr::null_mut(),
+        );
+        assert_eq!(err, argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR);
+
+        let (sender, receiver) = channel::<Result<String, argonautica_error_t>>();
+        let err = argonautica_hasher_hash_non_blocking(
+            hasher,
+            password.as_mut_ptr(),
+            password.len() as u32,
+            ::std::ptr::null(),
+            Some(hash_callback),
+            Box::into_raw(Box::new(sender)) as *mut c_void,
+        );
+        assert_eq!(err, argonautica_error_t::ARGONAUTICA_OK);
+        assert_eq!(
+            receiver.recv().unwrap(),
+            Err(argonautica_error_t::ARGONAUTICA_ERROR_ITERATIONS_TOO_FEW)
+        );
+        argonautica_hasher_free(hasher);
+    }
+
+    #[test]
+    fn test_non_blocking_cancelled() {
+        let hasher = argonautica_hasher_new();
+        argonautica_hasher_configure_iterations(hasher, 8);
+        argonautica_hasher_configure_memory_size(hasher, 128);
+        argonautica_hasher_opt_out_of_secret_key(hasher, 1);
+
+        let cancel_token = argonautica_cancel_token_new();
+        assert_eq!(
+            argonautica_cancel_token_cancel(cancel_token),
+            argonautica_error_t::ARGONAUTICA_OK
+        );
+        let (sender, receiver) = channel::<Result<String, argonautica_error_t>>();
+        let mut password = b"P@ssw0rd".to_vec();
+        let err = argonautica_hasher_hash_non_blocking(
+            hasher,
+            password.as_mut_ptr(),
+            password.len() as u32,
+            cancel_token,
+            Some(hash_callback),
+            Box::into_raw(Box::new(sender)) as *mut c_void,
+        );
+        assert_eq!(err, argonautica_error_t::ARGONAUTICA_OK);
+        argonautica_cancel_token_free(cancel_token);
+        assert_eq!(
+            receiver.recv().unwrap(),
+            Err(argonautica_error_t::ARGONAUTICA_ERROR_CANCELLED)
+        );
+        argonautica_hasher_free(hasher);
+
+        assert_eq!(
+            argonautica_cancel_token_cancel(::std::ptr::null()),
+            argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR
+        );
+    }
+}
@@ -46,6 +46,16 @@ impl From<argonautica_variant_t> for Variant {
     }
 }
 
+impl From<Variant> for argonautica_variant_t {
+    fn from(variant: Variant) -> argonautica_variant_t {
+        match variant {
+            Variant::Argon2d => argonautica_variant_t::ARGONAUTICA_ARGON2D,
+            Variant::Argon2i => argonautica_variant_t::ARGONAUTICA_ARGON2I,
+            Variant::Argon2id => argonautica_variant_t::ARGONAUTICA_ARGON2ID,
+        }
+    }
+}
+
 /// Available argon2 versions
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[repr(C)]
@@ -65,3 +75,12 @@ impl From<argonautica_version_t> for Version {
         }
     }
 }
+
+impl From<Version> for argonautica_version_t {
+    fn from(version: Version) -> argonautica_version_t {
+        match version {
+            Version::_0x10 => argonautica_version_t::ARGONAUTICA_0x10,
+            Version::_0x13 => argonautica_version_t::ARGONAUTICA_0x13,
+        }
+    }
+}
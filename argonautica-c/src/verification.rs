@@ -0,0 +1,76 @@
+#![allow(non_camel_case_types)]
+
+use argonautica::output::Verification;
+use libc::{c_char, c_int};
+
+use {argonautica_variant_t, argonautica_version_t};
+
+/// Struct representing the detailed outcome of verifying a password, as filled in by
+/// `argonautica_verifier_verify_detailed` or `argonautica_verifier_verify_with_key_set`.
+/// Besides whether or not the password matched, it reports the parameters decoded from the
+/// hash, whether the password should be rehashed, how long verifying took and, when verifying
+/// with a key set, the ID of the key that matched
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct argonautica_verification_t {
+    /// The number of microseconds verifying took
+    pub duration_micros: u64,
+
+    /// The number of bytes in the raw hash
+    pub hash_len: u32,
+
+    /// `1` if the password matched the hash or `0` if it did not
+    pub is_valid: c_int,
+
+    /// A `char*` pointing to the ID of the key that matched, if the password was verified with
+    /// `argonautica_verifier_verify_with_key_set` and matched, or `NULL` otherwise. It is owned
+    /// by the key set and stays valid until the key set is freed
+    pub key_id: *const c_char,
+
+    /// The iterations configuration that was used to produce the hash
+    pub iterations: u32,
+
+    /// The lanes configuration that was used to produce the hash
+    pub lanes: u32,
+
+    /// The memory size configuration (in kibibytes) that was used to produce the hash
+    pub memory_size: u32,
+
+    /// `1` if the password matched and the hash was produced with parameters that differ from
+    /// those of the hasher given to `argonautica_verifier_configure_rehash_hasher`, or `0`
+    /// otherwise
+    pub rehash_recommended: c_int,
+
+    /// The number of bytes in the raw salt
+    pub salt_len: u32,
+
+    /// The variant that was used to produce the hash
+    pub variant: argonautica_variant_t,
+
+    /// The version that was used to produce the hash
+    pub version: argonautica_version_t,
+}
+
+impl<'a> From<&'a Verification> for argonautica_verification_t {
+    fn from(verification: &Verification) -> argonautica_verification_t {
+        let duration = verification.duration();
+        let hash_raw = verification.hash_raw();
+        argonautica_verification_t {
+            duration_micros: duration.as_secs() * 1_000_000 + u64::from(duration.subsec_micros()),
+            hash_len: hash_raw.raw_hash_bytes().len() as u32,
+            is_valid: if verification.is_valid() { 1 } else { 0 },
+            key_id: ::std::ptr::null(),
+            iterations: hash_raw.iterations(),
+            lanes: hash_raw.lanes(),
+            memory_size: hash_raw.memory_size(),
+            rehash_recommended: if verification.rehash_recommended() {
+                1
+            } else {
+                0
+            },
+            salt_len: hash_raw.raw_salt_bytes().len() as u32,
+            variant: hash_raw.variant().into(),
+            version: hash_raw.version().into(),
+        }
+    }
+}
@@ -0,0 +1,613 @@
+#![allow(non_camel_case_types)]
+
+use std::ffi::CStr;
+use std::fmt;
+
+use argonautica::config::Backend;
+use argonautica::Verifier;
+use libc::{c_char, c_int};
+
+use error::set_last_error;
+use {
+    argonautica_backend_t, argonautica_error_t, argonautica_hasher_t, argonautica_key_set_t,
+    argonautica_verification_t,
+};
+
+/// Opaque handle holding a reusable verifier configuration (including an optional secret key
+/// and additional data). Create one with `argonautica_verifier_new` and release it with
+/// `argonautica_verifier_free`
+pub struct argonautica_verifier_t {
+    pub(crate) additional_data: Option<Vec<u8>>,
+    pub(crate) secret_key: Option<Vec<u8>>,
+    pub(crate) verifier: Verifier<'static>,
+}
+
+impl Drop for argonautica_verifier_t {
+    fn drop(&mut self) {
+        if let Some(ref mut secret_key) = self.secret_key {
+            for b in secret_key.iter_mut() {
+                *b = 0;
+            }
+        }
+    }
+}
+
+impl fmt::Debug for argonautica_verifier_t {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "argonautica_verifier_t {{ verifier: {:?} }}",
+            self.verifier
+        )
+    }
+}
+
+impl argonautica_verifier_t {
+    /// Returns a fresh `Verifier` carrying this handle's configuration, additional data
+    /// and secret key, ready to be given a hash and a password
+    pub(crate) fn verifier<'a>(&'a self) -> Verifier<'a> {
+        let mut verifier = self.verifier.to_owned();
+        if let Some(ref additional_data) = self.additional_data {
+            verifier.with_additional_data(&additional_data[..]);
+        }
+        if let Some(ref secret_key) = self.secret_key {
+            verifier.with_secret_key(&secret_key[..]);
+        }
+        verifier
+    }
+}
+
+macro_rules! verifier_mut {
+    ($verifier:expr) => {
+        match unsafe { $verifier.as_mut() } {
+            Some(verifier) => verifier,
+            None => return argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR.record(),
+        }
+    };
+}
+
+/// Function that creates a new `argonautica_verifier_t*` with the same default configuration
+/// as a Rust `Verifier`. The handle must eventually be released with `argonautica_verifier_free`
+#[no_mangle]
+pub extern "C" fn argonautica_verifier_new() -> *mut argonautica_verifier_t {
+    let verifier = argonautica_verifier_t {
+        additional_data: None,
+        secret_key: None,
+        verifier: Verifier::default(),
+    };
+    Box::into_raw(Box::new(verifier))
+}
+
+/// Function that frees an `argonautica_verifier_t*` created by `argonautica_verifier_new`.
+/// Any secret key held by the handle is zeroed out before its memory is released.
+/// Passing `NULL` is a no-op
+#[no_mangle]
+pub extern "C" fn argonautica_verifier_free(verifier: *mut argonautica_verifier_t) {
+    if verifier.is_null() {
+        return;
+    }
+    let _ = unsafe { Box::from_raw(verifier) };
+}
+
+/// Function that configures the backend of an `argonautica_verifier_t*`
+/// * `backend` = `ARGONAUTICA_C` for the C backend
+/// * `backend` = `ARGONAUTICA_RUST` for the Rust backend
+#[no_mangle]
+pub extern "C" fn argonautica_verifier_configure_backend(
+    verifier: *mut argonautica_verifier_t,
+    backend: argonautica_backend_t,
+) -> argonautica_error_t {
+    let handle = verifier_mut!(verifier);
+    let backend: Backend = backend.into();
+    handle.verifier.configure_backend(backend);
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+/// Function that configures password clearing for an `argonautica_verifier_t*`. If
+/// `password_clearing` is any value other than zero, `argonautica_verifier_verify` will zero
+/// out the bytes in the password buffer it is given
+#[no_mangle]
+pub extern "C" fn argonautica_verifier_configure_password_clearing(
+    verifier: *mut argonautica_verifier_t,
+    password_clearing: c_int,
+) -> argonautica_error_t {
+    let handle = verifier_mut!(verifier);
+    handle
+        .verifier
+        .configure_password_clearing(password_clearing != 0);
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+/// Function that provides an `argonautica_verifier_t*` with the configuration of the
+/// `argonautica_hasher_t*` you currently hash passwords with, so that
+/// `argonautica_verifier_verify_detailed` can recommend rehashing passwords whose hashes were
+/// produced with different parameters (hash length, iterations, lanes, memory size, variant or
+/// version; the salt length is not compared)
+/// * The configuration is copied into the verifier, so the hasher may be freed as soon as this
+///   function returns
+/// * Pass `NULL` to stop recommending rehashes
+#[no_mangle]
+pub extern "C" fn argonautica_verifier_configure_rehash_hasher(
+    verifier: *mut argonautica_verifier_t,
+    hasher: *const argonautica_hasher_t,
+) -> argonautica_error_t {
+    let handle = verifier_mut!(verifier);
+    let config = unsafe { hasher.as_ref() }.map(|hasher| hasher.hasher.config().clone());
+    handle.verifier.configure_rehash_config(config);
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+/// Function that configures the number of threads of an `argonautica_verifier_t*`
+#[no_mangle]
+pub extern "C" fn argonautica_verifier_configure_threads(
+    verifier: *mut argonautica_verifier_t,
+    threads: u32,
+) -> argonautica_error_t {
+    let handle = verifier_mut!(verifier);
+    handle.verifier.configure_threads(threads);
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+/// Function that provides an `argonautica_verifier_t*` with the additional data that was
+/// originally used to create the hashes it will verify
+/// * `additional_data` = a `uint8_t*` pointing to the additional data buffer
+/// * `additional_data_len` = a `uint32_t` indicating the number of bytes in the additional data buffer
+/// * The bytes are copied into the handle; this function will not modify the additional data buffer
+/// * Pass `NULL` and `0` to remove previously provided additional data
+#[no_mangle]
+pub extern "C" fn argonautica_verifier_with_additional_data(
+    verifier: *mut argonautica_verifier_t,
+    additional_data: *const u8,
+    additional_data_len: u32,
+) -> argonautica_error_t {
+    let handle = verifier_mut!(verifier);
+    handle.additional_data = if additional_data.is_null() {
+        None
+    } else {
+        let additional_data =
+            unsafe { ::std::slice::from_raw_parts(additional_data, additional_data_len as usize) };
+        Some(additional_data.to_vec())
+    };
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+/// Function that provides an `argonautica_verifier_t*` with the secret key that was
+/// originally used to create the hashes it will verify
+/// * `secret_key` = a `uint8_t*` pointing to the secret key buffer
+/// * `secret_key_len` = a `uint32_t` indicating the number of bytes in the secret key buffer
+/// * The bytes are copied into the handle, so you are free to zero out and / or free the
+///   secret key buffer as soon as this function returns. The handle's copy is zeroed out
+///   when the handle is freed
+/// * Pass `NULL` and `0` to remove a previously provided secret key
+#[no_mangle]
+pub extern "C" fn argonautica_verifier_with_secret_key(
+    verifier: *mut argonautica_verifier_t,
+    secret_key: *const u8,
+    secret_key_len: u32,
+) -> argonautica_error_t {
+    let handle = verifier_mut!(verifier);
+    if let Some(ref mut old) = handle.secret_key {
+        for b in old.iter_mut() {
+            *b = 0;
+        }
+    }
+    handle.secret_key = if secret_key.is_null() {
+        None
+    } else {
+        let secret_key =
+            unsafe { ::std::slice::from_raw_parts(secret_key, secret_key_len as usize) };
+        Some(secret_key.to_vec())
+    };
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+/// Function that verifies a password against a hash using the configuration, secret key and
+/// additional data held by an `argonautica_verifier_t*`. It will modify the provided `is_valid`
+/// int and return an `argonautica_error_t` indicating whether or not the verification was
+/// successful. The handle itself is not modified, so it can be reused for as many
+/// verifications as you like
+///
+/// On success, `is_valid` will be modified to be `1` if the hash / password combination is valid
+/// or `0` if the hash / password combination is not valid.
+///
+/// Arguments (from the perspective of C code):
+/// * `verifier` = an `argonautica_verifier_t*` created by `argonautica_verifier_new`
+/// * `is_valid` = an `int*` that will be set to the result of the verification
+/// * `encoded` = a `char*` pointing to the string-encoded hash
+/// * `password` = a `uint8_t*` pointing to the password buffer
+/// * `password_len` = a `uint32_t` indicating the number of bytes in the password buffer
+///     * If password clearing has been configured (see `argonautica_verifier_configure_password_clearing`),
+///       this function will zero out the bytes in the password buffer
+#[no_mangle]
+pub extern "C" fn argonautica_verifier_verify(
+    verifier: *const argonautica_verifier_t,
+    is_valid: *mut c_int,
+    encoded: *const c_char,
+    password: *mut u8,
+    password_len: u32,
+) -> argonautica_error_t {
+    if verifier.is_null() || is_valid.is_null() || encoded.is_null() || password.is_null() {
+        return argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR.record();
+    }
+    let handle = unsafe { &*verifier };
+
+    let mut verifier = handle.verifier();
+
+    // Hash
+    let encoded_cstr = unsafe { CStr::from_ptr(encoded) };
+    let encoded = match encoded_cstr.to_str() {
+        Ok(encoded) => encoded,
+        Err(_) => return argonautica_error_t::ARGONAUTICA_ERROR_UTF8_ENCODE.record(),
+    };
+    verifier.with_hash(encoded);
+
+    // Password
+    let password = unsafe { ::std::slice::from_raw_parts_mut(password, password_len as usize) };
+    verifier.with_password(password);
+
+    let valid = match verifier.verify() {
+        Ok(valid) => valid,
+        Err(e) => return set_last_error(e),
+    };
+
+    unsafe {
+        *is_valid = if valid { 1 } else { 0 };
+    }
+
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+/// Function that verifies a password against a hash just like `argonautica_verifier_verify`,
+/// but reports the detailed outcome in the provided `argonautica_verification_t` instead of
+/// in an `int`. It will modify the provided `verification` struct and return an
+/// `argonautica_error_t` indicating whether or not the verification was successful
+///
+/// Arguments (from the perspective of C code):
+/// * `verifier` = an `argonautica_verifier_t*` created by `argonautica_verifier_new`
+/// * `verification` = an `argonautica_verification_t*` that will be set to the outcome of the
+///   verification
+/// * For a description of the other arguments, see the documentation for
+///   `argonautica_verifier_verify`
+#[no_mangle]
+pub extern "C" fn argonautica_verifier_verify_detailed(
+    verifier: *const argonautica_verifier_t,
+    verification: *mut argonautica_verification_t,
+    encoded: *const c_char,
+    password: *mut u8,
+    password_len: u32,
+) -> argonautica_error_t {
+    if verifier.is_null() || verification.is_null() || encoded.is_null() || password.is_null() {
+        return argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR.record();
+    }
+    let handle = unsafe { &*verifier };
+
+    let mut verifier = handle.verifier();
+
+    // Hash
+    let encoded_cstr = unsafe { CStr::from_ptr(encoded) };
+    let encoded = match encoded_cstr.to_str() {
+        Ok(encoded) => encoded,
+        Err(_) => return argonautica_error_t::ARGONAUTICA_ERROR_UTF8_ENCODE.record(),
+    };
+    verifier.with_hash(encoded);
+
+    // Password
+    let password = unsafe { ::std::slice::from_raw_parts_mut(password, password_len as usize) };
+    verifier.with_password(password);
+
+    let outcome = match verifier.verify_detailed() {
+        Ok(outcome) => outcome,
+        Err(e) => return set_last_error(e),
+    };
+
+    unsafe {
+        *verification = (&outcome).into();
+    }
+
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+/// Function that verifies a password against a hash just like
+/// `argonautica_verifier_verify_detailed`, except that, instead of the secret key held by the
+/// `argonautica_verifier_t*`, it tries each key of the provided `argonautica_key_set_t*`,
+/// starting with the current one, and sets the `key_id` of the `argonautica_verification_t`
+/// to the ID of the key that matched. Rehashing is also recommended if the key that matched
+/// is not the current one, i.e. after a key rotation
+///
+/// Arguments (from the perspective of C code):
+/// * `verifier` = an `argonautica_verifier_t*` created by `argonautica_verifier_new`
+/// * `key_set` = an `argonautica_key_set_t*` created by `argonautica_key_set_new`. An empty
+///   key set returns `ARGONAUTICA_ERROR_SECRET_KEY_MISSING`
+/// * For a description of the other arguments, see the documentation for
+///   `argonautica_verifier_verify_detailed`
+#[no_mangle]
+pub extern "C" fn argonautica_verifier_verify_with_key_set(
+    verifier: *const argonautica_verifier_t,
+    key_set: *const argonautica_key_set_t,
+    verification: *mut argonautica_verification_t,
+    encoded: *const c_char,
+    password: *mut u8,
+    password_len: u32,
+) -> argonautica_error_t {
+    if verifier.is_null()
+        || key_set.is_null()
+        || verification.is_null()
+        || encoded.is_null()
+        || password.is_null()
+    {
+        return argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR.record();
+    }
+    let handle = unsafe { &*verifier };
+    let key_set = unsafe { &*key_set };
+
+    let mut verifier = handle.verifier();
+
+    // Hash
+    let encoded_cstr = unsafe { CStr::from_ptr(encoded) };
+    let encoded = match encoded_cstr.to_str() {
+        Ok(encoded) => encoded,
+        Err(_) => return argonautica_error_t::ARGONAUTICA_ERROR_UTF8_ENCODE.record(),
+    };
+    verifier.with_hash(encoded);
+
+    // Password
+    let password = unsafe { ::std::slice::from_raw_parts_mut(password, password_len as usize) };
+    verifier.with_password(password);
+
+    let outcome = match verifier.verify_with_key_set(&key_set.key_set) {
+        Ok(outcome) => outcome,
+        Err(e) => return set_last_error(e),
+    };
+
+    let mut out: argonautica_verification_t = (&outcome).into();
+    out.key_id = key_set.id_ptr(outcome.key_id());
+    unsafe {
+        *verification = out;
+    }
+
+    argonautica_error_t::ARGONAUTICA_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::{CStr, CString};
+
+    #[cfg(feature = "binding")]
+    use argonautica::keys::KeyFile;
+    use argonautica::Hasher;
+
+    use super::*;
+    use {
+        argonautica_hasher_configure_iterations, argonautica_hasher_configure_memory_size,
+        argonautica_hasher_free, argonautica_hasher_new, argonautica_key_set_free,
+        argonautica_key_set_insert, argonautica_key_set_new, argonautica_variant_t,
+        argonautica_version_t,
+    };
+
+    #[test]
+    fn test_verifier_handle() {
+        let hash = Hasher::default()
+            .configure_iterations(8)
+            .configure_memory_size(128)
+            .with_additional_data("additional data")
+            .with_password("P@ssw0rd")
+            .with_secret_key("secret")
+            .hash()
+            .unwrap();
+        let encoded = CString::new(hash).unwrap();
+
+        let verifier = argonautica_verifier_new();
+        let additional_data = b"additional data";
+        argonautica_verifier_with_additional_data(
+            verifier,
+            additional_data.as_ptr(),
+            additional_data.len() as u32,
+        );
+        let secret_key = b"secret";
+        argonautica_verifier_with_secret_key(
+            verifier,
+            secret_key.as_ptr(),
+            secret_key.len() as u32,
+        );
+
+        for &(password, expected) in &[(&b"P@ssw0rd"[..], 1), (&b"wrong"[..], 0)] {
+            let mut password = password.to_vec();
+            let mut is_valid = -1;
+            let err = argonautica_verifier_verify(
+                verifier,
+                &mut is_valid,
+                encoded.as_ptr(),
+                password.as_mut_ptr(),
+                password.len() as u32,
+            );
+            assert_eq!(err, argonautica_error_t::ARGONAUTICA_OK);
+            assert_eq!(is_valid, expected);
+        }
+
+        argonautica_verifier_free(verifier);
+    }
+
+    #[cfg(feature = "binding")]
+    #[test]
+    fn test_verifier_handle_binding() {
+        let key = KeyFile::new("key", "secret key, long enough for binding".into(), 1).unwrap();
+        let hash = Hasher::default()
+            .configure_binding(true)
+            .configure_iterations(8)
+            .configure_memory_size(128)
+            .with_additional_data("additional data")
+            .with_key_file(&key)
+            .with_password("P@ssw0rd")
+            .hash()
+            .unwrap();
+        let encoded = CString::new(hash).unwrap();
+
+        let verify = |secret_key: Option<&[u8]>, additional_data: Option<&[u8]>| {
+            let verifier = argonautica_verifier_new();
+            if let Some(additional_data) = additional_data {
+                argonautica_verifier_with_additional_data(
+                    verifier,
+                    additional_data.as_ptr(),
+                    additional_data.len() as u32,
+                );
+            }
+            if let Some(secret_key) = secret_key {
+                argonautica_verifier_with_secret_key(
+                    verifier,
+                    secret_key.as_ptr(),
+                    secret_key.len() as u32,
+                );
+            }
+            let mut password = b"P@ssw0rd".to_vec();
+            let mut is_valid = -1;
+            let err = argonautica_verifier_verify(
+                verifier,
+                &mut is_valid,
+                encoded.as_ptr(),
+                password.as_mut_ptr(),
+                password.len() as u32,
+            );
+            argonautica_verifier_free(verifier);
+            err
+        };
+        let secret_key = Some(&b"secret key, long enough for binding"[..]);
+        assert_eq!(
+            verify(secret_key, Some(b"additional data")),
+            argonautica_error_t::ARGONAUTICA_OK
+        );
+        assert_eq!(
+            verify(secret_key, Some(b"wrong")),
+            argonautica_error_t::ARGONAUTICA_ERROR_ADDITIONAL_DATA_MISMATCH
+        );
+        assert_eq!(
+            verify(secret_key, None),
+            argonautica_error_t::ARGONAUTICA_ERROR_ADDITIONAL_DATA_MISSING
+        );
+        assert_eq!(
+            verify(None, Some(b"additional data")),
+            argonautica_error_t::ARGONAUTICA_ERROR_SECRET_KEY_MISSING
+        );
+    }
+
+    #[test]
+    fn test_verifier_handle_verify_detailed() {
+        let hash = Hasher::default()
+            .configure_iterations(8)
+            .configure_memory_size(128)
+            .opt_out_of_secret_key(true)
+            .with_password("P@ssw0rd")
+            .hash()
+            .unwrap();
+        let encoded = CString::new(hash).unwrap();
+
+        let hasher = argonautica_hasher_new();
+        argonautica_hasher_configure_iterations(hasher, 9);
+        argonautica_hasher_configure_memory_size(hasher, 128);
+        let verifier = argonautica_verifier_new();
+        argonautica_verifier_configure_rehash_hasher(verifier, hasher);
+        argonautica_hasher_free(hasher);
+
+        for &(password, expected) in &[(&b"P@ssw0rd"[..], 1), (&b"wrong"[..], 0)] {
+            let mut password = password.to_vec();
+            let mut verification = verification();
+            let err = argonautica_verifier_verify_detailed(
+                verifier,
+                &mut verification,
+                encoded.as_ptr(),
+                password.as_mut_ptr(),
+                password.len() as u32,
+            );
+            assert_eq!(err, argonautica_error_t::ARGONAUTICA_OK);
+            assert_eq!(verification.is_valid, expected);
+            assert_eq!(verification.rehash_recommended, expected);
+            assert_eq!(verification.hash_len, 32);
+            assert_eq!(verification.iterations, 8);
+            assert_eq!(verification.memory_size, 128);
+            assert_eq!(verification.salt_len, 32);
+            assert_eq!(
+                verification.variant,
+                argonautica_variant_t::ARGONAUTICA_ARGON2ID
+            );
+            assert!(verification.duration_micros > 0);
+            assert!(verification.key_id.is_null());
+        }
+
+        argonautica_verifier_free(verifier);
+    }
+
+    #[test]
+    fn test_verifier_handle_verify_with_key_set() {
+        let old = b"old secret key, long enough";
+        let new = b"new secret key, long enough";
+        let hash = Hasher::default()
+            .configure_iterations(8)
+            .configure_memory_size(128)
+            .with_password("P@ssw0rd")
+            .with_secret_key(&old[..])
+            .hash()
+            .unwrap();
+        let encoded = CString::new(hash).unwrap();
+
+        let key_set = argonautica_key_set_new();
+        let verifier = argonautica_verifier_new();
+        let verify = |password: &[u8]| {
+            let mut password = password.to_vec();
+            let mut verification = verification();
+            let err = argonautica_verifier_verify_with_key_set(
+                verifier,
+                key_set,
+                &mut verification,
+                encoded.as_ptr(),
+                password.as_mut_ptr(),
+                password.len() as u32,
+            );
+            (err, verification)
+        };
+        let (err, _) = verify(b"P@ssw0rd");
+        assert_eq!(
+            err,
+            argonautica_error_t::ARGONAUTICA_ERROR_SECRET_KEY_MISSING
+        );
+
+        for &(id, secret_key, created) in &[(&b"old\0"[..], &old[..], 1), (b"new\0", new, 2)] {
+            let err = argonautica_key_set_insert(
+                key_set,
+                id.as_ptr() as *const c_char,
+                secret_key.as_ptr(),
+                secret_key.len() as u32,
+                created,
+            );
+            assert_eq!(err, argonautica_error_t::ARGONAUTICA_OK);
+        }
+        let (err, verification) = verify(b"P@ssw0rd");
+        assert_eq!(err, argonautica_error_t::ARGONAUTICA_OK);
+        assert_eq!(verification.is_valid, 1);
+        assert_eq!(verification.rehash_recommended, 1);
+        let key_id = unsafe { CStr::from_ptr(verification.key_id) };
+        assert_eq!(key_id.to_str().unwrap(), "old");
+
+        let (err, verification) = verify(b"wrong");
+        assert_eq!(err, argonautica_error_t::ARGONAUTICA_OK);
+        assert_eq!(verification.is_valid, 0);
+        assert!(verification.key_id.is_null());
+
+        argonautica_verifier_free(verifier);
+        argonautica_key_set_free(key_set);
+    }
+
+    fn verification() -> argonautica_verification_t {
+        argonautica_verification_t {
+            duration_micros: 0,
+            hash_len: 0,
+            is_valid: -1,
+            iterations: 0,
+            key_id: ::std::ptr::null(),
+            lanes: 0,
+            memory_size: 0,
+            rehash_recommended: -1,
+            salt_len: 0,
+            variant: argonautica_variant_t::ARGONAUTICA_ARGON2D,
+            version: argonautica_version_t::ARGONAUTICA_0x10,
+        }
+    }
+}
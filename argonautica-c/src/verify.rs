@@ -6,6 +6,7 @@ use argonautica::config::Backend;
 use argonautica::Verifier;
 use libc::{c_char, c_int};
 
+use error::set_last_error;
 use {argonautica_backend_t, argonautica_error_t};
 
 /// Function that verifies a password against a hash. It will modify the provided `is_valid` int
@@ -34,7 +35,7 @@ pub extern "C" fn argonautica_verify(
     threads: u32,
 ) -> argonautica_error_t {
     if is_valid.is_null() || encoded.is_null() || password.is_null() {
-        return argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR;
+        return argonautica_error_t::ARGONAUTICA_ERROR_NULL_PTR.record();
     }
 
     let backend: Backend = backend.into();
@@ -56,7 +57,7 @@ pub extern "C" fn argonautica_verify(
     let encoded_cstr = unsafe { CStr::from_ptr(encoded) };
     let encoded = match encoded_cstr.to_str() {
         Ok(encoded) => encoded,
-        Err(_) => return argonautica_error_t::ARGONAUTICA_ERROR_UTF8_ENCODE,
+        Err(_) => return argonautica_error_t::ARGONAUTICA_ERROR_UTF8_ENCODE.record(),
     };
     verifier.with_hash(encoded);
 
@@ -80,7 +81,7 @@ pub extern "C" fn argonautica_verify(
 
     let valid = match verifier.verify() {
         Ok(valid) => valid,
-        Err(e) => return e.into(),
+        Err(e) => return set_last_error(e),
     };
 
     if valid {
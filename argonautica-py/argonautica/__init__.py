@@ -1,5 +1,13 @@
 __version__ = "0.1.5"
 
 from argonautica.core.argon2 import Argon2
-from argonautica.core.hasher import Hasher, hash
-from argonautica.core.verifier import Verifier, verify
+from argonautica.core.hasher import Hasher, hash, hash_async, hash_raw, needs_rehash
+from argonautica.core.key_set import KeySet
+from argonautica.core.verifier import (
+    Verifier, verify, verify_async, verify_detailed, verify_with_key_set
+)
+from argonautica.utils import HashRaw, Verification
+from argonautica.errors import (
+    ArgonauticaError, DecodeError, MismatchError, MissingInputError, ParameterError,
+    ResourceError
+)
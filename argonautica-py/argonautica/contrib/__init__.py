@@ -0,0 +1,9 @@
+"""
+Adapters that plug argonautica into password hashing frameworks:
+
+* ``argonautica.contrib.django``: a Django ``PASSWORD_HASHERS`` backend
+* ``argonautica.contrib.passlib``: passlib-compatible handlers
+
+Neither Django nor passlib is a dependency of argonautica; each adapter works (and can be
+tested) without its framework installed
+"""
@@ -0,0 +1,137 @@
+import random
+import string
+from typing import Any, Dict, Tuple, Union
+
+from argonautica.config import Backend, Variant, Version
+from argonautica.core.argon2 import Argon2
+from argonautica.data import RandomSalt
+from argonautica.defaults import *
+from argonautica.errors import DecodeError
+from argonautica.utils import HashRaw
+
+try:
+    from django.contrib.auth.hashers import BasePasswordHasher, mask_hash
+    from django.utils.translation import gettext_noop as _
+except ImportError:
+    BasePasswordHasher = object  # type: ignore
+
+    def mask_hash(hash: str, show: int = 6, char: str = "*") -> str:
+        masked = hash[:show]
+        masked += char * len(hash[show:])
+        return masked
+
+    def _(message: str) -> str:  # type: ignore
+        return message
+
+
+class ArgonauticaPasswordHasher(BasePasswordHasher):  # type: ignore
+    """
+    A Django password hasher backed by argonautica. Add it to the ``PASSWORD_HASHERS``
+    setting (first, if you would like new passwords to be hashed with it), e.g.
+
+    ``PASSWORD_HASHERS = ['argonautica.contrib.django.ArgonauticaPasswordHasher', ...]``
+
+    List it in place of Django's own ``Argon2PasswordHasher``, since both use the ``argon2``
+    algorithm name.
+
+    It reads and writes Django's ``argon2$argon2id$v=19$m=...,t=...,p=...$salt$hash``
+    format, i.e. a regular string-encoded hash prefixed with ``argon2``; so it can verify
+    hashes produced by Django's own ``Argon2PasswordHasher`` and vice versa (as long as
+    no secret key or additional data are used), and other services can verify its hashes
+    after stripping the prefix.
+
+    To change the configuration, subclass it and override the class attributes below.
+    Since ``lanes`` defaults to the number of logical cores on the machine, you will
+    probably want to pin it if your servers differ; otherwise ``must_update`` will
+    consider hashes produced on other machines outdated
+    """
+    algorithm = "argon2"
+
+    additional_data = None      # type: Union[bytes, str, None]
+    backend = DEFAULT_BACKEND   # type: Backend
+    hash_len = DEFAULT_HASH_LEN
+    iterations = DEFAULT_ITERATIONS
+    lanes = DEFAULT_LANES
+    memory_size = DEFAULT_MEMORY_SIZE
+    salt_len = DEFAULT_SALT_LEN
+    secret_key = None           # type: Union[bytes, str, None]
+    threads = DEFAULT_THREADS
+    variant = DEFAULT_VARIANT   # type: Variant
+    version = DEFAULT_VERSION   # type: Version
+
+    def argon2(self) -> Argon2:
+        return Argon2(
+            additional_data=self.additional_data,
+            backend=self.backend,
+            hash_len=self.hash_len,
+            iterations=self.iterations,
+            lanes=self.lanes,
+            memory_size=self.memory_size,
+            salt=RandomSalt(self.salt_len),
+            secret_key=self.secret_key,
+            threads=self.threads,
+            variant=self.variant,
+            version=self.version,
+        )
+
+    def salt(self) -> str:
+        chars = string.ascii_letters + string.digits
+        rng = random.SystemRandom()
+        return "".join(rng.choice(chars) for i in range(self.salt_len))
+
+    def encode(self, password: str, salt: str) -> str:
+        hash = self.argon2().hash(password=password, salt=salt)
+        return self.algorithm + hash
+
+    def decode(self, encoded: str) -> Dict[str, Any]:
+        algorithm, hash = self._split(encoded)
+        hash_raw = HashRaw.parse(hash)
+        encoded_salt, encoded_hash = hash.split("$")[-2:]
+        return {
+            "algorithm": algorithm,
+            "hash": encoded_hash,
+            "memory_cost": hash_raw.memory_size,
+            "parallelism": hash_raw.lanes,
+            "salt": encoded_salt,
+            "time_cost": hash_raw.iterations,
+            "variety": str(hash_raw.variant),
+            "version": int(str(hash_raw.version)),
+        }
+
+    def verify(self, password: str, encoded: str) -> bool:
+        hash = self._split(encoded)[1]
+        try:
+            return self.argon2().verify(hash=hash, password=password)
+        except DecodeError:
+            return False
+
+    def safe_summary(self, encoded: str) -> Dict[str, Any]:
+        decoded = self.decode(encoded)
+        return {
+            _("algorithm"): decoded["algorithm"],
+            _("variety"): decoded["variety"],
+            _("version"): decoded["version"],
+            _("memory cost"): decoded["memory_cost"],
+            _("time cost"): decoded["time_cost"],
+            _("parallelism"): decoded["parallelism"],
+            _("salt"): mask_hash(decoded["salt"]),
+            _("hash"): mask_hash(decoded["hash"]),
+        }
+
+    def must_update(self, encoded: str) -> bool:
+        hash = self._split(encoded)[1]
+        try:
+            return self.argon2().needs_rehash(hash=hash)
+        except DecodeError:
+            return True
+
+    def harden_runtime(self, password: str, encoded: str) -> None:
+        # The number of iterations is fixed by the hash itself, so there is nothing to do
+        pass
+
+    def _split(self, encoded: str) -> Tuple[str, str]:
+        algorithm, separator, rest = encoded.partition("$")
+        if algorithm != self.algorithm:
+            raise ValueError(
+                "Hash is not of the expected {!r} format".format(self.algorithm))
+        return algorithm, "$" + rest
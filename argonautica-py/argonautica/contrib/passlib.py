@@ -0,0 +1,122 @@
+from typing import Any, Optional, Tuple, Type, Union
+
+from argonautica.config import Backend, Variant, Version
+from argonautica.core.argon2 import Argon2
+from argonautica.data import RandomSalt
+from argonautica.defaults import *
+from argonautica.errors import DecodeError
+
+
+class ArgonauticaHandler:
+    """
+    A passlib-compatible password hash handler backed by argonautica. It implements the
+    ``PasswordHash`` interface passlib expects (``hash``, ``verify``, ``identify``,
+    ``needs_update`` and ``using``); so it can be used on its own or registered with
+    passlib and used in a ``CryptContext``, e.g.
+
+    ``CryptContext(schemes=[ArgonauticaHandler.using(secret_key=...)])``
+
+    It reads and writes regular string-encoded hashes (``$argon2id$v=19$...``), i.e. the
+    same format as passlib's own ``argon2`` handler
+    """
+    name = "argonautica"
+    prefix = ""
+    setting_kwds = (
+        "additional_data",
+        "backend",
+        "hash_len",
+        "iterations",
+        "lanes",
+        "memory_size",
+        "salt_len",
+        "secret_key",
+        "threads",
+        "variant",
+        "version",
+    )  # type: Tuple[str, ...]
+    context_kwds = ()  # type: Tuple[str, ...]
+    is_disabled = False
+    truncate_size = None  # type: Optional[int]
+
+    additional_data = None      # type: Union[bytes, str, None]
+    backend = DEFAULT_BACKEND   # type: Backend
+    hash_len = DEFAULT_HASH_LEN
+    iterations = DEFAULT_ITERATIONS
+    lanes = DEFAULT_LANES
+    memory_size = DEFAULT_MEMORY_SIZE
+    salt_len = DEFAULT_SALT_LEN
+    secret_key = None           # type: Union[bytes, str, None]
+    threads = DEFAULT_THREADS
+    variant = DEFAULT_VARIANT   # type: Variant
+    version = DEFAULT_VERSION   # type: Version
+
+    @classmethod
+    def using(cls, relaxed: bool = False, **kwds: Any) -> Type['ArgonauticaHandler']:
+        """
+        Returns a subclass of this handler with the provided settings
+        """
+        for key in kwds:
+            if key not in cls.setting_kwds:
+                raise TypeError("{} does not support the {!r} setting".format(cls.name, key))
+        return type(cls.__name__, (cls,), kwds)
+
+    @classmethod
+    def argon2(cls) -> Argon2:
+        return Argon2(
+            additional_data=cls.additional_data,
+            backend=cls.backend,
+            hash_len=cls.hash_len,
+            iterations=cls.iterations,
+            lanes=cls.lanes,
+            memory_size=cls.memory_size,
+            salt=RandomSalt(cls.salt_len),
+            secret_key=cls.secret_key,
+            threads=cls.threads,
+            variant=cls.variant,
+            version=cls.version,
+        )
+
+    @classmethod
+    def hash(cls, secret: Union[bytes, str], **kwds: Any) -> str:
+        if kwds:
+            return cls.using(**kwds).hash(secret)
+        return cls.prefix + cls.argon2().hash(password=secret)
+
+    @classmethod
+    def verify(cls, secret: Union[bytes, str], hash: str) -> bool:
+        hash = cls._strip(hash)
+        try:
+            return cls.argon2().verify(hash=hash, password=secret)
+        except DecodeError:
+            return False
+
+    @classmethod
+    def identify(cls, hash: Union[bytes, str]) -> bool:
+        if isinstance(hash, bytes):
+            hash = hash.decode("utf-8")
+        return hash.startswith(cls.prefix + "$argon2")
+
+    @classmethod
+    def needs_update(cls, hash: str, secret: Union[bytes, str, None] = None) -> bool:
+        hash = cls._strip(hash)
+        try:
+            return cls.argon2().needs_rehash(hash=hash)
+        except DecodeError:
+            return True
+
+    @classmethod
+    def _strip(cls, hash: str) -> str:
+        if not cls.identify(hash):
+            raise ValueError("Hash is not a valid {} hash".format(cls.name))
+        return hash[len(cls.prefix):]
+
+
+class DjangoArgonauticaHandler(ArgonauticaHandler):
+    """
+    Same as ``ArgonauticaHandler``, except that it reads and writes Django's
+    ``argon2$argon2id$v=19$...`` format (the same format as
+    ``argonautica.contrib.django.ArgonauticaPasswordHasher`` and passlib's own
+    ``django_argon2`` handler)
+    """
+    name = "django_argonautica"
+    prefix = "argon2"
@@ -5,7 +5,7 @@ from argonautica.core.hasher import Hasher
 from argonautica.core.verifier import Verifier
 from argonautica.data import RandomSalt
 from argonautica.defaults import *
-from argonautica.utils import Void, VOID
+from argonautica.utils import HashRaw, Void, VOID
 
 
 class Argon2:
@@ -86,6 +86,73 @@ class Argon2:
             version=version
         )
 
+    async def hash_async(
+        self,
+        *,
+        password:           Union[bytes, str],
+
+        additional_data:    Union[bytes, str, None, Void] = VOID,
+        backend:            Union[Backend, Void] = VOID,
+        hash_len:           Union[int, Void] = VOID,
+        iterations:         Union[int, Void] = VOID,
+        lanes:              Union[int, Void] = VOID,
+        memory_size:        Union[int, Void] = VOID,
+        salt:               Union[bytes, RandomSalt, str, Void] = VOID,
+        secret_key:         Union[bytes, str, None, Void] = VOID,
+        threads:            Union[int, Void] = VOID,
+        variant:            Union[Variant, Void] = VOID,
+        version:            Union[Version, Void] = VOID
+    ) -> str:
+        return await self.hasher.hash_async(
+            additional_data=additional_data,
+            backend=backend,
+            hash_len=hash_len,
+            iterations=iterations,
+            lanes=lanes,
+            memory_size=memory_size,
+            password=password,
+            salt=salt,
+            secret_key=secret_key,
+            threads=threads,
+            variant=variant,
+            version=version
+        )
+
+    def hash_raw(
+        self,
+        *,
+        password:           Union[bytes, str],
+
+        additional_data:    Union[bytes, str, None, Void] = VOID,
+        backend:            Union[Backend, Void] = VOID,
+        hash_len:           Union[int, Void] = VOID,
+        iterations:         Union[int, Void] = VOID,
+        lanes:              Union[int, Void] = VOID,
+        memory_size:        Union[int, Void] = VOID,
+        salt:               Union[bytes, RandomSalt, str, Void] = VOID,
+        secret_key:         Union[bytes, str, None, Void] = VOID,
+        threads:            Union[int, Void] = VOID,
+        variant:            Union[Variant, Void] = VOID,
+        version:            Union[Version, Void] = VOID
+    ) -> HashRaw:
+        return self.hasher.hash_raw(
+            additional_data=additional_data,
+            backend=backend,
+            hash_len=hash_len,
+            iterations=iterations,
+            lanes=lanes,
+            memory_size=memory_size,
+            password=password,
+            salt=salt,
+            secret_key=secret_key,
+            threads=threads,
+            variant=variant,
+            version=version
+        )
+
+    def needs_rehash(self, *, hash: str) -> bool:
+        return self.hasher.needs_rehash(hash=hash)
+
     def verify(
         self,
         *,
@@ -106,6 +173,26 @@ class Argon2:
             threads=threads
         )
 
+    async def verify_async(
+        self,
+        *,
+        hash:               str,
+        password:           Union[bytes, str],
+
+        additional_data:    Union[bytes, str, None, Void] = VOID,
+        backend:            Union[Backend, Void] = VOID,
+        secret_key:         Union[bytes, str, None, Void] = VOID,
+        threads:            Union[int, Void] = VOID
+    ) -> bool:
+        return await self.verifier.verify_async(
+            additional_data=additional_data,
+            backend=backend,
+            hash=hash,
+            password=password,
+            secret_key=secret_key,
+            threads=threads
+        )
+
     @property
     def additional_data(self) -> Union[bytes, str, None]:
         return self.hasher.additional_data
@@ -35,3 +35,17 @@ def init_ffi() -> Tuple[FFI, Any]:
 
 
 (ffi, lib) = init_ffi()
+
+
+def error_message(err: int) -> str:
+    """
+    Returns the detailed message of the most recent error on the calling thread, which
+    includes context such as which length was too long or which part of a hash failed to
+    decode, falling back to the static message for ``err`` if there is no detail
+    """
+    error_msg_ptr = lib.argonautica_error_detail()
+    if error_msg_ptr == ffi.NULL:
+        error_msg_ptr = lib.argonautica_error_msg(err)
+    error_msg = ffi.string(error_msg_ptr).decode("utf-8")
+    lib.argonautica_error_clear()
+    return error_msg
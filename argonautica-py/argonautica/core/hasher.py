@@ -3,9 +3,11 @@ from typing import Union
 
 from argonautica.config import Backend, Variant, Version
 from argonautica.core.ffi import ffi, lib
+from argonautica.core.non_blocking import hash_non_blocking
 from argonautica.data import RandomSalt
 from argonautica.defaults import *
-from argonautica.utils import Void, VOID
+from argonautica.errors import error_from_code
+from argonautica.utils import HashRaw, Void, VOID
 
 
 class Hasher:
@@ -109,6 +111,126 @@ class Hasher:
             version=version
         )
 
+    async def hash_async(
+        self,
+        *,
+        password:           Union[bytes, str],
+
+        additional_data:    Union[bytes, str, None, Void] = VOID,
+        backend:            Union[Backend, Void] = VOID,
+        hash_len:           Union[int, Void] = VOID,
+        iterations:         Union[int, Void] = VOID,
+        lanes:              Union[int, Void] = VOID,
+        memory_size:        Union[int, Void] = VOID,
+        salt:               Union[bytes, RandomSalt, str, Void] = VOID,
+        secret_key:         Union[bytes, str, None, Void] = VOID,
+        threads:            Union[int, Void] = VOID,
+        variant:            Union[Variant, Void] = VOID,
+        version:            Union[Version, Void] = VOID
+    ) -> str:
+        if isinstance(additional_data, Void):
+            additional_data = self.additional_data
+        if isinstance(backend, Void):
+            backend = self.backend
+        if isinstance(hash_len, Void):
+            hash_len = self.hash_len
+        if isinstance(iterations, Void):
+            iterations = self.iterations
+        if isinstance(lanes, Void):
+            lanes = self.lanes
+        if isinstance(memory_size, Void):
+            memory_size = self.memory_size
+        if isinstance(salt, Void):
+            salt = self.salt
+        if isinstance(secret_key, Void):
+            secret_key = self.secret_key
+        if isinstance(threads, Void):
+            threads = self.threads
+        if isinstance(variant, Void):
+            variant = self.variant
+        if isinstance(version, Void):
+            version = self.version
+        return await hash_async(
+            additional_data=additional_data,
+            backend=backend,
+            hash_len=hash_len,
+            iterations=iterations,
+            lanes=lanes,
+            memory_size=memory_size,
+            password=password,
+            salt=salt,
+            secret_key=secret_key,
+            threads=threads,
+            variant=variant,
+            version=version
+        )
+
+    def hash_raw(
+        self,
+        *,
+        password:           Union[bytes, str],
+
+        additional_data:    Union[bytes, str, None, Void] = VOID,
+        backend:            Union[Backend, Void] = VOID,
+        hash_len:           Union[int, Void] = VOID,
+        iterations:         Union[int, Void] = VOID,
+        lanes:              Union[int, Void] = VOID,
+        memory_size:        Union[int, Void] = VOID,
+        salt:               Union[bytes, RandomSalt, str, Void] = VOID,
+        secret_key:         Union[bytes, str, None, Void] = VOID,
+        threads:            Union[int, Void] = VOID,
+        variant:            Union[Variant, Void] = VOID,
+        version:            Union[Version, Void] = VOID
+    ) -> HashRaw:
+        if isinstance(additional_data, Void):
+            additional_data = self.additional_data
+        if isinstance(backend, Void):
+            backend = self.backend
+        if isinstance(hash_len, Void):
+            hash_len = self.hash_len
+        if isinstance(iterations, Void):
+            iterations = self.iterations
+        if isinstance(lanes, Void):
+            lanes = self.lanes
+        if isinstance(memory_size, Void):
+            memory_size = self.memory_size
+        if isinstance(salt, Void):
+            salt = self.salt
+        if isinstance(secret_key, Void):
+            secret_key = self.secret_key
+        if isinstance(threads, Void):
+            threads = self.threads
+        if isinstance(variant, Void):
+            variant = self.variant
+        if isinstance(version, Void):
+            version = self.version
+        return hash_raw(
+            additional_data=additional_data,
+            backend=backend,
+            hash_len=hash_len,
+            iterations=iterations,
+            lanes=lanes,
+            memory_size=memory_size,
+            password=password,
+            salt=salt,
+            secret_key=secret_key,
+            threads=threads,
+            variant=variant,
+            version=version
+        )
+
+    def needs_rehash(self, *, hash: str) -> bool:
+        return needs_rehash(
+            hash=hash,
+            hash_len=self.hash_len,
+            iterations=self.iterations,
+            lanes=self.lanes,
+            memory_size=self.memory_size,
+            salt=self.salt,
+            variant=self.variant,
+            version=self.version
+        )
+
 
 def hash(
     *,
@@ -168,13 +290,179 @@ def hash(
         version.value,
     )
     if err != lib.ARGONAUTICA_OK:
-        error_msg_ptr = lib.argonautica_error_msg(err)
-        error_msg = ffi.string(error_msg_ptr).decode("utf-8")
-        raise Exception(error_msg)
+        raise error_from_code(err)
     hash = ffi.string(encoded).decode("utf-8")
     return hash
 
 
+async def hash_async(
+    *,
+    password:           Union[bytes, str],
+    secret_key:         Union[bytes, str, None],
+
+    additional_data:    Union[bytes, str, None] = None,
+    backend:            Backend = DEFAULT_BACKEND,
+    hash_len:           int = DEFAULT_HASH_LEN,
+    iterations:         int = DEFAULT_ITERATIONS,
+    lanes:              int = DEFAULT_LANES,
+    memory_size:        int = DEFAULT_MEMORY_SIZE,
+    salt:               Union[bytes, RandomSalt, str] = DEFAULT_SALT,
+    threads:            int = DEFAULT_THREADS,
+    variant:            Variant = DEFAULT_VARIANT,
+    version:            Version = DEFAULT_VERSION
+) -> str:
+    """
+    A standalone hash coroutine. The work is performed on argonautica's thread pool, so
+    awaiting it does not block the event loop. Cancelling the awaiting task cancels the hash
+    """
+    data = Validator(
+        additional_data=additional_data,
+        password=password,
+        salt=salt,
+        secret_key=secret_key,
+    )
+    hasher = new_hasher(
+        data=data,
+        backend=backend,
+        hash_len=hash_len,
+        iterations=iterations,
+        lanes=lanes,
+        memory_size=memory_size,
+        threads=threads,
+        variant=variant,
+        version=version,
+    )
+    return await hash_non_blocking(hasher, data.password)
+
+
+def hash_raw(
+    *,
+    password:           Union[bytes, str],
+    secret_key:         Union[bytes, str, None],
+
+    additional_data:    Union[bytes, str, None] = None,
+    backend:            Backend = DEFAULT_BACKEND,
+    hash_len:           int = DEFAULT_HASH_LEN,
+    iterations:         int = DEFAULT_ITERATIONS,
+    lanes:              int = DEFAULT_LANES,
+    memory_size:        int = DEFAULT_MEMORY_SIZE,
+    salt:               Union[bytes, RandomSalt, str] = DEFAULT_SALT,
+    threads:            int = DEFAULT_THREADS,
+    variant:            Variant = DEFAULT_VARIANT,
+    version:            Version = DEFAULT_VERSION
+) -> HashRaw:
+    """
+    A standalone hash function that, instead of a string-encoded hash, returns a ``HashRaw``
+    holding the parameters, the raw salt bytes and the raw hash bytes
+    """
+    data = Validator(
+        additional_data=additional_data,
+        password=password,
+        salt=salt,
+        secret_key=secret_key,
+    )
+    hasher = new_hasher(
+        data=data,
+        backend=backend,
+        hash_len=hash_len,
+        iterations=iterations,
+        lanes=lanes,
+        memory_size=memory_size,
+        threads=threads,
+        variant=variant,
+        version=version,
+    )
+    hash_buf = ffi.new("uint8_t[]", hash_len)
+    salt_buf = ffi.new("uint8_t[]", data.salt_len)
+    out = ffi.new("argonautica_hash_raw_t*")
+    out.hash = hash_buf
+    out.hash_len = hash_len
+    out.salt = salt_buf
+    out.salt_len = data.salt_len
+    err = lib.argonautica_hasher_hash_raw(hasher, out, data.password, data.password_len)
+    if err != lib.ARGONAUTICA_OK:
+        raise error_from_code(err)
+    return HashRaw._from_c(out)
+
+
+def needs_rehash(
+    *,
+    hash:               str,
+
+    hash_len:           int = DEFAULT_HASH_LEN,
+    iterations:         int = DEFAULT_ITERATIONS,
+    lanes:              int = DEFAULT_LANES,
+    memory_size:        int = DEFAULT_MEMORY_SIZE,
+    salt:               Union[bytes, RandomSalt, str] = DEFAULT_SALT,
+    variant:            Variant = DEFAULT_VARIANT,
+    version:            Version = DEFAULT_VERSION
+) -> bool:
+    """
+    A standalone function that returns ``True`` if a string-encoded hash was produced with
+    parameters (hash length, iterations, lanes, memory size, salt length, variant or version)
+    that differ from the ones provided, i.e. if the password should be rehashed the next time
+    it is successfully verified
+    """
+    data = Validator(
+        additional_data=None,
+        password=b'',
+        salt=salt,
+        secret_key=None,
+    )
+    hasher = new_hasher(
+        data=data,
+        backend=DEFAULT_BACKEND,
+        hash_len=hash_len,
+        iterations=iterations,
+        lanes=lanes,
+        memory_size=memory_size,
+        threads=DEFAULT_THREADS,
+        variant=variant,
+        version=version,
+    )
+    result = ffi.new("int*", 0)
+    err = lib.argonautica_hasher_needs_rehash(hasher, result, hash.encode('utf-8'))
+    if err != lib.ARGONAUTICA_OK:
+        raise error_from_code(err)
+    return result[0] == 1
+
+
+def new_hasher(
+    *,
+    data:               'Validator',
+    backend:            Backend,
+    hash_len:           int,
+    iterations:         int,
+    lanes:              int,
+    memory_size:        int,
+    threads:            int,
+    variant:            Variant,
+    version:            Version
+) -> ffi.CData:
+    """
+    Creates an ``argonautica_hasher_t*`` holding the provided configuration and data, which
+    is freed automatically once it is garbage collected
+    """
+    hasher = ffi.gc(lib.argonautica_hasher_new(), lib.argonautica_hasher_free)
+    lib.argonautica_hasher_configure_backend(hasher, backend.value)
+    lib.argonautica_hasher_configure_hash_len(hasher, hash_len)
+    lib.argonautica_hasher_configure_iterations(hasher, iterations)
+    lib.argonautica_hasher_configure_lanes(hasher, lanes)
+    lib.argonautica_hasher_configure_memory_size(hasher, memory_size)
+    lib.argonautica_hasher_configure_threads(hasher, threads)
+    lib.argonautica_hasher_configure_variant(hasher, variant.value)
+    lib.argonautica_hasher_configure_version(hasher, version.value)
+    if data.additional_data != ffi.NULL:
+        lib.argonautica_hasher_with_additional_data(
+            hasher, data.additional_data, data.additional_data_len)
+    lib.argonautica_hasher_with_salt(hasher, data.salt, data.salt_len)
+    if data.secret_key == ffi.NULL:
+        lib.argonautica_hasher_opt_out_of_secret_key(hasher, 1)
+    else:
+        lib.argonautica_hasher_with_secret_key(hasher, data.secret_key, data.secret_key_len)
+    return hasher
+
+
 class Validator:
     def __init__(
         self,
@@ -0,0 +1,56 @@
+from typing import Union
+
+from argonautica.core.ffi import ffi, lib
+from argonautica.errors import error_from_code
+
+
+class KeySet:
+    """
+    A class that represents a set of secret keys, each with a key ID, for key rotation, i.e.
+    the current (most recently created) key and all previous keys that may still be needed to
+    verify existing hashes. Pass it to ``verify_with_key_set``, which tries each of its keys
+    and reports the ID of the key that matched
+    """
+    __slots__ = ['_key_set']
+
+    def __init__(self) -> None:
+        self._key_set = ffi.gc(lib.argonautica_key_set_new(), lib.argonautica_key_set_free)
+
+    def insert(
+        self,
+        *,
+        created:    int,
+        id:         str,
+        secret_key: Union[bytes, str]
+    ) -> None:
+        """
+        Adds a secret key (at least 16 bytes long) to the set. ``id`` must be non-empty, consist
+        only of ASCII letters, digits, ``-``, ``_`` and ``.``, and not already be in the set;
+        ``created`` is the creation time of the key in seconds since the Unix epoch
+        """
+        if isinstance(secret_key, str):
+            secret_key = secret_key.encode('utf-8')
+        elif not isinstance(secret_key, bytes):
+            raise TypeError("Type of secret_key must be bytes or str")
+        err = lib.argonautica_key_set_insert(
+            self._key_set,
+            id.encode('utf-8'),
+            secret_key,
+            len(secret_key),
+            created,
+        )
+        if err != lib.ARGONAUTICA_OK:
+            raise error_from_code(err)
+
+    @classmethod
+    def load_dir(cls, dir: str) -> 'KeySet':
+        """
+        Reads every key file (i.e. every file with the extension ``.key``) in the provided
+        directory into a new ``KeySet``. On Unix, the key files must not be accessible by users
+        other than their owner
+        """
+        key_set = cls()
+        err = lib.argonautica_key_set_load_dir(key_set._key_set, dir.encode('utf-8'))
+        if err != lib.ARGONAUTICA_OK:
+            raise error_from_code(err)
+        return key_set
@@ -0,0 +1,112 @@
+import asyncio
+from typing import Any, Optional, Set
+
+from argonautica.core.ffi import ffi, lib
+from argonautica.errors import error_from_code
+
+
+_PENDING = set()  # type: Set[_Call]
+# 👆 Keeps every in-flight call (and therefore its cffi handle) alive until argonautica
+# has invoked its callback, which happens exactly once per successfully scheduled call
+
+
+class _Call:
+    """
+    The state of a single non-blocking call: the future the coroutine awaits and the
+    event loop that owns it
+    """
+    __slots__ = ['future', 'handle', 'loop']
+
+    def __init__(self, loop: asyncio.AbstractEventLoop) -> None:
+        self.loop = loop
+        self.future = loop.create_future()
+        self.handle = ffi.new_handle(self)
+
+    def resolve(self, result: Any, exception: Optional[Exception]) -> None:
+        # Called on one of argonautica's worker threads
+        try:
+            self.loop.call_soon_threadsafe(self._set, result, exception)
+        except RuntimeError:
+            # The event loop has been closed, so nobody is waiting for the result anymore
+            _PENDING.discard(self)
+
+    def _set(self, result: Any, exception: Optional[Exception]) -> None:
+        # Called on the event loop's thread
+        _PENDING.discard(self)
+        if self.future.done():
+            return
+        if exception is not None:
+            self.future.set_exception(exception)
+        else:
+            self.future.set_result(result)
+
+
+@ffi.callback("argonautica_hash_callback_t")
+def _hash_callback(err: int, encoded: Any, user_data: Any) -> None:
+    call = ffi.from_handle(user_data)
+    if err == lib.ARGONAUTICA_OK:
+        call.resolve(ffi.string(encoded).decode("utf-8"), None)
+    else:
+        # The error detail is thread-local, so it has to be read here, on the worker thread
+        call.resolve(None, error_from_code(err))
+
+
+@ffi.callback("argonautica_verify_callback_t")
+def _verify_callback(err: int, is_valid: int, user_data: Any) -> None:
+    call = ffi.from_handle(user_data)
+    if err == lib.ARGONAUTICA_OK:
+        call.resolve(is_valid == 1, None)
+    else:
+        call.resolve(None, error_from_code(err))
+
+
+async def hash_non_blocking(hasher: Any, password: bytes) -> str:
+    """
+    Hashes ``password`` with an ``argonautica_hasher_t*`` on argonautica's thread pool,
+    without blocking the event loop (or holding the GIL). If the awaiting task is cancelled,
+    the call is cancelled as well
+    """
+    return await _run(
+        lambda cancel_token, call: lib.argonautica_hasher_hash_non_blocking(
+            hasher,
+            password,
+            len(password),
+            cancel_token,
+            _hash_callback,
+            call.handle,
+        )
+    )
+
+
+async def verify_non_blocking(verifier: Any, hash: str, password: bytes) -> bool:
+    """
+    Verifies ``password`` against ``hash`` with an ``argonautica_verifier_t*`` on
+    argonautica's thread pool, without blocking the event loop (or holding the GIL). If the
+    awaiting task is cancelled, the call is cancelled as well
+    """
+    return await _run(
+        lambda cancel_token, call: lib.argonautica_verifier_verify_non_blocking(
+            verifier,
+            hash.encode('utf-8'),
+            password,
+            len(password),
+            cancel_token,
+            _verify_callback,
+            call.handle,
+        )
+    )
+
+
+async def _run(schedule: Any) -> Any:
+    call = _Call(asyncio.get_event_loop())
+    cancel_token = ffi.gc(lib.argonautica_cancel_token_new(), lib.argonautica_cancel_token_free)
+    _PENDING.add(call)
+    err = schedule(cancel_token, call)
+    if err != lib.ARGONAUTICA_OK:
+        _PENDING.discard(call)
+        raise error_from_code(err)
+    try:
+        return await call.future
+    except asyncio.CancelledError:
+        lib.argonautica_cancel_token_cancel(cancel_token)
+        raise
@@ -1,9 +1,13 @@
-from typing import Union
+from typing import Optional, Union
 
 from argonautica.config import Backend
 from argonautica.core.ffi import ffi, lib
+from argonautica.core.hasher import Hasher, Validator, new_hasher
+from argonautica.core.key_set import KeySet
+from argonautica.core.non_blocking import verify_non_blocking
 from argonautica.defaults import *
-from argonautica.utils import Void, VOID
+from argonautica.errors import error_from_code
+from argonautica.utils import Verification, Void, VOID
 
 
 class Verifier:
@@ -59,6 +63,92 @@ class Verifier:
             threads=threads
         )
 
+    def verify_detailed(
+        self,
+        *,
+        hash:               str,
+        password:           Union[bytes, str],
+
+        additional_data:    Union[bytes, str, None, Void] = VOID,
+        backend:            Union[Backend, Void] = VOID,
+        hasher:             Optional[Hasher] = None,
+        secret_key:         Union[bytes, str, None, Void] = VOID,
+        threads:            Union[int, Void] = VOID
+    ) -> Verification:
+        if isinstance(additional_data, Void):
+            additional_data = self.additional_data
+        if isinstance(backend, Void):
+            backend = self.backend
+        if isinstance(secret_key, Void):
+            secret_key = self.secret_key
+        if isinstance(threads, Void):
+            threads = self.threads
+        return verify_detailed(
+            additional_data=additional_data,
+            backend=backend,
+            hash=hash,
+            hasher=hasher,
+            password=password,
+            secret_key=secret_key,
+            threads=threads
+        )
+
+    def verify_with_key_set(
+        self,
+        *,
+        hash:               str,
+        key_set:            KeySet,
+        password:           Union[bytes, str],
+
+        additional_data:    Union[bytes, str, None, Void] = VOID,
+        backend:            Union[Backend, Void] = VOID,
+        hasher:             Optional[Hasher] = None,
+        threads:            Union[int, Void] = VOID
+    ) -> Verification:
+        if isinstance(additional_data, Void):
+            additional_data = self.additional_data
+        if isinstance(backend, Void):
+            backend = self.backend
+        if isinstance(threads, Void):
+            threads = self.threads
+        return verify_with_key_set(
+            additional_data=additional_data,
+            backend=backend,
+            hash=hash,
+            hasher=hasher,
+            key_set=key_set,
+            password=password,
+            threads=threads
+        )
+
+    async def verify_async(
+        self,
+        *,
+        hash:               str,
+        password:           Union[bytes, str],
+
+        additional_data:    Union[bytes, str, None, Void] = VOID,
+        backend:            Union[Backend, Void] = VOID,
+        secret_key:         Union[bytes, str, None, Void] = VOID,
+        threads:            Union[int, Void] = VOID
+    ) -> bool:
+        if isinstance(additional_data, Void):
+            additional_data = self.additional_data
+        if isinstance(backend, Void):
+            backend = self.backend
+        if isinstance(secret_key, Void):
+            secret_key = self.secret_key
+        if isinstance(threads, Void):
+            threads = self.threads
+        return await verify_async(
+            additional_data=additional_data,
+            backend=backend,
+            hash=hash,
+            password=password,
+            secret_key=secret_key,
+            threads=threads
+        )
+
 
 def verify(
     *,
@@ -122,11 +212,180 @@ def verify(
         threads,
     )
     if err != lib.ARGONAUTICA_OK:
-        error_msg_ptr = lib.argonautica_error_msg(err)
-        error_msg = ffi.string(error_msg_ptr).decode("utf-8")
-        raise Exception(error_msg)
+        raise error_from_code(err)
 
     if is_valid[0] == 1:
         return True
 
     return False
+
+
+def verify_detailed(
+    *,
+    hash:               str,
+    password:           Union[bytes, str],
+    secret_key:         Union[bytes, str, None],
+
+    additional_data:    Union[bytes, str, None] = None,
+    backend:            Backend = DEFAULT_BACKEND,
+    hasher:             Optional[Hasher] = None,
+    threads:            int = DEFAULT_THREADS
+) -> Verification:
+    """
+    A standalone verify function that returns a ``Verification`` instead of a ``bool``. If
+    ``hasher`` (the ``Hasher`` you currently hash passwords with) is provided, the
+    ``Verification`` recommends a rehash when the password matched but the hash was produced
+    with parameters (hash length, iterations, lanes, memory size, variant or version) that
+    differ from the hasher's
+    """
+    if isinstance(password, str):
+        password = password.encode('utf-8')
+    elif not isinstance(password, bytes):
+        raise TypeError("Type of password must be bytes or str")
+    verifier = new_verifier(
+        additional_data=additional_data,
+        backend=backend,
+        secret_key=secret_key,
+        threads=threads,
+    )
+    if hasher is not None:
+        configure_rehash_hasher(verifier, hasher)
+    out = ffi.new("argonautica_verification_t*")
+    err = lib.argonautica_verifier_verify_detailed(
+        verifier,
+        out,
+        hash.encode('utf-8'),
+        password,
+        len(password),
+    )
+    if err != lib.ARGONAUTICA_OK:
+        raise error_from_code(err)
+    return Verification._from_c(out)
+
+
+def verify_with_key_set(
+    *,
+    hash:               str,
+    key_set:            KeySet,
+    password:           Union[bytes, str],
+
+    additional_data:    Union[bytes, str, None] = None,
+    backend:            Backend = DEFAULT_BACKEND,
+    hasher:             Optional[Hasher] = None,
+    threads:            int = DEFAULT_THREADS
+) -> Verification:
+    """
+    A standalone verify function that, instead of a single secret key, tries each key of the
+    provided ``KeySet``, starting with the current one, and returns a ``Verification`` whose
+    ``key_id`` is the ID of the key that matched. Besides the cases described for
+    ``verify_detailed``, the ``Verification`` recommends a rehash when the key that matched is
+    not the current one, i.e. after a key rotation
+    """
+    if isinstance(password, str):
+        password = password.encode('utf-8')
+    elif not isinstance(password, bytes):
+        raise TypeError("Type of password must be bytes or str")
+    verifier = new_verifier(
+        additional_data=additional_data,
+        backend=backend,
+        secret_key=None,
+        threads=threads,
+    )
+    if hasher is not None:
+        configure_rehash_hasher(verifier, hasher)
+    out = ffi.new("argonautica_verification_t*")
+    err = lib.argonautica_verifier_verify_with_key_set(
+        verifier,
+        key_set._key_set,
+        out,
+        hash.encode('utf-8'),
+        password,
+        len(password),
+    )
+    if err != lib.ARGONAUTICA_OK:
+        raise error_from_code(err)
+    # The key ID is owned by the key set, so it is copied before the key set can be freed
+    return Verification._from_c(out)
+
+
+async def verify_async(
+    *,
+    hash:               str,
+    password:           Union[bytes, str],
+    secret_key:         Union[bytes, str, None],
+
+    additional_data:    Union[bytes, str, None] = None,
+    backend:            Backend = DEFAULT_BACKEND,
+    threads:            int = DEFAULT_THREADS
+) -> bool:
+    """
+    A standalone verify coroutine. The work is performed on argonautica's thread pool, so
+    awaiting it does not block the event loop. Cancelling the awaiting task cancels the
+    verification
+    """
+    if isinstance(password, str):
+        password = password.encode('utf-8')
+    elif not isinstance(password, bytes):
+        raise TypeError("Type of password must be bytes or str")
+    verifier = new_verifier(
+        additional_data=additional_data,
+        backend=backend,
+        secret_key=secret_key,
+        threads=threads,
+    )
+    return await verify_non_blocking(verifier, hash, password)
+
+
+def configure_rehash_hasher(verifier: ffi.CData, hasher: Hasher) -> None:
+    """
+    Configures an ``argonautica_verifier_t*`` to recommend a rehash when a hash was produced
+    with parameters that differ from those of the provided ``Hasher``
+    """
+    rehash_hasher = new_hasher(
+        data=Validator(
+            additional_data=None,
+            password=b'',
+            salt=hasher.salt,
+            secret_key=None,
+        ),
+        backend=hasher.backend,
+        hash_len=hasher.hash_len,
+        iterations=hasher.iterations,
+        lanes=hasher.lanes,
+        memory_size=hasher.memory_size,
+        threads=hasher.threads,
+        variant=hasher.variant,
+        version=hasher.version,
+    )
+    lib.argonautica_verifier_configure_rehash_hasher(verifier, rehash_hasher)
+
+
+def new_verifier(
+    *,
+    additional_data:    Union[bytes, str, None],
+    backend:            Backend,
+    secret_key:         Union[bytes, str, None],
+    threads:            int
+) -> ffi.CData:
+    """
+    Creates an ``argonautica_verifier_t*`` holding the provided configuration and data, which
+    is freed automatically once it is garbage collected
+    """
+    if isinstance(additional_data, str):
+        additional_data = additional_data.encode('utf-8')
+    elif not (additional_data is None or isinstance(additional_data, bytes)):
+        raise TypeError("Type of additional_data must be bytes, str, or None")
+    if isinstance(secret_key, str):
+        secret_key = secret_key.encode('utf-8')
+    elif not (secret_key is None or isinstance(secret_key, bytes)):
+        raise TypeError("Type of secret_key must be bytes, str, or None")
+
+    verifier = ffi.gc(lib.argonautica_verifier_new(), lib.argonautica_verifier_free)
+    lib.argonautica_verifier_configure_backend(verifier, backend.value)
+    lib.argonautica_verifier_configure_threads(verifier, threads)
+    if additional_data is not None:
+        lib.argonautica_verifier_with_additional_data(
+            verifier, additional_data, len(additional_data))
+    if secret_key is not None:
+        lib.argonautica_verifier_with_secret_key(verifier, secret_key, len(secret_key))
+    return verifier
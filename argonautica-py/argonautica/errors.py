@@ -0,0 +1,109 @@
+from typing import Dict, Type
+
+from argonautica.core.ffi import error_message, lib
+
+
+class ArgonauticaError(Exception):
+    """
+    ``ArgonauticaError`` is the base class of every exception raised because argonautica
+    returned an error. It carries:
+
+    * ``code``: the ``argonautica_error_t`` returned by argonautica (e.g.
+      ``lib.ARGONAUTICA_ERROR_HASH_DECODE``)
+    * ``detail``: a detailed error message, which includes context such as which length was
+      too long or which part of a hash failed to decode
+
+    Errors that do not fall into one of the more specific subclasses below (e.g. bugs)
+    are raised as a plain ``ArgonauticaError``
+    """
+
+    def __init__(self, code: int, detail: str) -> None:
+        super().__init__(detail)
+        self.code = code
+        self.detail = detail
+
+
+class DecodeError(ArgonauticaError, ValueError):
+    """
+    Raised when a hash (or some other input) could not be decoded, e.g. when verifying
+    against or parsing an invalid string-encoded hash
+    """
+
+
+class ParameterError(ArgonauticaError, ValueError):
+    """
+    Raised when a parameter or an input fails validation, e.g. too few iterations, an
+    invalid memory size or a salt that is too short. This usually indicates a
+    misconfiguration
+    """
+
+
+class MismatchError(ArgonauticaError):
+    """
+    Raised when verifying a bound hash with a secret key or additional data other than the
+    ones it was created with
+    """
+
+
+class MissingInputError(ArgonauticaError):
+    """
+    Raised when a required input (e.g. a password, a hash, or the secret key or additional
+    data a hash is bound to) was not provided
+    """
+
+
+class ResourceError(ArgonauticaError):
+    """
+    Raised when a system resource could not be obtained, e.g. when allocating memory,
+    spawning threads or accessing the OS random number generator fails
+    """
+
+
+_ERRORS = {
+    lib.ARGONAUTICA_ERROR_BASE64_DECODE: DecodeError,
+    lib.ARGONAUTICA_ERROR_HASH_DECODE: DecodeError,
+    lib.ARGONAUTICA_ERROR_UTF8_ENCODE: DecodeError,
+
+    lib.ARGONAUTICA_ERROR_ADDITIONAL_DATA_TOO_LONG: ParameterError,
+    lib.ARGONAUTICA_ERROR_BACKEND_UNSUPPORTED: ParameterError,
+    lib.ARGONAUTICA_ERROR_HASH_LEN_TOO_SHORT: ParameterError,
+    lib.ARGONAUTICA_ERROR_INNER_DIGEST_INVALID: ParameterError,
+    lib.ARGONAUTICA_ERROR_ITERATIONS_TOO_FEW: ParameterError,
+    lib.ARGONAUTICA_ERROR_KEY_FILE_INVALID: ParameterError,
+    lib.ARGONAUTICA_ERROR_LANES_TOO_FEW: ParameterError,
+    lib.ARGONAUTICA_ERROR_LANES_TOO_MANY: ParameterError,
+    lib.ARGONAUTICA_ERROR_MEMORY_SIZE_INVALID: ParameterError,
+    lib.ARGONAUTICA_ERROR_MEMORY_SIZE_TOO_SMALL: ParameterError,
+    lib.ARGONAUTICA_ERROR_PASSWORD_NORMALIZATION: ParameterError,
+    lib.ARGONAUTICA_ERROR_PASSWORD_TOO_LONG: ParameterError,
+    lib.ARGONAUTICA_ERROR_PASSWORD_TOO_SHORT: ParameterError,
+    lib.ARGONAUTICA_ERROR_SALT_TOO_LONG: ParameterError,
+    lib.ARGONAUTICA_ERROR_SALT_TOO_SHORT: ParameterError,
+    lib.ARGONAUTICA_ERROR_SECRET_KEY_PERMISSIONS: ParameterError,
+    lib.ARGONAUTICA_ERROR_SECRET_KEY_TOO_LONG: ParameterError,
+    lib.ARGONAUTICA_ERROR_SECRET_KEY_TOO_SHORT: ParameterError,
+    lib.ARGONAUTICA_ERROR_THREADS_TOO_FEW: ParameterError,
+    lib.ARGONAUTICA_ERROR_THREADS_TOO_MANY: ParameterError,
+
+    lib.ARGONAUTICA_ERROR_ADDITIONAL_DATA_MISMATCH: MismatchError,
+    lib.ARGONAUTICA_ERROR_SECRET_KEY_MISMATCH: MismatchError,
+
+    lib.ARGONAUTICA_ERROR_ADDITIONAL_DATA_MISSING: MissingInputError,
+    lib.ARGONAUTICA_ERROR_HASH_MISSING: MissingInputError,
+    lib.ARGONAUTICA_ERROR_PASSWORD_MISSING: MissingInputError,
+    lib.ARGONAUTICA_ERROR_SECRET_KEY_MISSING: MissingInputError,
+
+    lib.ARGONAUTICA_ERROR_IO: ResourceError,
+    lib.ARGONAUTICA_ERROR_MEMORY_ALLOCATION: ResourceError,
+    lib.ARGONAUTICA_ERROR_OS_RNG: ResourceError,
+    lib.ARGONAUTICA_ERROR_THREAD: ResourceError,
+}  # type: Dict[int, Type[ArgonauticaError]]
+
+
+def error_from_code(err: int) -> ArgonauticaError:
+    """
+    Builds the exception corresponding to an ``argonautica_error_t``, carrying the detailed
+    error message of the most recent error on the calling thread
+    """
+    cls = _ERRORS.get(err, ArgonauticaError)
+    return cls(err, error_message(err))
@@ -1,21 +1,17 @@
-import base64
-import re
-from typing import Any, Dict, List
+from typing import List, Optional
 
 from argonautica.config import Variant, Version
-
-
-RE_HASH = re.compile(r"\$([^$]+)")
-RE_OTHER = re.compile(r"m=([0-9]+),t=([0-9]+),p=([0-9]+)")
-RE_VERSION = re.compile(r"v=([0-9]+)")
+from argonautica.core.ffi import ffi, lib
+from argonautica.errors import error_from_code
 
 
 class HashRaw:
     """
     A class that represents a string-encoded hash that has been decoded into its constituent parts.
 
-    You can obtain an instance of this class by calling the ``decode`` function on
-    a string-encoded hash.
+    You can obtain an instance of this class by calling ``HashRaw.parse`` (or the ``decode``
+    function) on a string-encoded hash or by calling the ``hash_raw`` method / function
+    instead of ``hash``.
     """
     __slots__ = [
         'iterations',
@@ -46,98 +42,177 @@ class HashRaw:
         self.variant = variant
         self.version = version
 
+    @classmethod
+    def parse(cls, hash: str) -> 'HashRaw':
+        """
+        Decodes a string-encoded hash into its component parts
+
+        Every string-encoded hash (the return value of all ``hash`` methods / functions) has
+        the following form:
+
+        * \${variant string}
+        * \$v={version number}
+        * \$m={memory cost},t={iterations},p={lanes}
+        * \${standard base64-encoded raw salt bytes (without padding)}
+        * \${standard base64-encoded raw hash bytes (without padding)}
+
+        Raises a ``DecodeError`` (which is a ``ValueError``) if the hash is invalid or has an
+        ``inner`` or ``data`` parameter (e.g. an onion hash or a bound hash), which this class
+        cannot represent
+        """
+        encoded = hash.encode('utf-8')
+        # Decoding never produces more bytes than there are characters in the encoded hash
+        hash_buf = ffi.new("uint8_t[]", len(encoded))
+        salt_buf = ffi.new("uint8_t[]", len(encoded))
+        out = ffi.new("argonautica_hash_raw_t*")
+        out.hash = hash_buf
+        out.hash_len = len(encoded)
+        out.salt = salt_buf
+        out.salt_len = len(encoded)
+        err = lib.argonautica_decode(out, encoded)
+        if err != lib.ARGONAUTICA_OK:
+            raise error_from_code(err)
+        return cls._from_c(out)
+
+    @classmethod
+    def _from_c(cls, out: ffi.CData) -> 'HashRaw':
+        return cls(
+            iterations=out.iterations,
+            lanes=out.lanes,
+            memory_size=out.memory_size,
+            raw_hash_bytes=bytes(ffi.buffer(out.hash, out.hash_len)),
+            raw_salt_bytes=bytes(ffi.buffer(out.salt, out.salt_len)),
+            variant=Variant(out.variant),
+            version=Version(out.version),
+        )
+
     def encode(self) -> str:
-        hash_encoded = base64.b64encode(self.raw_hash_bytes).decode('utf-8').rstrip('=')
-        salt_encoded = base64.b64encode(self.raw_salt_bytes).decode('utf-8').rstrip('=')
-        encoded = '${}$v={}$m={},t={},p={}${}${}'.format(
-            self.variant,
-            self.version,
-            self.memory_size,
-            self.iterations,
-            self.lanes,
-            salt_encoded,
-            hash_encoded
+        """
+        Encodes the ``HashRaw`` into a string-encoded hash
+        """
+        hash_buf = ffi.new("uint8_t[]", self.raw_hash_bytes)
+        salt_buf = ffi.new("uint8_t[]", self.raw_salt_bytes)
+        hash_raw = ffi.new("argonautica_hash_raw_t*")
+        hash_raw.hash = hash_buf
+        hash_raw.hash_len = len(self.raw_hash_bytes)
+        hash_raw.iterations = self.iterations
+        hash_raw.lanes = self.lanes
+        hash_raw.memory_size = self.memory_size
+        hash_raw.salt = salt_buf
+        hash_raw.salt_len = len(self.raw_salt_bytes)
+        hash_raw.variant = self.variant.value
+        hash_raw.version = self.version.value
+        encoded_len = lib.argonautica_encoded_len(
+            hash_raw.hash_len,
+            hash_raw.iterations,
+            hash_raw.lanes,
+            hash_raw.memory_size,
+            hash_raw.salt_len,
+            hash_raw.variant,
         )
-        return encoded
+        if encoded_len < 0:
+            raise Exception("Error calculating length of string-encoded hash")
+        encoded = ffi.new("char[]", encoded_len)
+        err = lib.argonautica_encode(encoded, hash_raw)
+        if err != lib.ARGONAUTICA_OK:
+            raise error_from_code(err)
+        return ffi.string(encoded).decode("utf-8")
+
+    def __eq__(self, other: object) -> bool:
+        if not isinstance(other, HashRaw):
+            return NotImplemented
+        return all(getattr(self, name) == getattr(other, name) for name in self.__slots__)
+
+    def __repr__(self) -> str:
+        return "HashRaw({})".format(", ".join(
+            "{}={!r}".format(name, getattr(self, name)) for name in self.__slots__
+        ))
+
+
+class Verification:
+    """
+    A class that represents the detailed outcome of verifying a password.
+
+    You can obtain an instance of this class by calling the ``verify_detailed`` method /
+    function instead of ``verify``. Besides whether or not the password matched
+    (``is_valid``), it reports the parameters decoded from the hash, whether the password
+    should be rehashed (``rehash_recommended``) and how long verifying took (``duration``, in
+    seconds). When verifying with a ``KeySet`` (see ``verify_with_key_set``), ``key_id`` is the
+    ID of the key that matched, or ``None`` if the password did not match. It is truthy if and
+    only if the password matched, so it can be used wherever the return value of ``verify`` is
+    """
+    __slots__ = [
+        'duration',
+        'hash_len',
+        'is_valid',
+        'iterations',
+        'key_id',
+        'lanes',
+        'memory_size',
+        'rehash_recommended',
+        'salt_len',
+        'variant',
+        'version'
+    ]
 
+    def __init__(
+        self,
+        *,
+        duration:           float,
+        hash_len:           int,
+        is_valid:           bool,
+        iterations:         int,
+        key_id:             Optional[str],
+        lanes:              int,
+        memory_size:        int,
+        rehash_recommended: bool,
+        salt_len:           int,
+        variant:            Variant,
+        version:            Version
+    ) -> None:
+        self.duration = duration
+        self.hash_len = hash_len
+        self.is_valid = is_valid
+        self.iterations = iterations
+        self.key_id = key_id
+        self.lanes = lanes
+        self.memory_size = memory_size
+        self.rehash_recommended = rehash_recommended
+        self.salt_len = salt_len
+        self.variant = variant
+        self.version = version
 
-def decode(hash: str) -> HashRaw:
-    """
-    The ``decode`` function takes a string-encoded hash and decodes it into its component parts
+    @classmethod
+    def _from_c(cls, out: ffi.CData) -> 'Verification':
+        return cls(
+            duration=out.duration_micros / 1000000,
+            hash_len=out.hash_len,
+            is_valid=out.is_valid == 1,
+            iterations=out.iterations,
+            key_id=None if out.key_id == ffi.NULL else ffi.string(out.key_id).decode('utf-8'),
+            lanes=out.lanes,
+            memory_size=out.memory_size,
+            rehash_recommended=out.rehash_recommended == 1,
+            salt_len=out.salt_len,
+            variant=Variant(out.variant),
+            version=Version(out.version),
+        )
+
+    def __bool__(self) -> bool:
+        return self.is_valid
 
-    Every string-encoded hash (the return value of all ``hash`` methods / functions) has
-    the following form:
+    def __repr__(self) -> str:
+        return "Verification({})".format(", ".join(
+            "{}={!r}".format(name, getattr(self, name)) for name in self.__slots__
+        ))
 
-    * \${variant string}
-    * \$v={version number}
-    * \$m={memory cost},t={iterations},p={lanes}
-    * \${standard base64-encoded raw salt bytes (without padding)}
-    * \${standard base64-encoded raw hash bytes (without padding)}
 
-    This function accepts a string-encoded hash and returns a ``HashRaw`` instance,
-    which give you access to these underlying components.
+def decode(hash: str) -> HashRaw:
+    """
+    The ``decode`` function takes a string-encoded hash and decodes it into its component
+    parts. It is equivalent to ``HashRaw.parse``
     """
-    l = RE_HASH.findall(hash)
-    if len(l) != 5:
-        raise ValueError("Invalid hash. Failed to decode hash. Hash: {}".format(hash))
-
-    # Variant
-    variant_str = l[0]
-    if variant_str == "argon2d":
-        variant = Variant.Argon2d
-    elif variant_str == "argon2i":
-        variant = Variant.Argon2i
-    elif variant_str == "argon2id":
-        variant = Variant.Argon2id
-    else:
-        raise ValueError("Invalid hash. Failed to decode hash. Hash: {}".format(hash))
-
-    # Version
-    l2 = RE_VERSION.findall(l[1])
-    if len(l2) != 1:
-        raise ValueError("Invalid hash. Failed to decode hash. Hash: {}".format(hash))
-    version_str = l2[0]
-    if version_str == "16":
-        version = Version._0x10
-    elif version_str == "19":
-        version = Version._0x13
-    else:
-        raise ValueError("Invalid hash. Failed to decode hash. Hash: {}".format(hash))
-
-    # Memory size, iterations, lanes
-    l3 = RE_OTHER.findall(l[2])
-    if len(l3) != 1:
-        raise ValueError("Invalid hash. Failed to decode hash. Hash: {}".format(hash))
-    if len(l3[0]) != 3:
-        raise ValueError("Invalid hash. Failed to decode hash. Hash: {}".format(hash))
-    iterations = int(l3[0][1])
-    lanes = int(l3[0][2])
-    memory_size = int(l3[0][0])
-
-    # Data
-    raw_salt_bytes = _base64_decode(l[3])
-    raw_hash_bytes = _base64_decode(l[4])
-
-    return HashRaw(
-        iterations=iterations,
-        lanes=lanes,
-        memory_size=memory_size,
-        raw_hash_bytes=raw_hash_bytes,
-        raw_salt_bytes=raw_salt_bytes,
-        variant=variant,
-        version=version,
-    )
-
-
-def _base64_decode(base64_encoded: str) -> bytes:
-    missing_padding = len(base64_encoded) % 4
-    if missing_padding != 0:
-        base64_encoded += '=' * (4 - missing_padding)
-    try:
-        decoded = base64.standard_b64decode(base64_encoded)
-    except:
-        raise ValueError("Invalid hash. Failed to decode hash. Hash: {}".format(hash))
-    return decoded
+    return HashRaw.parse(hash)
 
 
 class Void:
@@ -0,0 +1,77 @@
+import asyncio
+import unittest
+
+from argonautica import Argon2, Hasher, Verifier
+
+
+class TestAsync(unittest.TestCase):
+    def setUp(self):
+        self.loop = asyncio.new_event_loop()
+        self.password = "P@ssw0rd"
+
+    def tearDown(self):
+        self.loop.close()
+
+    def test_hash_verify_async(self):
+        argon2 = Argon2(secret_key="secret", iterations=8, memory_size=128)
+
+        async def run():
+            hash = await argon2.hash_async(password=self.password)
+            is_valid = await argon2.verify_async(hash=hash, password=self.password)
+            is_invalid = await argon2.verify_async(hash=hash, password="wrong")
+            return hash, is_valid, is_invalid
+
+        hash, is_valid, is_invalid = self.loop.run_until_complete(run())
+        self.assertTrue(is_valid)
+        self.assertFalse(is_invalid)
+        self.assertTrue(argon2.verify(hash=hash, password=self.password))
+
+    def test_event_loop_not_blocked(self):
+        hasher = Hasher(secret_key=None, iterations=64, memory_size=4096)
+        ticks = []
+
+        async def tick():
+            while True:
+                ticks.append(None)
+                await asyncio.sleep(0.001)
+
+        async def run():
+            ticker = self.loop.create_task(tick())
+            await hasher.hash_async(password=self.password)
+            ticker.cancel()
+
+        self.loop.run_until_complete(run())
+        self.assertGreater(len(ticks), 1)
+
+    def test_error_async(self):
+        hasher = Hasher(secret_key=None, iterations=0)
+        verifier = Verifier(secret_key=None)
+
+        with self.assertRaises(Exception) as context:
+            self.loop.run_until_complete(hasher.hash_async(password=self.password))
+        self.assertEqual(
+            str(context.exception),
+            "Iterations must be greater than 0: Iterations: 0",
+        )
+
+        with self.assertRaises(Exception) as context:
+            self.loop.run_until_complete(verifier.verify_async(
+                hash="$argon2id$v=19$m=4096,t=128",
+                password=self.password,
+            ))
+        self.assertTrue(str(context.exception).startswith("Hash decode error"))
+
+    def test_cancel_async(self):
+        hasher = Hasher(secret_key=None, iterations=8, memory_size=128)
+
+        async def run():
+            task = self.loop.create_task(hasher.hash_async(password=self.password))
+            await asyncio.sleep(0)
+            task.cancel()
+            with self.assertRaises(asyncio.CancelledError):
+                await task
+            # The loop keeps working (and further calls succeed) after a cancellation
+            return await hasher.hash_async(password=self.password)
+
+        hash = self.loop.run_until_complete(run())
+        self.assertTrue(hash.startswith("$argon2id$"))
@@ -0,0 +1,110 @@
+import unittest
+
+from argonautica import Argon2
+from argonautica.contrib.django import ArgonauticaPasswordHasher
+from argonautica.contrib.passlib import ArgonauticaHandler, DjangoArgonauticaHandler
+
+
+class FastPasswordHasher(ArgonauticaPasswordHasher):
+    iterations = 8
+    lanes = 2
+    memory_size = 64
+    threads = 2
+
+
+class TestDjango(unittest.TestCase):
+    def setUp(self):
+        self.hasher = FastPasswordHasher()
+        self.password = "P@ssw0rd"
+
+    def test_encode_verify(self):
+        salt = self.hasher.salt()
+        self.assertEqual(len(salt), FastPasswordHasher.salt_len)
+        encoded = self.hasher.encode(self.password, salt)
+        self.assertTrue(encoded.startswith("argon2$argon2id$v=19$m=64,t=8,p=2$"))
+        self.assertTrue(self.hasher.verify(self.password, encoded))
+        self.assertFalse(self.hasher.verify("wrong", encoded))
+        self.assertFalse(self.hasher.verify(self.password, "argon2$argon2id$v=19$m=64"))
+        with self.assertRaises(ValueError):
+            self.hasher.verify(self.password, "pbkdf2_sha256$100000$salt$hash")
+
+    def test_interoperability(self):
+        # A hash produced by Django can be verified by anyone after stripping the prefix...
+        encoded = self.hasher.encode(self.password, self.hasher.salt())
+        argon2 = Argon2(secret_key=None)
+        self.assertTrue(argon2.verify(hash=encoded[len("argon2"):], password=self.password))
+
+        # ... and vice versa
+        hash = argon2.hash(password=self.password)
+        self.assertTrue(self.hasher.verify(self.password, "argon2" + hash))
+
+    def test_decode_safe_summary(self):
+        encoded = self.hasher.encode(self.password, "somesaltsomesalt")
+        decoded = self.hasher.decode(encoded)
+        self.assertEqual(decoded["algorithm"], "argon2")
+        self.assertEqual(decoded["memory_cost"], 64)
+        self.assertEqual(decoded["parallelism"], 2)
+        self.assertEqual(decoded["time_cost"], 8)
+        self.assertEqual(decoded["variety"], "argon2id")
+        self.assertEqual(decoded["version"], 19)
+        self.assertEqual(decoded["salt"], "c29tZXNhbHRzb21lc2FsdA")
+
+        summary = self.hasher.safe_summary(encoded)
+        self.assertEqual(summary["salt"], "c29tZX" + "*" * 16)
+
+    def test_must_update(self):
+        encoded = self.hasher.encode(self.password, self.hasher.salt())
+        self.assertFalse(self.hasher.must_update(encoded))
+
+        class StrongerPasswordHasher(FastPasswordHasher):
+            iterations = 16
+
+        self.assertTrue(StrongerPasswordHasher().must_update(encoded))
+        self.assertTrue(self.hasher.must_update("argon2$argon2id$v=19$m=64"))
+
+
+class TestPasslib(unittest.TestCase):
+    def setUp(self):
+        self.handler = ArgonauticaHandler.using(
+            iterations=8,
+            lanes=2,
+            memory_size=64,
+            threads=2,
+        )
+        self.password = "P@ssw0rd"
+
+    def test_hash_verify(self):
+        hash = self.handler.hash(self.password)
+        self.assertTrue(hash.startswith("$argon2id$v=19$m=64,t=8,p=2$"))
+        self.assertTrue(self.handler.identify(hash))
+        self.assertTrue(self.handler.verify(self.password, hash))
+        self.assertFalse(self.handler.verify("wrong", hash))
+        self.assertFalse(self.handler.identify("$2b$12$abcdefghijklmnopqrstuv"))
+        with self.assertRaises(TypeError):
+            self.handler.using(rounds=10)
+
+    def test_secret_key(self):
+        handler = self.handler.using(secret_key="secret")
+        hash = handler.hash(self.password)
+        self.assertTrue(handler.verify(self.password, hash))
+        self.assertFalse(self.handler.verify(self.password, hash))
+
+    def test_needs_update(self):
+        hash = self.handler.hash(self.password)
+        self.assertFalse(self.handler.needs_update(hash))
+        self.assertTrue(self.handler.using(memory_size=128).needs_update(hash))
+
+    def test_django_format(self):
+        handler = DjangoArgonauticaHandler.using(
+            iterations=8,
+            lanes=2,
+            memory_size=64,
+            threads=2,
+        )
+        hash = handler.hash(self.password)
+        self.assertTrue(hash.startswith("argon2$argon2id$"))
+        self.assertTrue(handler.identify(hash))
+        self.assertFalse(handler.identify(hash[len("argon2"):]))
+        self.assertTrue(handler.verify(self.password, hash))
+        self.assertTrue(FastPasswordHasher().verify(self.password, hash))
+        self.assertFalse(handler.needs_update(hash))
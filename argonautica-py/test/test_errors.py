@@ -0,0 +1,82 @@
+import unittest
+
+from argonautica import (
+    ArgonauticaError, DecodeError, Hasher, HashRaw, MismatchError, MissingInputError,
+    ParameterError, Verifier
+)
+from argonautica.core.ffi import lib
+
+# Bound to the secret key "secret key, long enough for binding" and the additional data
+# "additional data"
+BOUND_HASH = (
+    "$argon2id$v=19$m=128,t=8,p=1,data=VEnvDp2i7jkHtGuzrvvV6Q"
+    "$7QRiF7Oekv3zaEd8Vmt7xNr+7qjiNBP4mac5aFzI3qA$mv3pDVoT8FlhaLgGQwnfQEF+kyYbYBv92hhLPmhnewk"
+)
+
+
+class TestErrors(unittest.TestCase):
+    def test_error_detail(self):
+        hasher = Hasher(secret_key=None, iterations=0)
+        with self.assertRaises(Exception) as context:
+            hasher.hash(password="P@ssw0rd")
+        self.assertEqual(
+            str(context.exception),
+            "Iterations must be greater than 0: Iterations: 0",
+        )
+
+        verifier = Verifier(secret_key=None)
+        with self.assertRaises(Exception) as context:
+            verifier.verify(password="P@ssw0rd", hash="$argon2id$v=19$m=4096,t=128")
+        self.assertTrue(str(context.exception).startswith("Hash decode error"))
+
+    def test_parameter_error(self):
+        hasher = Hasher(secret_key=None, iterations=0)
+        with self.assertRaises(ParameterError) as context:
+            hasher.hash(password="P@ssw0rd")
+        e = context.exception
+        self.assertIsInstance(e, ArgonauticaError)
+        self.assertIsInstance(e, ValueError)
+        self.assertEqual(e.code, lib.ARGONAUTICA_ERROR_ITERATIONS_TOO_FEW)
+        self.assertEqual(e.detail, "Iterations must be greater than 0: Iterations: 0")
+
+        hasher = Hasher(secret_key=None, iterations=8, memory_size=100)
+        with self.assertRaises(ParameterError) as context:
+            hasher.hash(password="P@ssw0rd")
+        self.assertEqual(context.exception.code, lib.ARGONAUTICA_ERROR_MEMORY_SIZE_INVALID)
+
+    def test_decode_error(self):
+        verifier = Verifier(secret_key=None)
+        with self.assertRaises(DecodeError) as context:
+            verifier.verify(password="P@ssw0rd", hash="$argon2id$v=19$m=4096,t=128")
+        self.assertEqual(context.exception.code, lib.ARGONAUTICA_ERROR_HASH_DECODE)
+
+        with self.assertRaises(DecodeError):
+            HashRaw.parse("not a hash")
+        with self.assertRaises(ValueError):
+            HashRaw.parse("not a hash")
+
+        for param in ["inner=md5", "data=c29tZWRhdGE"]:
+            hash = "$argon2id$v=19$m=64,t=8,p=2,{}$c29tZXNhbHQ$".format(param) + \
+                "rYR0S4u1lTvIvtrt9IDtzCAplumWAwyYrbwb7hNnbl0"
+            with self.assertRaises(DecodeError) as context:
+                HashRaw.parse(hash)
+            self.assertIn("parameter is not supported", context.exception.detail)
+
+    def test_binding_errors(self):
+        verifier = Verifier(
+            secret_key="secret key, long enough for binding",
+            additional_data="additional data",
+        )
+        self.assertTrue(verifier.verify(password="P@ssw0rd", hash=BOUND_HASH))
+
+        with self.assertRaises(MismatchError) as context:
+            verifier.verify(password="P@ssw0rd", hash=BOUND_HASH, additional_data="wrong")
+        self.assertEqual(context.exception.code, lib.ARGONAUTICA_ERROR_ADDITIONAL_DATA_MISMATCH)
+
+        with self.assertRaises(MissingInputError) as context:
+            verifier.verify(password="P@ssw0rd", hash=BOUND_HASH, additional_data=None)
+        self.assertEqual(context.exception.code, lib.ARGONAUTICA_ERROR_ADDITIONAL_DATA_MISSING)
+
+        with self.assertRaises(MissingInputError) as context:
+            verifier.verify(password="P@ssw0rd", hash=BOUND_HASH, secret_key=None)
+        self.assertEqual(context.exception.code, lib.ARGONAUTICA_ERROR_SECRET_KEY_MISSING)
@@ -0,0 +1,80 @@
+import unittest
+
+from argonautica import Hasher, HashRaw, Verifier, hash_raw, needs_rehash
+from argonautica.config import Variant, Version
+
+
+class TestHashRaw(unittest.TestCase):
+    def setUp(self):
+        self.password = "P@ssw0rd"
+        self.hasher = Hasher(
+            secret_key=None,
+            iterations=8,
+            lanes=2,
+            memory_size=64,
+            threads=2,
+        )
+
+    def test_hash_raw(self):
+        hash_raw = self.hasher.hash_raw(password=self.password)
+        self.assertEqual(hash_raw.iterations, 8)
+        self.assertEqual(hash_raw.lanes, 2)
+        self.assertEqual(hash_raw.memory_size, 64)
+        self.assertEqual(len(hash_raw.raw_hash_bytes), 32)
+        self.assertEqual(len(hash_raw.raw_salt_bytes), 32)
+        self.assertEqual(hash_raw.variant, Variant.Argon2id)
+        self.assertEqual(hash_raw.version, Version._0x13)
+
+        encoded = hash_raw.encode()
+        self.assertEqual(HashRaw.parse(encoded), hash_raw)
+        verifier = Verifier(secret_key=None)
+        self.assertTrue(verifier.verify(password=self.password, hash=encoded))
+
+    def test_hash_raw_deterministic_salt(self):
+        hash_raw1 = self.hasher.hash_raw(password=self.password, salt="somesalt")
+        hash_raw2 = hash_raw(
+            password=self.password,
+            secret_key=None,
+            iterations=8,
+            lanes=2,
+            memory_size=64,
+            salt=b"somesalt",
+            threads=2,
+        )
+        self.assertEqual(hash_raw1.raw_salt_bytes, b"somesalt")
+        self.assertEqual(hash_raw1, hash_raw2)
+
+    def test_parse(self):
+        encoded = "$argon2id$v=19$m=4096,t=128,p=2$c29tZXNhbHQ$iWh06vD8Fy27wf9npn6FXWiCX4K6pW6Ue1Bnzz07Z8A"
+        hash_raw = HashRaw.parse(encoded)
+        self.assertEqual(hash_raw.iterations, 128)
+        self.assertEqual(hash_raw.lanes, 2)
+        self.assertEqual(hash_raw.memory_size, 4096)
+        self.assertEqual(hash_raw.raw_salt_bytes, b"somesalt")
+        self.assertEqual(hash_raw.variant, Variant.Argon2id)
+        self.assertEqual(hash_raw.version, Version._0x13)
+        self.assertEqual(hash_raw.encode(), encoded)
+
+        with self.assertRaises(ValueError):
+            HashRaw.parse("$argon2id$v=19$m=4096,t=128")
+
+    def test_needs_rehash(self):
+        encoded = self.hasher.hash(password=self.password)
+        self.assertFalse(self.hasher.needs_rehash(hash=encoded))
+        self.assertFalse(needs_rehash(
+            hash=encoded,
+            iterations=8,
+            lanes=2,
+            memory_size=64,
+        ))
+        self.assertTrue(needs_rehash(
+            hash=encoded,
+            iterations=16,
+            lanes=2,
+            memory_size=64,
+        ))
+        self.hasher.variant = Variant.Argon2i
+        self.assertTrue(self.hasher.needs_rehash(hash=encoded))
+
+        with self.assertRaises(ValueError):
+            self.hasher.needs_rehash(hash="$argon2id$v=19$m=64")
@@ -0,0 +1,101 @@
+import unittest
+
+import os
+import tempfile
+
+from argonautica import (
+    Hasher, KeySet, MissingInputError, ParameterError, ResourceError, Verification, Verifier,
+    verify_detailed, verify_with_key_set
+)
+from argonautica.config import Variant, Version
+
+
+class TestVerification(unittest.TestCase):
+    def setUp(self):
+        self.password = "P@ssw0rd"
+        self.hasher = Hasher(
+            secret_key="secret",
+            iterations=8,
+            lanes=2,
+            memory_size=64,
+            threads=2,
+        )
+        self.hash = self.hasher.hash(password=self.password)
+
+    def test_verify_detailed(self):
+        verifier = Verifier(secret_key="secret")
+        verification = verifier.verify_detailed(hash=self.hash, password=self.password)
+        self.assertIsInstance(verification, Verification)
+        self.assertTrue(verification)
+        self.assertTrue(verification.is_valid)
+        self.assertFalse(verification.rehash_recommended)
+        self.assertEqual(verification.hash_len, 32)
+        self.assertEqual(verification.iterations, 8)
+        self.assertEqual(verification.lanes, 2)
+        self.assertEqual(verification.memory_size, 64)
+        self.assertEqual(verification.salt_len, 32)
+        self.assertEqual(verification.variant, Variant.Argon2id)
+        self.assertEqual(verification.version, Version._0x13)
+        self.assertGreater(verification.duration, 0)
+        self.assertIsNone(verification.key_id)
+
+        verification = verifier.verify_detailed(hash=self.hash, password="wrong")
+        self.assertFalse(verification)
+        self.assertFalse(verification.rehash_recommended)
+
+    def test_verify_detailed_rehash(self):
+        self.assertFalse(verify_detailed(
+            hash=self.hash,
+            hasher=self.hasher,
+            password=self.password,
+            secret_key="secret",
+        ).rehash_recommended)
+
+        self.hasher.iterations = 9
+        self.assertTrue(verify_detailed(
+            hash=self.hash,
+            hasher=self.hasher,
+            password=self.password,
+            secret_key="secret",
+        ).rehash_recommended)
+
+    def test_verify_with_key_set(self):
+        old = "old secret key, long enough"
+        new = "new secret key, long enough"
+        hash = Hasher(secret_key=old, iterations=8, memory_size=64).hash(password=self.password)
+
+        key_set = KeySet()
+        with self.assertRaises(MissingInputError):
+            verify_with_key_set(hash=hash, key_set=key_set, password=self.password)
+
+        key_set.insert(id="old", secret_key=old, created=1)
+        verification = verify_with_key_set(hash=hash, key_set=key_set, password=self.password)
+        self.assertTrue(verification)
+        self.assertEqual(verification.key_id, "old")
+        self.assertFalse(verification.rehash_recommended)
+
+        key_set.insert(id="new", secret_key=new.encode('utf-8'), created=2)
+        verification = Verifier(secret_key=None).verify_with_key_set(
+            hash=hash, key_set=key_set, password=self.password)
+        self.assertTrue(verification)
+        self.assertEqual(verification.key_id, "old")
+        self.assertTrue(verification.rehash_recommended)
+
+        verification = verify_with_key_set(hash=hash, key_set=key_set, password="wrong")
+        self.assertFalse(verification)
+        self.assertIsNone(verification.key_id)
+
+        with self.assertRaises(ParameterError):
+            key_set.insert(id="old", secret_key=old, created=3)
+        with self.assertRaises(ParameterError):
+            key_set.insert(id="short", secret_key="short", created=3)
+
+    def test_key_set_load_dir(self):
+        with tempfile.TemporaryDirectory() as dir:
+            self.assertIsInstance(KeySet.load_dir(dir), KeySet)
+            with self.assertRaises(ResourceError):
+                KeySet.load_dir(os.path.join(dir, "missing"))
+
+
+if __name__ == '__main__':
+    unittest.main()
@@ -1,10 +1,61 @@
+//! Command-line tool for secret keys. Without arguments, prints a random, base64-encoded,
+//! 32-byte secret key. Subcommands:
+//!
+//! * `generate <id> <path> [len]`: writes a new key file with a random key of `len` bytes
+//!   (default `32`)
+//! * `show <path>...`: prints the key ID and creation time of each key file, marking the
+//!   current (i.e. newest) key with `*`
+//! * `convert <base64|hex> <key>`: re-encodes a base64-encoded key as hex or vice versa
 extern crate argonautica;
 extern crate failure;
 
+use argonautica::input::SecretKey;
+use argonautica::keys::{KeyFile, KeySet};
 use argonautica::utils;
 
+const USAGE: &str = "usage: generate_secret_key [generate <id> <path> [len] | show <path>... | convert <base64|hex> <key>]";
+
 fn main() -> Result<(), failure::Error> {
-    let base64_encoded_secret_key = utils::generate_random_base64_encoded_string(32)?;
-    println!("{}", &base64_encoded_secret_key);
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    let args = args.iter().map(|arg| arg.as_str()).collect::<Vec<&str>>();
+    match &args[..] {
+        [] => {
+            let base64_encoded_secret_key = utils::generate_random_base64_encoded_string(32)?;
+            println!("{}", &base64_encoded_secret_key);
+        }
+        ["generate", id, path] => generate(id, path, "32")?,
+        ["generate", id, path, len] => generate(id, path, len)?,
+        ["show", paths @ ..] if !paths.is_empty() => {
+            let key_set = KeySet::load(paths)?;
+            let current = key_set.current().map(|key| key.id());
+            for key in key_set.iter() {
+                let marker = if Some(key.id()) == current { "*" } else { " " };
+                println!(
+                    "{} {} created {} ({} bytes)",
+                    marker,
+                    key.id(),
+                    key.created(),
+                    key.secret_key().len()
+                );
+            }
+        }
+        ["convert", "base64", key] => {
+            println!("{}", SecretKey::from_base64_encoded(key)?.to_hex());
+        }
+        ["convert", "hex", key] => {
+            println!("{}", SecretKey::from_hex(key)?.to_base64_encoded());
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    }
+    Ok(())
+}
+
+fn generate(id: &str, path: &str, len: &str) -> Result<(), failure::Error> {
+    let len = len.parse::<u32>()?;
+    KeyFile::generate(id, len)?.write(path)?;
+    println!("{}", path);
     Ok(())
 }
@@ -52,6 +52,8 @@ impl<'a> Hasher<'a> {
         let err = unsafe { ffi::argon2_ctx(context_ptr, variant) };
         check_error(err)?;
         Ok(HashRaw {
+            data: None,
+            inner: None,
             iterations: self.config.iterations(),
             lanes: self.config.lanes(),
             memory_size: self.config.memory_size(),
@@ -1,6 +1,6 @@
 use base64;
 
-use config::{Variant, Version};
+use config::{Inner, Variant, Version};
 use output::HashRaw;
 use {Error, ErrorKind};
 
@@ -12,6 +12,8 @@ pub(crate) fn decode_rust(hash: &str) -> Result<HashRaw, Error> {
         Error::new(ErrorKind::HashDecodeError).add_context(format!("Hash: {}", &hash))
     })?;
     let hash_raw = HashRaw {
+        data: intermediate.data,
+        inner: intermediate.inner,
         iterations: intermediate.iterations,
         lanes: intermediate.lanes,
         memory_size: intermediate.memory_size,
@@ -24,6 +26,8 @@ pub(crate) fn decode_rust(hash: &str) -> Result<HashRaw, Error> {
 }
 
 struct IntermediateStruct {
+    data: Option<Vec<u8>>,
+    inner: Option<Inner>,
     variant: Variant,
     version: Version,
     memory_size: u32,
@@ -32,6 +36,41 @@ struct IntermediateStruct {
     raw_salt_bytes: Vec<u8>,
 }
 
+struct Params {
+    data: Option<Vec<u8>>,
+    inner: Option<Inner>,
+    lanes: u32,
+}
+
+// Parses the remainder of the parameters after `p=`, i.e. the lanes optionally followed by
+// an `inner` parameter and / or a `data` parameter, in that order (e.g. `2`, `2,inner=md5`
+// or `2,inner=md5,data=...`)
+fn parse_params(x: &str) -> Result<Params, Error> {
+    let mut parts = x.split(',');
+    let lanes = parts
+        .next()
+        .unwrap_or("")
+        .parse::<u32>()
+        .map_err(|_| Error::new(ErrorKind::HashDecodeError))?;
+    let mut params = Params {
+        data: None,
+        inner: None,
+        lanes,
+    };
+    for part in parts {
+        if part.starts_with("inner=") && params.inner.is_none() && params.data.is_none() {
+            params.inner = Some(part["inner=".len()..].parse::<Inner>()?);
+        } else if part.starts_with("data=") && params.data.is_none() {
+            let data = base64::decode_config(&part["data=".len()..], base64::STANDARD_NO_PAD)
+                .map_err(|_| Error::new(ErrorKind::HashDecodeError))?;
+            params.data = Some(data);
+        } else {
+            return Err(Error::new(ErrorKind::HashDecodeError));
+        }
+    }
+    Ok(params)
+}
+
 #[cfg_attr(rustfmt, rustfmt_skip)]
 named!(parse_hash<&str, IntermediateStruct>, do_parse!(
     take_until!("$") >>
@@ -48,7 +87,7 @@ named!(parse_hash<&str, IntermediateStruct>, do_parse!(
     iterations: map_res!(take_until!(","), |x: &str| x.parse::<u32>()) >>
     take_until!(",p=") >>
     take!(3) >>
-    lanes: map_res!(take_until!("$"), |x: &str| x.parse::<u32>()) >>
+    params: map_res!(take_until!("$"), parse_params) >>
     take_until!("$") >>
     take!(1) >>
     raw_salt_bytes: map_res!(take_until!("$"), |x: &str| {
@@ -57,8 +96,10 @@ named!(parse_hash<&str, IntermediateStruct>, do_parse!(
     take_until!("$") >>
     take!(1) >>
     (IntermediateStruct {
+        data: params.data,
+        inner: params.inner,
         iterations,
-        lanes,
+        lanes: params.lanes,
         memory_size,
         raw_salt_bytes,
         variant,
@@ -102,6 +143,25 @@ mod tests {
         assert_eq!(hash_raw.lanes(), 1);
     }
 
+    #[test]
+    fn test_decode_data() {
+        let hash = "$argon2id$v=19$m=4096,t=128,p=2,inner=md5,data=AAECAwQFBgcICQoLDA0ODw$gt4I/z7gnC8Ao0ofCFvz+2LGxI3it1TnCnlxn0PWKko$v6V587B9qbKraulhK/6vFUq93BGWugdzgRhtyap9tDM";
+        let hash_raw = decode_rust(hash).unwrap();
+        assert_eq!(hash_raw.lanes(), 2);
+        assert!(hash_raw.inner().is_some());
+        assert_eq!(hash_raw.data().unwrap(), &(0u8..16).collect::<Vec<_>>()[..]);
+        assert_eq!(hash_raw.encode_rust(), hash);
+
+        for hash in &[
+            "$argon2id$v=19$m=4096,t=128,p=2,data=AAECAwQFBgcICQoLDA0ODw,inner=md5$gt4I/z7gnC8Ao0ofCFvz+2LGxI3it1TnCnlxn0PWKko$v6V587B9qbKraulhK/6vFUq93BGWugdzgRhtyap9tDM",
+            "$argon2id$v=19$m=4096,t=128,p=2,data=!!$gt4I/z7gnC8Ao0ofCFvz+2LGxI3it1TnCnlxn0PWKko$v6V587B9qbKraulhK/6vFUq93BGWugdzgRhtyap9tDM",
+            "$argon2id$v=19$m=4096,t=128,p=2,keyid=AAEC$gt4I/z7gnC8Ao0ofCFvz+2LGxI3it1TnCnlxn0PWKko$v6V587B9qbKraulhK/6vFUq93BGWugdzgRhtyap9tDM",
+        ] {
+            let err = decode_rust(hash).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::HashDecodeError);
+        }
+    }
+
     #[test]
     #[ignore] // TODO: Turn back on once implemented decode_c
     fn test_decode_against_c() {
@@ -6,13 +6,26 @@ impl HashRaw {
     pub(crate) fn encode_rust(&self) -> String {
         let hash_encoded = base64::encode_config(self.raw_hash_bytes(), base64::STANDARD_NO_PAD);
         let salt_encoded = base64::encode_config(self.raw_salt_bytes(), base64::STANDARD_NO_PAD);
+        let inner_encoded = match self.inner() {
+            Some(inner) => format!(",inner={}", inner),
+            None => String::new(),
+        };
+        let data_encoded = match self.data() {
+            Some(data) => format!(
+                ",data={}",
+                base64::encode_config(data, base64::STANDARD_NO_PAD)
+            ),
+            None => String::new(),
+        };
         format!(
-            "${}$v={}$m={},t={},p={}${}${}",
+            "${}$v={}$m={},t={},p={}{}{}${}${}",
             self.variant().as_str(),
             self.version().as_str(),
             self.memory_size(),
             self.iterations(),
             self.lanes(),
+            inner_encoded,
+            data_encoded,
             salt_encoded,
             hash_encoded,
         )
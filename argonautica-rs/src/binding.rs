@@ -0,0 +1,210 @@
+//! Fingerprints binding a hash to the secret key and additional data it was created with
+//! (requires the `binding` feature).
+//!
+//! The `data` parameter of a bound hash (e.g. `$argon2id$v=19$m=4096,t=192,p=4,data=...$`)
+//! holds two 8-byte fingerprints, base64-encoded:
+//! * the key fingerprint, a BLAKE2b digest of the salt and the ID of the secret key (e.g.
+//!   [`KeyFile::id`](../keys/struct.KeyFile.html#method.id)), which identifies the key the hash
+//!   was created with without depending on the key material, and
+//! * the additional data fingerprint, a BLAKE2b digest of the salt and the additional data,
+//!   keyed with a key derived from the secret key.
+//!
+//! Both differ from hash to hash, even when the key and additional data are the same. Checking
+//! them is cheap compared to Argon2, which lets [`Verifier`](../struct.Verifier.html) report a
+//! wrong or missing secret key or additional data as such instead of as a wrong password.
+//!
+//! Binding is refused without a secret key, and the key fingerprint only ever reveals the key
+//! ID, which is not secret. The additional data fingerprint, however, still depends on the
+//! secret key: someone who has your database and knows (or can guess) the additional data of a
+//! hash can test guesses of the secret key against it offline at the speed of BLAKE2b, without
+//! going through Argon2. Binding is therefore also refused with a secret key shorter than 32
+//! bytes, and the key should be random, such as the ones made by
+//! [`KeyFile::generate`](../keys/struct.KeyFile.html#method.generate), never chosen by a person.
+use blake2_rfc::blake2b::{blake2b, Blake2b};
+
+use input::{AdditionalData, SecretKey};
+use {Error, ErrorKind};
+
+const FINGERPRINT_LEN: usize = 8;
+
+/// Minimum length in bytes of the secret key a hash is bound to
+pub(crate) const SECRET_KEY_MIN_LEN: usize = 32;
+const KEY_DERIVATION_KEY: &[u8] = b"argonautica-binding";
+
+/// Computes the `data` parameter for a hash with the provided salt
+pub(crate) fn bind(
+    salt: &[u8],
+    key_id: &str,
+    secret_key: &SecretKey,
+    additional_data: Option<&AdditionalData>,
+) -> Vec<u8> {
+    let mut data = key_fingerprint(salt, key_id);
+    data.extend_from_slice(&additional_data_fingerprint(
+        &derive_key(secret_key),
+        salt,
+        additional_data,
+    ));
+    data
+}
+
+/// Checks the `data` parameter of a hash against the key ID, secret key and additional data
+/// provided for verifying it, returning an error of kind `SecretKeyMissingError`,
+/// `SecretKeyMismatchError`, `AdditionalDataMissingError` or `AdditionalDataMismatchError` if
+/// they are not the ones the hash was created with. Without a key ID, a wrong secret key
+/// cannot be told apart from wrong additional data
+pub(crate) fn check(
+    data: &[u8],
+    salt: &[u8],
+    key_id: Option<&str>,
+    secret_key: Option<&SecretKey>,
+    additional_data: Option<&AdditionalData>,
+) -> Result<(), Error> {
+    if data.len() != 2 * FINGERPRINT_LEN {
+        return Err(Error::new(ErrorKind::HashDecodeError)
+            .add_context("The data parameter is not a pair of binding fingerprints"));
+    }
+    if let Some(key_id) = key_id {
+        if !matches_key_id(data, salt, key_id) {
+            return Err(Error::new(ErrorKind::SecretKeyMismatchError));
+        }
+    }
+    let secret_key = match secret_key {
+        Some(secret_key) => secret_key,
+        None => {
+            return Err(Error::new(ErrorKind::SecretKeyMissingError)
+                .add_context("The hash is bound to a secret key"))
+        }
+    };
+    let expected = additional_data_fingerprint(&derive_key(secret_key), salt, additional_data);
+    if !constant_time_eq(&data[FINGERPRINT_LEN..], &expected) {
+        let err = match additional_data {
+            Some(_) => Error::new(ErrorKind::AdditionalDataMismatchError),
+            None => Error::new(ErrorKind::AdditionalDataMissingError),
+        };
+        return match key_id {
+            Some(_) => Err(err),
+            None => Err(err.add_context(
+                "Or the secret key is not the one the hash was created with (provide the key ID \
+                 to tell these apart)",
+            )),
+        };
+    }
+    Ok(())
+}
+
+/// Returns `true` if the key fingerprint in the `data` parameter of a hash is that of the
+/// provided key ID, e.g. to pick the key a hash was created with out of a key set
+pub(crate) fn matches_key_id(data: &[u8], salt: &[u8], key_id: &str) -> bool {
+    data.len() == 2 * FINGERPRINT_LEN
+        && constant_time_eq(&data[..FINGERPRINT_LEN], &key_fingerprint(salt, key_id))
+}
+
+fn derive_key(secret_key: &SecretKey) -> Vec<u8> {
+    blake2b(32, KEY_DERIVATION_KEY, secret_key.as_bytes())
+        .as_bytes()
+        .to_vec()
+}
+
+fn key_fingerprint(salt: &[u8], key_id: &str) -> Vec<u8> {
+    let mut state = Blake2b::with_key(FINGERPRINT_LEN, KEY_DERIVATION_KEY);
+    state.update(b"key id");
+    state.update(salt);
+    state.update(key_id.as_bytes());
+    state.finalize().as_bytes().to_vec()
+}
+
+fn additional_data_fingerprint(
+    key: &[u8],
+    salt: &[u8],
+    additional_data: Option<&AdditionalData>,
+) -> Vec<u8> {
+    let mut state = Blake2b::with_key(FINGERPRINT_LEN, key);
+    state.update(b"additional data");
+    state.update(salt);
+    match additional_data {
+        Some(additional_data) => {
+            state.update(&[1]);
+            state.update(additional_data.as_bytes());
+        }
+        None => state.update(&[0]),
+    }
+    state.finalize().as_bytes().to_vec()
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let secret_key = SecretKey::from("secret key");
+        let additional_data = AdditionalData::from("additional data");
+        let data = bind(b"somesalt", "key", &secret_key, Some(&additional_data));
+        assert_eq!(data.len(), 16);
+        assert_ne!(
+            data,
+            bind(b"othersalt", "key", &secret_key, Some(&additional_data))
+        );
+        for &key_id in &[Some("key"), None] {
+            check(
+                &data,
+                b"somesalt",
+                key_id,
+                Some(&secret_key),
+                Some(&additional_data),
+            )
+            .unwrap();
+        }
+        assert!(matches_key_id(&data, b"somesalt", "key"));
+        assert!(!matches_key_id(&data, b"somesalt", "other key"));
+        // The key fingerprint does not depend on the key material
+        let other_key = SecretKey::from("other secret key");
+        assert_eq!(
+            data[..8],
+            bind(b"somesalt", "key", &other_key, Some(&additional_data))[..8]
+        );
+
+        let wrong_additional_data = AdditionalData::from("wrong additional data");
+        for &(key_id, secret_key, additional_data, kind) in &[
+            (
+                Some("other key"),
+                Some(&other_key),
+                Some(&additional_data),
+                ErrorKind::SecretKeyMismatchError,
+            ),
+            (
+                Some("key"),
+                None,
+                Some(&additional_data),
+                ErrorKind::SecretKeyMissingError,
+            ),
+            (
+                Some("key"),
+                Some(&secret_key),
+                Some(&wrong_additional_data),
+                ErrorKind::AdditionalDataMismatchError,
+            ),
+            (
+                Some("key"),
+                Some(&secret_key),
+                None,
+                ErrorKind::AdditionalDataMissingError,
+            ),
+            (
+                None,
+                Some(&other_key),
+                Some(&additional_data),
+                ErrorKind::AdditionalDataMismatchError,
+            ),
+        ] {
+            let err = check(&data, b"somesalt", key_id, secret_key, additional_data).unwrap_err();
+            assert_eq!(err.kind(), kind);
+        }
+        let err = check(&data[1..], b"somesalt", None, Some(&secret_key), None).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::HashDecodeError);
+    }
+}
@@ -0,0 +1,257 @@
+//! Checking passwords against a local dataset of breached passwords (requires the `breach`
+//! feature).
+//!
+//! If you configure a [`Hasher`](../struct.Hasher.html) with a
+//! [`BreachLookup`](trait.BreachLookup.html) (see
+//! [`configure_breach_lookup`](../struct.Hasher.html#method.configure_breach_lookup)), every
+//! password is looked up before it is hashed, and hashing a password that appears in the
+//! dataset returns an [`Error`](../struct.Error.html) of kind
+//! [`PasswordBreachedError`](../enum.ErrorKind.html#variant.PasswordBreachedError), which
+//! carries the number of times the password was seen in breaches.
+//!
+//! Two lookups for the [Have I Been Pwned](https://haveibeenpwned.com/Passwords) dataset are
+//! provided, neither of which requires network access:
+//! * [`HibpFile`](struct.HibpFile.html), for the single file of SHA-1 hashes ordered by hash
+//!   (lines of the form `<SHA-1>:<count>`), and
+//! * [`HibpDirectory`](struct.HibpDirectory.html), for a directory of range files as served by
+//!   the k-anonymity API (one file per 5-character hash prefix, named e.g. `21BD1.txt`,
+//!   containing lines of the form `<remaining 35 characters>:<count>`).
+//!
+//! Both files must be sorted by hash, as they are when downloaded; they are binary searched on
+//! disk and never loaded into memory. If your team keeps breached passwords elsewhere, implement
+//! [`BreachLookup`](trait.BreachLookup.html) yourself.
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use sha1::{Digest, Sha1};
+
+use {Error, ErrorKind};
+
+/// Trait for backends that know how often a password has appeared in breaches
+pub trait BreachLookup: fmt::Debug + Send + Sync {
+    /// Returns the number of times the password with the provided SHA-1 digest appears in the
+    /// dataset, or `0` if it does not appear at all
+    fn lookup(&self, sha1: &[u8; 20]) -> Result<u64, Error>;
+
+    /// Computes the SHA-1 digest of the password and looks it up
+    fn lookup_password(&self, password: &[u8]) -> Result<u64, Error> {
+        let mut sha1 = [0u8; 20];
+        sha1.copy_from_slice(&Sha1::digest(password));
+        self.lookup(&sha1)
+    }
+}
+
+/// [`BreachLookup`](trait.BreachLookup.html) backed by the single Have I Been Pwned file of
+/// SHA-1 hashes ordered by hash
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct HibpFile {
+    path: PathBuf,
+}
+
+impl HibpFile {
+    /// Creates a new [`HibpFile`](struct.HibpFile.html), returning an error if the file cannot
+    /// be opened
+    pub fn open<P>(path: P) -> Result<HibpFile, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().to_path_buf();
+        open(&path)?;
+        Ok(HibpFile { path })
+    }
+    /// Read-only access to the path of the file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl BreachLookup for HibpFile {
+    fn lookup(&self, sha1: &[u8; 20]) -> Result<u64, Error> {
+        search(&self.path, hex(sha1).as_bytes())
+    }
+}
+
+/// [`BreachLookup`](trait.BreachLookup.html) backed by a directory of Have I Been Pwned range
+/// files, one per 5-character SHA-1 prefix. Since every prefix has a range file in the complete
+/// dataset, a missing range file is reported as an error rather than as "not breached"
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct HibpDirectory {
+    path: PathBuf,
+}
+
+impl HibpDirectory {
+    /// Creates a new [`HibpDirectory`](struct.HibpDirectory.html), returning an error if the
+    /// path is not a directory
+    pub fn open<P>(path: P) -> Result<HibpDirectory, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().to_path_buf();
+        if !path.is_dir() {
+            return Err(Error::new(ErrorKind::IoError)
+                .add_context(format!("{}: Not a directory", path.display())));
+        }
+        Ok(HibpDirectory { path })
+    }
+    /// Read-only access to the path of the directory
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl BreachLookup for HibpDirectory {
+    fn lookup(&self, sha1: &[u8; 20]) -> Result<u64, Error> {
+        let hex = hex(sha1);
+        let (prefix, suffix) = hex.split_at(5);
+        let path = self.path.join(format!("{}.txt", prefix));
+        search(&path, suffix.as_bytes())
+    }
+}
+
+/// Returns an error of kind [`PasswordBreachedError`](../enum.ErrorKind.html#variant.PasswordBreachedError)
+/// if the password appears in the dataset
+pub(crate) fn check(lookup: &dyn BreachLookup, password: &[u8]) -> Result<(), Error> {
+    match lookup.lookup_password(password)? {
+        0 => Ok(()),
+        count => Err(Error::new(ErrorKind::PasswordBreachedError(count))),
+    }
+}
+
+fn hex(sha1: &[u8; 20]) -> String {
+    sha1.iter().map(|byte| format!("{:02X}", byte)).collect()
+}
+
+fn open(path: &Path) -> Result<File, Error> {
+    File::open(path).map_err(|e| {
+        Error::new(ErrorKind::IoError).add_context(format!("{}: {}", path.display(), e))
+    })
+}
+
+/// Binary searches a file of lines of the form `<uppercase hex>:<count>`, sorted by hex, for
+/// the provided key, returning the count or `0` if the key is not found
+fn search(path: &Path, key: &[u8]) -> Result<u64, Error> {
+    let io_error = |e: ::std::io::Error| {
+        Error::new(ErrorKind::IoError).add_context(format!("{}: {}", path.display(), e))
+    };
+    let file = open(path)?;
+    let len = file.metadata().map_err(io_error)?.len();
+    let mut reader = BufReader::new(file);
+    let mut line = Vec::new();
+    // Invariant: `lo` is the start of a line, every line starting before `lo` is less than the
+    // key, and every line starting at or after `hi` is greater than the key
+    let (mut lo, mut hi) = (0u64, len);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        let start = if mid == lo {
+            lo
+        } else {
+            reader.seek(SeekFrom::Start(mid - 1)).map_err(io_error)?;
+            line.clear();
+            mid - 1 + reader.read_until(b'\n', &mut line).map_err(io_error)? as u64
+        };
+        if start >= hi {
+            hi = mid;
+            continue;
+        }
+        reader.seek(SeekFrom::Start(start)).map_err(io_error)?;
+        line.clear();
+        let line_len = reader.read_until(b'\n', &mut line).map_err(io_error)? as u64;
+        let (hash, count) = parse_line(&line).ok_or_else(|| {
+            Error::new(ErrorKind::IoError).add_context(format!(
+                "{}: Malformed line at byte {}",
+                path.display(),
+                start
+            ))
+        })?;
+        match hash.to_ascii_uppercase()[..].cmp(key) {
+            ::std::cmp::Ordering::Equal => return Ok(count),
+            ::std::cmp::Ordering::Less => lo = start + line_len,
+            ::std::cmp::Ordering::Greater => hi = start,
+        }
+    }
+    Ok(0)
+}
+
+fn parse_line(line: &[u8]) -> Option<(&[u8], u64)> {
+    let line = ::std::str::from_utf8(line).ok()?.trim_end();
+    let mut parts = line.splitn(2, ':');
+    let hash = parts.next()?;
+    let count = parts.next()?.parse::<u64>().ok()?;
+    Some((hash.as_bytes(), count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::Write;
+
+    // sha1("P@ssw0rd")
+    const SHA1: &str = "21BD12DC183F740EE76F27B78EB39C8AD972A757";
+
+    fn temp_path(name: &str) -> PathBuf {
+        ::std::env::temp_dir().join(format!("argonautica-{}-{}", name, ::std::process::id()))
+    }
+
+    #[test]
+    fn test_hibp_file() {
+        let path = temp_path("hibp-file.txt");
+        {
+            let mut file = File::create(&path).unwrap();
+            for i in 0..256u32 {
+                let hash = if i == 0x21 {
+                    SHA1.to_string()
+                } else {
+                    format!("{:02X}{}", i, &"0".repeat(38))
+                };
+                write!(file, "{}:{}\r\n", hash, i + 1).unwrap();
+            }
+        }
+        let lookup = HibpFile::open(&path).unwrap();
+        assert_eq!(lookup.lookup_password(b"P@ssw0rd").unwrap(), 34);
+        assert_eq!(lookup.lookup_password(b"not breached").unwrap(), 0);
+        let mut sha1 = [0u8; 20];
+        sha1[0] = 0xff;
+        assert_eq!(lookup.lookup(&sha1).unwrap(), 256);
+        assert_eq!(lookup.lookup(&[0u8; 20]).unwrap(), 1);
+        let err = check(&lookup, b"P@ssw0rd").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PasswordBreachedError(34));
+        fs::remove_file(&path).unwrap();
+
+        let err = HibpFile::open(&path).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::IoError);
+    }
+
+    #[test]
+    fn test_hibp_directory() {
+        let path = temp_path("hibp-directory");
+        fs::create_dir_all(&path).unwrap();
+        {
+            let mut file = File::create(path.join("21BD1.txt")).unwrap();
+            write!(file, "0000000000000000000000000000000000A:1\r\n").unwrap();
+            write!(file, "{}:3861493\r\n", &SHA1[5..]).unwrap();
+            write!(file, "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF:2").unwrap();
+        }
+        let lookup = HibpDirectory::open(&path).unwrap();
+        assert_eq!(lookup.lookup_password(b"P@ssw0rd").unwrap(), 3_861_493);
+        let err = lookup.lookup_password(b"not breached").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::IoError);
+        fs::remove_dir_all(&path).unwrap();
+    }
+
+    #[test]
+    fn test_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<HibpFile>();
+        assert_send::<HibpDirectory>();
+    }
+
+    #[test]
+    fn test_sync() {
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<HibpFile>();
+        assert_sync::<HibpDirectory>();
+    }
+}
@@ -51,6 +51,9 @@ pub const DEFAULT_SALT_LEN: u32 = 32;
 /// `false`
 pub const DEFAULT_SECRET_KEY_CLEARING: bool = false;
 
+/// `16_u32`
+pub const DEFAULT_SECRET_KEY_MIN_LEN: u32 = 16;
+
 /// [`Variant::Argon2id`](enum.Variant.html#variant.Argon2id)
 pub const DEFAULT_VARIANT: Variant = Variant::Argon2id;
 
@@ -1,7 +1,16 @@
+use std::sync::Arc;
+
 use futures_cpupool::CpuPool;
 
+#[cfg(feature = "breach")]
+use breach::BreachLookup;
 use config::defaults::*;
-use config::{Backend, Flags, Variant, Version};
+#[cfg(feature = "normalization")]
+use config::Normalization;
+use config::{Backend, Flags, InnerScheme, SharedRng, ValidationReport, Variant, Version};
+use metrics::Metrics;
+use output::HashRaw;
+use policy::PasswordPolicy;
 use {Error, ErrorKind};
 
 const PANIC_WARNING: &str = "Your program will error if you use this configuration";
@@ -13,7 +22,13 @@ const PANIC_WARNING: &str = "Your program will error if you use this configurati
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct HasherConfig {
-    backend: Backend,
+    pub(crate) backend: Backend,
+    #[cfg(feature = "binding")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) binding: bool,
+    #[cfg(feature = "breach")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing, skip_deserializing))]
+    pub(crate) breach_lookup: Option<Arc<dyn BreachLookup>>,
     #[cfg_attr(
         feature = "serde",
         serde(
@@ -22,17 +37,26 @@ pub struct HasherConfig {
             default = "default_cpu_pool_serde"
         )
     )]
-    cpu_pool: Option<CpuPool>,
-    hash_len: u32,
-    iterations: u32,
-    lanes: u32,
-    memory_size: u32,
-    opt_out_of_secret_key: bool,
-    password_clearing: bool,
-    secret_key_clearing: bool,
-    threads: u32,
-    variant: Variant,
-    version: Version,
+    pub(crate) cpu_pool: Option<CpuPool>,
+    pub(crate) hash_len: u32,
+    pub(crate) inner_scheme: Option<InnerScheme>,
+    pub(crate) iterations: u32,
+    pub(crate) lanes: u32,
+    pub(crate) memory_size: u32,
+    #[cfg_attr(feature = "serde", serde(skip_serializing, skip_deserializing))]
+    pub(crate) metrics: Option<Arc<dyn Metrics>>,
+    #[cfg(feature = "normalization")]
+    pub(crate) normalization: Option<Normalization>,
+    pub(crate) opt_out_of_secret_key: bool,
+    pub(crate) password_clearing: bool,
+    #[cfg_attr(feature = "serde", serde(skip_serializing, skip_deserializing))]
+    pub(crate) password_policy: Option<PasswordPolicy>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing, skip_deserializing))]
+    pub(crate) rng: Option<SharedRng>,
+    pub(crate) secret_key_clearing: bool,
+    pub(crate) threads: u32,
+    pub(crate) variant: Variant,
+    pub(crate) version: Version,
 }
 
 impl HasherConfig {
@@ -40,6 +64,16 @@ impl HasherConfig {
     pub fn backend(&self) -> Backend {
         self.backend
     }
+    #[cfg(feature = "binding")]
+    #[allow(missing_docs)]
+    pub fn binding(&self) -> bool {
+        self.binding
+    }
+    #[cfg(feature = "breach")]
+    #[allow(missing_docs)]
+    pub fn breach_lookup(&self) -> Option<&dyn BreachLookup> {
+        self.breach_lookup.as_deref()
+    }
     #[allow(missing_docs)]
     pub fn cpu_pool(&self) -> Option<CpuPool> {
         match self.cpu_pool {
@@ -52,6 +86,10 @@ impl HasherConfig {
         self.hash_len
     }
     #[allow(missing_docs)]
+    pub fn inner_scheme(&self) -> Option<InnerScheme> {
+        self.inner_scheme
+    }
+    #[allow(missing_docs)]
     pub fn iterations(&self) -> u32 {
         self.iterations
     }
@@ -64,6 +102,15 @@ impl HasherConfig {
         self.memory_size
     }
     #[allow(missing_docs)]
+    pub fn metrics(&self) -> Option<&dyn Metrics> {
+        self.metrics.as_deref()
+    }
+    #[cfg(feature = "normalization")]
+    #[allow(missing_docs)]
+    pub fn normalization(&self) -> Option<Normalization> {
+        self.normalization
+    }
+    #[allow(missing_docs)]
     pub fn opt_out_of_secret_key(&self) -> bool {
         self.opt_out_of_secret_key
     }
@@ -72,6 +119,14 @@ impl HasherConfig {
         self.password_clearing
     }
     #[allow(missing_docs)]
+    pub fn password_policy(&self) -> Option<&PasswordPolicy> {
+        self.password_policy.as_ref()
+    }
+    #[allow(missing_docs)]
+    pub fn rng(&self) -> Option<&SharedRng> {
+        self.rng.as_ref()
+    }
+    #[allow(missing_docs)]
     pub fn secret_key_clearing(&self) -> bool {
         self.secret_key_clearing
     }
@@ -93,13 +148,23 @@ impl HasherConfig {
     pub(crate) fn default() -> HasherConfig {
         HasherConfig {
             backend: Backend::default(),
+            #[cfg(feature = "binding")]
+            binding: false,
+            #[cfg(feature = "breach")]
+            breach_lookup: None,
             cpu_pool: None,
             hash_len: DEFAULT_HASH_LEN,
+            inner_scheme: None,
             iterations: DEFAULT_ITERATIONS,
             lanes: default_lanes(),
             memory_size: DEFAULT_MEMORY_SIZE,
+            metrics: None,
+            #[cfg(feature = "normalization")]
+            normalization: None,
             opt_out_of_secret_key: DEFAULT_OPT_OUT_OF_SECRET_KEY,
             password_clearing: DEFAULT_PASSWORD_CLEARING,
+            password_policy: None,
+            rng: None,
             secret_key_clearing: DEFAULT_SECRET_KEY_CLEARING,
             threads: default_threads(),
             variant: Variant::default(),
@@ -123,6 +188,14 @@ impl HasherConfig {
         });
         self.backend = backend;
     }
+    #[cfg(feature = "binding")]
+    pub(crate) fn set_binding(&mut self, boolean: bool) {
+        self.binding = boolean;
+    }
+    #[cfg(feature = "breach")]
+    pub(crate) fn set_breach_lookup(&mut self, breach_lookup: Option<Arc<dyn BreachLookup>>) {
+        self.breach_lookup = breach_lookup;
+    }
     pub(crate) fn set_cpu_pool(&mut self, cpu_pool: CpuPool) {
         self.cpu_pool = Some(cpu_pool);
     }
@@ -132,6 +205,9 @@ impl HasherConfig {
         });
         self.hash_len = hash_len;
     }
+    pub(crate) fn set_inner_scheme(&mut self, inner_scheme: Option<InnerScheme>) {
+        self.inner_scheme = inner_scheme;
+    }
     pub(crate) fn set_iterations(&mut self, iterations: u32) {
         validate_iterations(iterations).unwrap_or_else(|e| {
             warn!("{}. {}.", e, PANIC_WARNING);
@@ -150,12 +226,25 @@ impl HasherConfig {
         });
         self.memory_size = memory_size;
     }
+    pub(crate) fn set_metrics(&mut self, metrics: Option<Arc<dyn Metrics>>) {
+        self.metrics = metrics;
+    }
+    #[cfg(feature = "normalization")]
+    pub(crate) fn set_normalization(&mut self, normalization: Option<Normalization>) {
+        self.normalization = normalization;
+    }
     pub(crate) fn set_opt_out_of_secret_key(&mut self, boolean: bool) {
         self.opt_out_of_secret_key = boolean;
     }
     pub(crate) fn set_password_clearing(&mut self, boolean: bool) {
         self.password_clearing = boolean;
     }
+    pub(crate) fn set_password_policy(&mut self, password_policy: Option<PasswordPolicy>) {
+        self.password_policy = password_policy;
+    }
+    pub(crate) fn set_rng(&mut self, rng: Option<SharedRng>) {
+        self.rng = rng;
+    }
     pub(crate) fn set_secret_key_clearing(&mut self, boolean: bool) {
         self.secret_key_clearing = boolean;
     }
@@ -178,6 +267,35 @@ impl HasherConfig {
         }
         self.version = version;
     }
+    /// Returns `true` if the hash was produced with parameters (other than the salt length,
+    /// which is not part of the configuration) that differ from this configuration
+    pub(crate) fn needs_rehash(&self, hash_raw: &HashRaw) -> bool {
+        #[cfg(feature = "binding")]
+        {
+            if hash_raw.data().is_some() != self.binding {
+                return true;
+            }
+        }
+        hash_raw.raw_hash_bytes().len() != self.hash_len as usize
+            || hash_raw.iterations() != self.iterations
+            || hash_raw.lanes() != self.lanes
+            || hash_raw.memory_size() != self.memory_size
+            || hash_raw.variant() != self.variant
+            || hash_raw.version() != self.version
+            || hash_raw.inner().map(|inner| inner.scheme()) != self.inner_scheme
+    }
+    /// Adds every invalid option to the report, rather than stopping at the first one
+    pub(crate) fn report(&self, report: &mut ValidationReport) {
+        report.check("backend", validate_backend(self.backend));
+        report.check("hash_len", validate_hash_len(self.hash_len));
+        report.check("iterations", validate_iterations(self.iterations));
+        report.check("lanes", validate_lanes(self.lanes));
+        report.check(
+            "memory_size",
+            validate_memory_size(self.lanes, self.memory_size),
+        );
+        report.check("threads", validate_threads(self.threads));
+    }
     pub(crate) fn validate(&self) -> Result<(), Error> {
         validate_backend(self.backend)?;
         validate_hash_len(self.hash_len)?;
@@ -189,7 +307,7 @@ impl HasherConfig {
     }
 }
 
-fn validate_backend(backend: Backend) -> Result<(), Error> {
+pub(crate) fn validate_backend(backend: Backend) -> Result<(), Error> {
     match backend {
         Backend::C => (),
         Backend::Rust => return Err(Error::new(ErrorKind::BackendUnsupportedError)),
@@ -197,7 +315,7 @@ fn validate_backend(backend: Backend) -> Result<(), Error> {
     Ok(())
 }
 
-fn validate_hash_len(hash_len: u32) -> Result<(), Error> {
+pub(crate) fn validate_hash_len(hash_len: u32) -> Result<(), Error> {
     if hash_len < 4 {
         return Err(Error::new(ErrorKind::HashLenTooShortError)
             .add_context(format!("Hash len: {}", hash_len)));
@@ -205,7 +323,7 @@ fn validate_hash_len(hash_len: u32) -> Result<(), Error> {
     Ok(())
 }
 
-fn validate_iterations(iterations: u32) -> Result<(), Error> {
+pub(crate) fn validate_iterations(iterations: u32) -> Result<(), Error> {
     if iterations == 0 {
         return Err(Error::new(ErrorKind::IterationsTooFewError)
             .add_context(format!("Iterations: {}", iterations)));
@@ -213,7 +331,7 @@ fn validate_iterations(iterations: u32) -> Result<(), Error> {
     Ok(())
 }
 
-fn validate_lanes(lanes: u32) -> Result<(), Error> {
+pub(crate) fn validate_lanes(lanes: u32) -> Result<(), Error> {
     if lanes == 0 {
         return Err(
             Error::new(ErrorKind::LanesTooFewError).add_context(format!("Lanes: {}", lanes))
@@ -227,7 +345,7 @@ fn validate_lanes(lanes: u32) -> Result<(), Error> {
     Ok(())
 }
 
-fn validate_memory_size(lanes: u32, memory_size: u32) -> Result<(), Error> {
+pub(crate) fn validate_memory_size(lanes: u32, memory_size: u32) -> Result<(), Error> {
     if memory_size < 8 * lanes {
         return Err(Error::new(ErrorKind::MemorySizeTooSmallError)
             .add_context(format!("Lanes: {}. Memory size: {}", lanes, memory_size)));
@@ -239,7 +357,22 @@ fn validate_memory_size(lanes: u32, memory_size: u32) -> Result<(), Error> {
     Ok(())
 }
 
-fn validate_threads(threads: u32) -> Result<(), Error> {
+#[cfg(feature = "settings")]
+pub(crate) fn validate_salt_len(salt_len: u32) -> Result<(), Error> {
+    if salt_len < 8 {
+        return Err(
+            Error::new(ErrorKind::SaltTooShortError).add_context(format!("Length: {}", salt_len))
+        );
+    }
+    if salt_len == ::std::u32::MAX {
+        return Err(
+            Error::new(ErrorKind::SaltTooLongError).add_context(format!("Length: {}", salt_len))
+        );
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_threads(threads: u32) -> Result<(), Error> {
     if threads == 0 {
         return Err(
             Error::new(ErrorKind::ThreadsTooFewError).add_context(format!("Threads: {}", threads))
@@ -0,0 +1,347 @@
+use std::fmt;
+use std::str::FromStr;
+
+use {Error, ErrorKind};
+
+const BCRYPT_ALPHABET: &[u8] = b"./ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+impl fmt::Display for InnerScheme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for InnerScheme {
+    type Err = Error;
+
+    /// Performs the following mapping:
+    /// * `"md5"` => `Ok(InnerScheme::Md5)`<br/>
+    /// * `"sha1"` => `Ok(InnerScheme::Sha1)`<br/>
+    /// * `"bcrypt"` => `Ok(InnerScheme::Bcrypt)`<br/>
+    /// * anything else => an error
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "md5" => Ok(InnerScheme::Md5),
+            "sha1" => Ok(InnerScheme::Sha1),
+            "bcrypt" => Ok(InnerScheme::Bcrypt),
+            _ => Err(Error::new(ErrorKind::HashDecodeError)
+                .add_context(format!("Unknown inner scheme: {}", s))),
+        }
+    }
+}
+
+/// Enum representing the legacy schemes whose digests can be wrapped inside an Argon2 hash
+/// ("onion hashing").
+///
+/// If you configure a [`Hasher`](../struct.Hasher.html) with an inner scheme (see
+/// [`configure_inner_scheme`](../struct.Hasher.html#method.configure_inner_scheme)), the
+/// "password" you provide it must be an existing legacy digest of that scheme (as stored in
+/// your database) instead of a plaintext password. The resulting hash records the inner scheme
+/// (e.g. `$argon2id$v=19$m=4096,t=192,p=2,inner=md5$...`), and a
+/// [`Verifier`](../struct.Verifier.html) presented with such a hash will first compute the
+/// legacy digest of the candidate password and then verify it against the Argon2 hash. This
+/// allows you to upgrade a whole table of weak hashes offline, without knowing any of the
+/// plaintext passwords. Verifying such hashes requires the `legacy` feature
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum InnerScheme {
+    /// Unsalted MD5, stored as a hex-encoded digest (32 characters)
+    Md5,
+
+    /// Unsalted SHA-1, stored as a hex-encoded digest (40 characters)
+    Sha1,
+
+    /// bcrypt, stored in the modular crypt format (e.g. `$2b$12$...`, 60 characters). `$2x$`
+    /// digests are not supported, as their buggy handling of non-ASCII passwords is not
+    /// recorded in the hash
+    Bcrypt,
+}
+
+impl InnerScheme {
+    /// Performs the following mapping:
+    /// * `InnerScheme::Md5` => `"md5"`<br/>
+    /// * `InnerScheme::Sha1` => `"sha1"`<br/>
+    /// * `InnerScheme::Bcrypt` => `"bcrypt"`
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            InnerScheme::Md5 => "md5",
+            InnerScheme::Sha1 => "sha1",
+            InnerScheme::Bcrypt => "bcrypt",
+        }
+    }
+}
+
+impl fmt::Display for Inner {
+    /// Formats the [`Inner`](enum.Inner.html) the way it is recorded in the `inner` parameter
+    /// of a string-encoded hash, i.e. `md5`, `sha1` or `bcrypt-<cost>-<salt>`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Inner::Md5 => write!(f, "md5"),
+            Inner::Sha1 => write!(f, "sha1"),
+            Inner::Bcrypt { cost, ref salt } => write!(f, "bcrypt-{:02}-{}", cost, salt),
+        }
+    }
+}
+
+impl FromStr for Inner {
+    type Err = Error;
+
+    /// Parses the value of the `inner` parameter of a string-encoded hash
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(3, '-');
+        let scheme = parts.next().unwrap_or("").parse::<InnerScheme>()?;
+        let inner = match (scheme, parts.next(), parts.next()) {
+            (InnerScheme::Md5, None, None) => Inner::Md5,
+            (InnerScheme::Sha1, None, None) => Inner::Sha1,
+            (InnerScheme::Bcrypt, Some(cost), Some(salt)) => Inner::Bcrypt {
+                cost: cost.parse::<u32>().map_err(|_| {
+                    Error::new(ErrorKind::HashDecodeError)
+                        .add_context(format!("Invalid inner parameter: {}", s))
+                })?,
+                salt: validate_bcrypt_salt(salt).ok_or_else(|| {
+                    Error::new(ErrorKind::HashDecodeError)
+                        .add_context(format!("Invalid inner parameter: {}", s))
+                })?,
+            },
+            _ => {
+                return Err(Error::new(ErrorKind::HashDecodeError)
+                    .add_context(format!("Invalid inner parameter: {}", s)))
+            }
+        };
+        Ok(inner)
+    }
+}
+
+/// Enum representing the inner legacy scheme recorded in an onion hash, together with the
+/// parameters needed to recompute the legacy digest from a candidate password (see
+/// [`InnerScheme`](enum.InnerScheme.html))
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum Inner {
+    /// Unsalted MD5
+    Md5,
+
+    /// Unsalted SHA-1
+    Sha1,
+
+    /// bcrypt with the given cost and (bcrypt-base64-encoded, 22 character) salt
+    Bcrypt {
+        /// The bcrypt cost
+        cost: u32,
+        /// The bcrypt salt, as it appears in the modular crypt format
+        salt: String,
+    },
+}
+
+impl Inner {
+    /// Returns the [`InnerScheme`](enum.InnerScheme.html) of this [`Inner`](enum.Inner.html)
+    pub fn scheme(&self) -> InnerScheme {
+        match *self {
+            Inner::Md5 => InnerScheme::Md5,
+            Inner::Sha1 => InnerScheme::Sha1,
+            Inner::Bcrypt { .. } => InnerScheme::Bcrypt,
+        }
+    }
+}
+
+impl Inner {
+    /// Validates a legacy digest of the given scheme, returning the [`Inner`](enum.Inner.html)
+    /// to record in the hash and the bytes to use as the Argon2 password: the lowercase hex
+    /// digest for MD5 and SHA-1, and the 31 character hash portion for bcrypt (its cost and
+    /// salt are recorded in the [`Inner`](enum.Inner.html) instead)
+    pub(crate) fn from_digest(
+        scheme: InnerScheme,
+        digest: &[u8],
+    ) -> Result<(Inner, Vec<u8>), Error> {
+        let invalid = || {
+            Error::new(ErrorKind::InnerDigestInvalidError)
+                .add_context(format!("Inner scheme: {}", scheme))
+        };
+        let digest = ::std::str::from_utf8(digest).map_err(|_| invalid())?;
+        match scheme {
+            InnerScheme::Md5 | InnerScheme::Sha1 => {
+                let expected_len = if scheme == InnerScheme::Md5 { 32 } else { 40 };
+                if digest.len() != expected_len || !digest.bytes().all(|b| b.is_ascii_hexdigit()) {
+                    return Err(invalid());
+                }
+                let inner = if scheme == InnerScheme::Md5 {
+                    Inner::Md5
+                } else {
+                    Inner::Sha1
+                };
+                Ok((inner, digest.to_ascii_lowercase().into_bytes()))
+            }
+            InnerScheme::Bcrypt => {
+                // $2b$12$<22 character salt><31 character hash>
+                let parts = digest.split('$').collect::<Vec<&str>>();
+                if parts.len() != 4
+                    || !parts[0].is_empty()
+                    || !["2a", "2b", "2y"].contains(&parts[1])
+                    || parts[3].len() != 53
+                    || !parts[3].is_ascii()
+                {
+                    return Err(invalid());
+                }
+                let cost = parts[2].parse::<u32>().map_err(|_| invalid())?;
+                let salt = validate_bcrypt_salt(&parts[3][..22]).ok_or_else(invalid)?;
+                let hash = &parts[3][22..];
+                if !hash.bytes().all(|b| BCRYPT_ALPHABET.contains(&b)) {
+                    return Err(invalid());
+                }
+                Ok((Inner::Bcrypt { cost, salt }, hash.as_bytes().to_vec()))
+            }
+        }
+    }
+
+    /// Computes the legacy digest of a candidate password, in the same form as the Argon2
+    /// password produced by [`from_digest`](#method.from_digest)
+    #[cfg(feature = "legacy")]
+    pub(crate) fn digest(&self, password: &[u8]) -> Result<Vec<u8>, Error> {
+        use bcrypt;
+        use md5;
+        use sha1::{Digest, Sha1};
+
+        match *self {
+            Inner::Md5 => Ok(format!("{:x}", md5::compute(password)).into_bytes()),
+            Inner::Sha1 => Ok(format!("{:x}", Sha1::digest(password)).into_bytes()),
+            Inner::Bcrypt { cost, ref salt } => {
+                let salt = decode_bcrypt_salt(salt);
+                let parts = bcrypt::hash_with_salt(password, cost, salt).map_err(|e| {
+                    Error::new(ErrorKind::HashDecodeError).add_context(format!("bcrypt: {}", e))
+                })?;
+                let hash = parts.to_string();
+                Ok(hash.as_bytes()[hash.len() - 31..].to_vec())
+            }
+        }
+    }
+
+    /// Computing legacy digests requires the `legacy` feature
+    #[cfg(not(feature = "legacy"))]
+    pub(crate) fn digest(&self, _password: &[u8]) -> Result<Vec<u8>, Error> {
+        Err(Error::new(ErrorKind::HashDecodeError).add_context(format!(
+            "Verifying a hash with inner scheme {} requires the `legacy` feature",
+            self.scheme(),
+        )))
+    }
+}
+
+fn validate_bcrypt_salt(salt: &str) -> Option<String> {
+    // 22 characters encode 16 bytes; the last character only carries 2 significant bits
+    if salt.len() != 22 || !salt.bytes().all(|b| BCRYPT_ALPHABET.contains(&b)) {
+        return None;
+    }
+    Some(salt.to_string())
+}
+
+#[cfg(feature = "legacy")]
+fn decode_bcrypt_salt(salt: &str) -> [u8; 16] {
+    let mut bytes = [0u8; 16];
+    let mut acc = 0u32;
+    let mut bits = 0;
+    let mut i = 0;
+    for b in salt.bytes() {
+        let value = BCRYPT_ALPHABET.iter().position(|&c| c == b).unwrap() as u32;
+        acc = (acc << 6) | value;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            if i < bytes.len() {
+                bytes[i] = (acc >> bits) as u8;
+                i += 1;
+            }
+        }
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inner_round_trip() {
+        for s in &["md5", "sha1", "bcrypt-12-EGdrhbKUv8Oc9vGiXX0HQO"] {
+            let inner = s.parse::<Inner>().unwrap();
+            assert_eq!(&inner.to_string(), s);
+        }
+        for s in &[
+            "md4",
+            "md5-1",
+            "bcrypt",
+            "bcrypt-x-EGdrhbKUv8Oc9vGiXX0HQO",
+            "bcrypt-12-short",
+        ] {
+            assert_eq!(
+                s.parse::<Inner>().unwrap_err().kind(),
+                ErrorKind::HashDecodeError
+            );
+        }
+    }
+
+    #[test]
+    fn test_inner_from_digest() {
+        let (inner, password) =
+            Inner::from_digest(InnerScheme::Md5, b"161EBD7D45089B3446EE4E0D86DBCF92").unwrap();
+        assert_eq!(inner, Inner::Md5);
+        assert_eq!(password, b"161ebd7d45089b3446ee4e0d86dbcf92".to_vec());
+
+        let (inner, password) = Inner::from_digest(
+            InnerScheme::Bcrypt,
+            b"$2b$04$5LvzRHSQAsu3ifo92tiMsePX1uGi02L1JW7EXH4HQHt18Zh0I2Xg2",
+        )
+        .unwrap();
+        assert_eq!(
+            inner,
+            Inner::Bcrypt {
+                cost: 4,
+                salt: "5LvzRHSQAsu3ifo92tiMse".to_string(),
+            }
+        );
+        assert_eq!(password, b"PX1uGi02L1JW7EXH4HQHt18Zh0I2Xg2".to_vec());
+
+        for &(scheme, digest) in &[
+            (InnerScheme::Md5, &b"161ebd7d45089b3446ee4e0d86dbcf9"[..]),
+            (InnerScheme::Sha1, &b"161ebd7d45089b3446ee4e0d86dbcf92"[..]),
+            (InnerScheme::Bcrypt, &b"$1$somesalt$somehash"[..]),
+            (
+                InnerScheme::Bcrypt,
+                &b"$2x$04$5LvzRHSQAsu3ifo92tiMsePX1uGi02L1JW7EXH4HQHt18Zh0I2Xg2"[..],
+            ),
+        ] {
+            assert_eq!(
+                Inner::from_digest(scheme, digest).unwrap_err().kind(),
+                ErrorKind::InnerDigestInvalidError
+            );
+        }
+
+        // 53 bytes, but with a multi-byte character straddling the end of the salt
+        let digest = format!("$2b$12${}é{}", "a".repeat(21), "a".repeat(30));
+        assert_eq!(
+            Inner::from_digest(InnerScheme::Bcrypt, digest.as_bytes())
+                .unwrap_err()
+                .kind(),
+            ErrorKind::InnerDigestInvalidError
+        );
+    }
+
+    #[cfg(feature = "legacy")]
+    #[test]
+    fn test_inner_digest() {
+        assert_eq!(
+            Inner::Md5.digest(b"P@ssw0rd").unwrap(),
+            b"161ebd7d45089b3446ee4e0d86dbcf92".to_vec()
+        );
+        assert_eq!(
+            Inner::Sha1.digest(b"P@ssw0rd").unwrap(),
+            b"21bd12dc183f740ee76f27b78eb39c8ad972a757".to_vec()
+        );
+        let (inner, password) = Inner::from_digest(
+            InnerScheme::Bcrypt,
+            b"$2b$04$5LvzRHSQAsu3ifo92tiMsePX1uGi02L1JW7EXH4HQHt18Zh0I2Xg2",
+        )
+        .unwrap();
+        assert_eq!(inner.digest(b"P@ssw0rd").unwrap(), password);
+    }
+}
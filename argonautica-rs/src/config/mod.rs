@@ -4,6 +4,13 @@ mod backend;
 pub(crate) mod defaults;
 mod flags;
 mod hasher_config;
+mod inner;
+#[cfg(feature = "normalization")]
+mod normalization;
+mod rng;
+#[cfg(feature = "settings")]
+mod settings;
+mod validation_report;
 mod variant;
 mod verifier_config;
 mod version;
@@ -12,6 +19,14 @@ pub use self::backend::Backend;
 pub use self::defaults::*;
 pub(crate) use self::flags::Flags;
 pub use self::hasher_config::HasherConfig;
+pub(crate) use self::hasher_config::{validate_backend, validate_threads};
+pub use self::inner::{Inner, InnerScheme};
+#[cfg(feature = "normalization")]
+pub use self::normalization::Normalization;
+pub use self::rng::SharedRng;
+#[cfg(feature = "settings")]
+pub use self::settings::{Settings, ENV_PREFIX};
+pub use self::validation_report::{ValidationIssue, ValidationReport};
 pub use self::variant::Variant;
 pub use self::verifier_config::VerifierConfig;
 pub use self::version::Version;
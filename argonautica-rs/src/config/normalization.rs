@@ -0,0 +1,157 @@
+use std::fmt;
+
+use unicode_normalization::UnicodeNormalization;
+
+use {Error, ErrorKind};
+
+impl fmt::Display for Normalization {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Enum representing the Unicode normalizations that can be applied to a
+/// [`Password`](../input/struct.Password.html) before it is hashed or verified (requires the
+/// `normalization` feature).
+///
+/// The same passphrase can arrive as different sequences of bytes depending on where it was
+/// typed (e.g. `"é"` as the single code point `U+00E9` on one machine and as `"e"` followed by
+/// the combining acute accent `U+0301` on another). Because Argon2 hashes raw bytes, such a
+/// password would fail to verify against a hash created from the other form. Configuring
+/// [`Hasher`](../struct.Hasher.html) and [`Verifier`](../struct.Verifier.html) with the same
+/// normalization (see
+/// [`configure_normalization`](../struct.Hasher.html#method.configure_normalization)) avoids
+/// this. The password must be valid utf-8; otherwise hashing or verifying will return an
+/// [`Error`](../struct.Error.html) of kind
+/// [`Utf8EncodeError`](../enum.ErrorKind.html#variant.Utf8EncodeError)
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum Normalization {
+    /// Unicode Normalization Form C (canonical composition)
+    Nfc,
+
+    /// Unicode Normalization Form KC (compatibility composition). Unlike NFC, this also folds
+    /// compatibility characters, e.g. the ligature `"ﬁ"` becomes `"fi"`
+    Nfkc,
+
+    /// The OpaqueString profile of [RFC 8265](https://tools.ietf.org/html/rfc8265#section-4.2)
+    /// (the successor of SASLprep), i.e. non-ASCII spaces are mapped to `U+0020`, the result
+    /// is normalized with NFC and passwords containing control characters are rejected with
+    /// an [`Error`](../struct.Error.html) of kind
+    /// [`PasswordNormalizationError`](../enum.ErrorKind.html#variant.PasswordNormalizationError)
+    OpaqueString,
+}
+
+impl Normalization {
+    /// Performs the following mapping:
+    /// * `Normalization::Nfc` => `"nfc"`<br/>
+    /// * `Normalization::Nfkc` => `"nfkc"`<br/>
+    /// * `Normalization::OpaqueString` => `"opaque-string"`
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Normalization::Nfc => "nfc",
+            Normalization::Nfkc => "nfkc",
+            Normalization::OpaqueString => "opaque-string",
+        }
+    }
+}
+
+impl Normalization {
+    /// Returns the normalized form of the provided password bytes
+    pub(crate) fn normalize(&self, bytes: &[u8]) -> Result<Vec<u8>, Error> {
+        let s = ::std::str::from_utf8(bytes).map_err(|_| Error::new(ErrorKind::Utf8EncodeError))?;
+        let normalized = match *self {
+            Normalization::Nfc => s.nfc().collect::<String>(),
+            Normalization::Nfkc => s.nfkc().collect::<String>(),
+            Normalization::OpaqueString => {
+                if s.chars().any(|c| c.is_control()) {
+                    return Err(Error::new(ErrorKind::PasswordNormalizationError)
+                        .add_context("Control characters are not allowed by OpaqueString"));
+                }
+                s.chars()
+                    .map(|c| if is_non_ascii_space(c) { ' ' } else { c })
+                    .nfc()
+                    .collect::<String>()
+            }
+        };
+        Ok(normalized.into_bytes())
+    }
+}
+
+/// Returns `true` for the non-ASCII characters of Unicode general category Zs ("space separator")
+// `matches!` would require Rust 1.42.0
+#[allow(clippy::match_like_matches_macro)]
+fn is_non_ascii_space(c: char) -> bool {
+    match c {
+        '\u{00A0}'
+        | '\u{1680}'
+        | '\u{2000}'..='\u{200A}'
+        | '\u{202F}'
+        | '\u{205F}'
+        | '\u{3000}' => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMPOSED: &str = "p\u{00E9}ssw\u{00F6}rd";
+    const DECOMPOSED: &str = "pe\u{0301}sswo\u{0308}rd";
+
+    #[test]
+    fn test_normalize_composed_and_decomposed() {
+        assert_ne!(COMPOSED.as_bytes(), DECOMPOSED.as_bytes());
+        for normalization in &[
+            Normalization::Nfc,
+            Normalization::Nfkc,
+            Normalization::OpaqueString,
+        ] {
+            let composed = normalization.normalize(COMPOSED.as_bytes()).unwrap();
+            let decomposed = normalization.normalize(DECOMPOSED.as_bytes()).unwrap();
+            assert_eq!(composed, decomposed);
+            assert_eq!(&composed[..], COMPOSED.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_normalize_compatibility() {
+        let ligature = "\u{FB01}re";
+        let nfc = Normalization::Nfc.normalize(ligature.as_bytes()).unwrap();
+        assert_eq!(&nfc[..], ligature.as_bytes());
+        let nfkc = Normalization::Nfkc.normalize(ligature.as_bytes()).unwrap();
+        assert_eq!(&nfkc[..], b"fire");
+    }
+
+    #[test]
+    fn test_normalize_opaque_string() {
+        let normalized = Normalization::OpaqueString
+            .normalize("correct\u{00A0}horse\u{3000}battery".as_bytes())
+            .unwrap();
+        assert_eq!(&normalized[..], b"correct horse battery");
+        let err = Normalization::OpaqueString
+            .normalize(b"pass\x07word")
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PasswordNormalizationError);
+    }
+
+    #[test]
+    fn test_normalize_invalid_utf8() {
+        let err = Normalization::Nfc.normalize(&[0xff, 0xfe]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Utf8EncodeError);
+    }
+
+    #[test]
+    fn test_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<Normalization>();
+    }
+
+    #[test]
+    fn test_sync() {
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<Normalization>();
+    }
+}
@@ -0,0 +1,82 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use rand::{CryptoRng, Error as RandError, RngCore};
+
+impl fmt::Debug for SharedRng {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SharedRng {{ .. }}")
+    }
+}
+
+/// Struct representing a user-supplied, cryptographically-secure random number generator with
+/// which a [`Hasher`](../struct.Hasher.html) fills its random [`Salt`](../input/struct.Salt.html)
+/// (see [`configure_rng`](../struct.Hasher.html#method.configure_rng)) instead of `OsRng`.
+///
+/// Any generator implementing the [`rand`](https://docs.rs/rand/0.7) 0.7 `CryptoRng` and
+/// `RngCore` traits can be used, e.g. a seeded `StdRng` for reproducible salts in tests or an
+/// alternative entropy source where the operating system's one is unavailable. The generator
+/// is shared (behind a mutex) between clones of a `SharedRng`, and therefore also between a
+/// [`Hasher`](../struct.Hasher.html) and the copies of it that the non-blocking methods move
+/// to the cpu pool, so that they draw from a single stream of random bytes
+#[derive(Clone)]
+pub struct SharedRng(Arc<Mutex<dyn RngCore + Send>>);
+
+impl SharedRng {
+    /// Creates a new [`SharedRng`](struct.SharedRng.html) wrapping the provided generator
+    pub fn new<R>(rng: R) -> SharedRng
+    where
+        R: CryptoRng + RngCore + Send + 'static,
+    {
+        SharedRng(Arc::new(Mutex::new(rng)))
+    }
+}
+
+impl CryptoRng for SharedRng {}
+
+impl RngCore for SharedRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.lock().unwrap().next_u32()
+    }
+    fn next_u64(&mut self) -> u64 {
+        self.0.lock().unwrap().next_u64()
+    }
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.lock().unwrap().fill_bytes(dest)
+    }
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), RandError> {
+        match self.0.lock() {
+            Ok(mut rng) => rng.try_fill_bytes(dest),
+            Err(_) => Err(RandError::new("Random number generator mutex poisoned")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_shared_rng_is_shared_between_clones() {
+        let mut expected = StdRng::seed_from_u64(1);
+        let mut rng = SharedRng::new(StdRng::seed_from_u64(1));
+        let mut clone = rng.clone();
+        assert_eq!(rng.next_u64(), expected.next_u64());
+        assert_eq!(clone.next_u64(), expected.next_u64());
+        assert_eq!(rng.next_u32(), expected.next_u32());
+    }
+
+    #[test]
+    fn test_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<SharedRng>();
+    }
+
+    #[test]
+    fn test_sync() {
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<SharedRng>();
+    }
+}
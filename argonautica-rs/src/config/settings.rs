@@ -0,0 +1,505 @@
+use std::collections::HashMap;
+use std::env;
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::path::Path;
+
+use serde_json;
+use toml;
+
+use config::defaults::*;
+use config::hasher_config::{
+    validate_backend, validate_hash_len, validate_iterations, validate_lanes, validate_memory_size,
+    validate_salt_len, validate_threads,
+};
+#[cfg(feature = "normalization")]
+use config::Normalization;
+use config::{Backend, ValidationReport, Variant, Version};
+use {Error, ErrorKind};
+
+/// Prefix of the environment variables read by
+/// [`Settings::from_env`](struct.Settings.html#method.from_env)
+pub const ENV_PREFIX: &str = "ARGONAUTICA_";
+
+const FIELDS: &[&str] = &[
+    "backend",
+    "hash_len",
+    "iterations",
+    "lanes",
+    "memory_size",
+    "normalization",
+    "opt_out_of_secret_key",
+    "password_clearing",
+    "salt_len",
+    "secret_key_clearing",
+    "threads",
+    "variant",
+    "version",
+];
+
+impl Default for Settings {
+    /// Returns the default configuration of [`Hasher`](../struct.Hasher.html), i.e. the same
+    /// values as described in [`Hasher::new`](../struct.Hasher.html#method.new)
+    fn default() -> Settings {
+        Settings {
+            backend: Backend::default(),
+            hash_len: DEFAULT_HASH_LEN,
+            iterations: DEFAULT_ITERATIONS,
+            lanes: default_lanes(),
+            memory_size: DEFAULT_MEMORY_SIZE,
+            #[cfg(feature = "normalization")]
+            normalization: None,
+            opt_out_of_secret_key: DEFAULT_OPT_OUT_OF_SECRET_KEY,
+            password_clearing: DEFAULT_PASSWORD_CLEARING,
+            salt_len: DEFAULT_SALT_LEN,
+            secret_key_clearing: DEFAULT_SECRET_KEY_CLEARING,
+            threads: default_threads(),
+            variant: Variant::default(),
+            version: Version::default(),
+        }
+    }
+}
+
+/// Validated configuration for [`Hasher`](../struct.Hasher.html) and
+/// [`Verifier`](../struct.Verifier.html) that can be loaded from a TOML or JSON file and from
+/// `ARGONAUTICA_*` environment variables (requires the `settings` feature).
+///
+/// Files contain the options below at the top level, e.g.
+///
+/// ```toml
+/// iterations = 256
+/// memory_size = 8192
+/// variant = "argon2id"
+/// ```
+///
+/// and environment variables are named after them, e.g. `ARGONAUTICA_MEMORY_SIZE=8192`.
+/// Options that are not set keep their defaults. The options are `backend` (`"c"`),
+/// `hash_len`, `iterations`, `lanes`, `memory_size`, `normalization` (`"none"`, `"nfc"`,
+/// `"nfkc"` or `"opaque-string"`; requires the `normalization` feature), `opt_out_of_secret_key`, `password_clearing`,
+/// `salt_len` (the length of the random salt), `secret_key_clearing`, `threads`, `variant`
+/// (`"argon2d"`, `"argon2i"` or `"argon2id"`) and `version` (`16` or `19`).
+///
+/// Every loader checks all options and, on failure, returns a
+/// [`ValidationReport`](struct.ValidationReport.html) listing every unknown option, malformed
+/// value and invalid configuration (e.g. a memory size that is not a power of two) at once,
+/// so a [`Settings`](struct.Settings.html) always holds a configuration that can be hashed
+/// with. Apply it with [`Hasher::configure_settings`](../struct.Hasher.html#method.configure_settings)
+/// or [`Verifier::configure_settings`](../struct.Verifier.html#method.configure_settings)
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Settings {
+    backend: Backend,
+    hash_len: u32,
+    iterations: u32,
+    lanes: u32,
+    memory_size: u32,
+    #[cfg(feature = "normalization")]
+    normalization: Option<Normalization>,
+    opt_out_of_secret_key: bool,
+    password_clearing: bool,
+    salt_len: u32,
+    secret_key_clearing: bool,
+    threads: u32,
+    variant: Variant,
+    version: Version,
+}
+
+impl Settings {
+    /// Loads the settings from the provided TOML or JSON file (chosen by its extension,
+    /// `.toml` or `.json`) and then from `ARGONAUTICA_*` environment variables, which take
+    /// precedence over the file
+    pub fn load<P>(path: P) -> Result<Settings, ValidationReport>
+    where
+        P: AsRef<Path>,
+    {
+        let mut settings = Settings::default();
+        let mut report = ValidationReport::new();
+        settings.merge_file(path.as_ref(), &mut report);
+        settings.merge_env(&mut report);
+        settings.finish(report)
+    }
+    /// Loads the settings from `ARGONAUTICA_*` environment variables only
+    pub fn from_env() -> Result<Settings, ValidationReport> {
+        let mut settings = Settings::default();
+        let mut report = ValidationReport::new();
+        settings.merge_env(&mut report);
+        settings.finish(report)
+    }
+    /// Loads the settings from a TOML or JSON file (chosen by its extension, `.toml` or
+    /// `.json`) only
+    pub fn from_file<P>(path: P) -> Result<Settings, ValidationReport>
+    where
+        P: AsRef<Path>,
+    {
+        let mut settings = Settings::default();
+        let mut report = ValidationReport::new();
+        settings.merge_file(path.as_ref(), &mut report);
+        settings.finish(report)
+    }
+    /// Loads the settings from a JSON document
+    pub fn from_json_str(s: &str) -> Result<Settings, ValidationReport> {
+        let mut settings = Settings::default();
+        let mut report = ValidationReport::new();
+        settings.merge_json(s, &mut report);
+        settings.finish(report)
+    }
+    /// Loads the settings from a TOML document
+    pub fn from_toml_str(s: &str) -> Result<Settings, ValidationReport> {
+        let mut settings = Settings::default();
+        let mut report = ValidationReport::new();
+        settings.merge_toml(s, &mut report);
+        settings.finish(report)
+    }
+    #[allow(missing_docs)]
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
+    #[allow(missing_docs)]
+    pub fn hash_len(&self) -> u32 {
+        self.hash_len
+    }
+    #[allow(missing_docs)]
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+    #[allow(missing_docs)]
+    pub fn lanes(&self) -> u32 {
+        self.lanes
+    }
+    #[allow(missing_docs)]
+    pub fn memory_size(&self) -> u32 {
+        self.memory_size
+    }
+    #[cfg(feature = "normalization")]
+    #[allow(missing_docs)]
+    pub fn normalization(&self) -> Option<Normalization> {
+        self.normalization
+    }
+    #[allow(missing_docs)]
+    pub fn opt_out_of_secret_key(&self) -> bool {
+        self.opt_out_of_secret_key
+    }
+    #[allow(missing_docs)]
+    pub fn password_clearing(&self) -> bool {
+        self.password_clearing
+    }
+    #[allow(missing_docs)]
+    pub fn salt_len(&self) -> u32 {
+        self.salt_len
+    }
+    #[allow(missing_docs)]
+    pub fn secret_key_clearing(&self) -> bool {
+        self.secret_key_clearing
+    }
+    #[allow(missing_docs)]
+    pub fn threads(&self) -> u32 {
+        self.threads
+    }
+    #[allow(missing_docs)]
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+    #[allow(missing_docs)]
+    pub fn version(&self) -> Version {
+        self.version
+    }
+}
+
+impl Settings {
+    fn finish(self, mut report: ValidationReport) -> Result<Settings, ValidationReport> {
+        report.check("backend", validate_backend(self.backend));
+        report.check("hash_len", validate_hash_len(self.hash_len));
+        report.check("iterations", validate_iterations(self.iterations));
+        report.check("lanes", validate_lanes(self.lanes));
+        report.check(
+            "memory_size",
+            validate_memory_size(self.lanes, self.memory_size),
+        );
+        report.check("salt_len", validate_salt_len(self.salt_len));
+        report.check("threads", validate_threads(self.threads));
+        report.into_result(self)
+    }
+    fn merge_env(&mut self, report: &mut ValidationReport) {
+        self.merge_env_from(env::vars_os(), report)
+    }
+    fn merge_env_from<I>(&mut self, vars: I, report: &mut ValidationReport)
+    where
+        I: IntoIterator<Item = (OsString, OsString)>,
+    {
+        let vars = vars.into_iter().collect::<HashMap<OsString, OsString>>();
+        for field in FIELDS {
+            let name = format!("{}{}", ENV_PREFIX, field.to_uppercase());
+            match vars.get(OsStr::new(&name)).map(|value| value.to_str()) {
+                Some(Some(value)) => {
+                    let result = self.set(field, value);
+                    report.check(name, result);
+                }
+                Some(None) => report.push(
+                    Some(name),
+                    Error::new(ErrorKind::ConfigurationError).add_context("Not valid utf-8"),
+                ),
+                None => (),
+            }
+        }
+    }
+    fn merge_file(&mut self, path: &Path, report: &mut ValidationReport) {
+        let s = match fs::read_to_string(path) {
+            Ok(s) => s,
+            Err(e) => {
+                return report.push(
+                    None::<String>,
+                    Error::new(ErrorKind::IoError).add_context(format!(
+                        "{}: {}",
+                        path.display(),
+                        e
+                    )),
+                );
+            }
+        };
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => self.merge_json(&s, report),
+            Some("toml") => self.merge_toml(&s, report),
+            _ => report.push(
+                None::<String>,
+                Error::new(ErrorKind::ConfigurationError).add_context(format!(
+                    "{}: Unsupported file extension, expected .toml or .json",
+                    path.display()
+                )),
+            ),
+        }
+    }
+    fn merge_json(&mut self, s: &str, report: &mut ValidationReport) {
+        let object = match serde_json::from_str::<serde_json::Value>(s) {
+            Ok(serde_json::Value::Object(object)) => object,
+            Ok(_) => return report.push(None::<String>, syntax_error("Expected a JSON object")),
+            Err(e) => return report.push(None::<String>, syntax_error(&e.to_string())),
+        };
+        for (field, value) in object {
+            let result = match value {
+                serde_json::Value::Bool(b) => self.set(&field, &b.to_string()),
+                serde_json::Value::Number(n) => self.set(&field, &n.to_string()),
+                serde_json::Value::String(s) => self.set(&field, &s),
+                _ => Err(type_error()),
+            };
+            report.check(field, result);
+        }
+    }
+    fn merge_toml(&mut self, s: &str, report: &mut ValidationReport) {
+        let table = match s.parse::<toml::Value>() {
+            Ok(toml::Value::Table(table)) => table,
+            Ok(_) => return report.push(None::<String>, syntax_error("Expected a TOML table")),
+            Err(e) => return report.push(None::<String>, syntax_error(&e.to_string())),
+        };
+        for (field, value) in table {
+            let result = match value {
+                toml::Value::Boolean(b) => self.set(&field, &b.to_string()),
+                toml::Value::Integer(i) => self.set(&field, &i.to_string()),
+                toml::Value::String(s) => self.set(&field, &s),
+                _ => Err(type_error()),
+            };
+            report.check(field, result);
+        }
+    }
+    fn set(&mut self, field: &str, value: &str) -> Result<(), Error> {
+        match field {
+            "backend" => {
+                self.backend = match value {
+                    "c" => Backend::C,
+                    "rust" => Backend::Rust,
+                    _ => return Err(value_error(value)),
+                }
+            }
+            "hash_len" => self.hash_len = parse_u32(value)?,
+            "iterations" => self.iterations = parse_u32(value)?,
+            "lanes" => self.lanes = parse_u32(value)?,
+            "memory_size" => self.memory_size = parse_u32(value)?,
+            #[cfg(feature = "normalization")]
+            "normalization" => {
+                self.normalization = match value {
+                    "none" => None,
+                    "nfc" => Some(Normalization::Nfc),
+                    "nfkc" => Some(Normalization::Nfkc),
+                    "opaque-string" => Some(Normalization::OpaqueString),
+                    _ => return Err(value_error(value)),
+                }
+            }
+            #[cfg(not(feature = "normalization"))]
+            "normalization" => {
+                return Err(Error::new(ErrorKind::ConfigurationError)
+                    .add_context("Requires the normalization feature"))
+            }
+            "opt_out_of_secret_key" => self.opt_out_of_secret_key = parse_bool(value)?,
+            "password_clearing" => self.password_clearing = parse_bool(value)?,
+            "salt_len" => self.salt_len = parse_u32(value)?,
+            "secret_key_clearing" => self.secret_key_clearing = parse_bool(value)?,
+            "threads" => self.threads = parse_u32(value)?,
+            "variant" => self.variant = value.parse().map_err(|_| value_error(value))?,
+            "version" => self.version = value.parse().map_err(|_| value_error(value))?,
+            _ => {
+                return Err(Error::new(ErrorKind::ConfigurationError).add_context("Unknown option"))
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_bool(value: &str) -> Result<bool, Error> {
+    match value {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        _ => Err(value_error(value)),
+    }
+}
+
+fn parse_u32(value: &str) -> Result<u32, Error> {
+    value.parse::<u32>().map_err(|_| value_error(value))
+}
+
+fn syntax_error(msg: &str) -> Error {
+    Error::new(ErrorKind::ConfigurationError).add_context(msg)
+}
+
+fn type_error() -> Error {
+    Error::new(ErrorKind::ConfigurationError)
+        .add_context("Expected a string, an integer or a boolean")
+}
+
+fn value_error(value: &str) -> Error {
+    Error::new(ErrorKind::ConfigurationError).add_context(format!("Invalid value: {:?}", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_from_toml_str() {
+        let settings = Settings::from_toml_str(
+            "iterations = 256\nmemory_size = 8192\nlanes = 2\nvariant = \"argon2i\"\n\
+             version = 16\nopt_out_of_secret_key = true\n",
+        )
+        .unwrap();
+        assert_eq!(settings.iterations(), 256);
+        assert_eq!(settings.memory_size(), 8192);
+        assert_eq!(settings.lanes(), 2);
+        assert_eq!(settings.variant(), Variant::Argon2i);
+        assert_eq!(settings.version(), Version::_0x10);
+        assert!(settings.opt_out_of_secret_key());
+        assert_eq!(settings.hash_len(), DEFAULT_HASH_LEN);
+        assert_eq!(settings.salt_len(), DEFAULT_SALT_LEN);
+    }
+
+    #[cfg(feature = "normalization")]
+    #[test]
+    fn test_settings_normalization() {
+        let settings = Settings::from_toml_str("normalization = \"opaque-string\"\n").unwrap();
+        assert_eq!(settings.normalization(), Some(Normalization::OpaqueString));
+        let settings = Settings::from_json_str(r#"{"normalization": "none"}"#).unwrap();
+        assert_eq!(settings.normalization(), None);
+    }
+
+    #[cfg(not(feature = "normalization"))]
+    #[test]
+    fn test_settings_normalization() {
+        let report = Settings::from_toml_str("normalization = \"nfc\"\n").unwrap_err();
+        let issue = report.iter().next().unwrap();
+        assert_eq!(issue.field(), Some("normalization"));
+        assert_eq!(issue.error().kind(), ErrorKind::ConfigurationError);
+    }
+
+    #[test]
+    fn test_settings_from_json_str() {
+        let settings =
+            Settings::from_json_str(r#"{"hash_len": 64, "password_clearing": true}"#).unwrap();
+        assert_eq!(settings.hash_len(), 64);
+        assert!(settings.password_clearing());
+        assert_eq!(
+            settings,
+            Settings {
+                hash_len: 64,
+                password_clearing: true,
+                ..Settings::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_settings_report_lists_every_issue() {
+        let report = Settings::from_toml_str(
+            "iterations = 0\nlanes = \"four\"\nmemory_size = 1000\nsalt_len = 4\n\
+             varient = \"argon2id\"\nthreads = [1]\nbackend = \"rust\"\n",
+        )
+        .unwrap_err();
+        let mut issues = report
+            .iter()
+            .map(|issue| (issue.field().unwrap().to_string(), issue.error().kind()))
+            .collect::<Vec<_>>();
+        issues.sort();
+        assert_eq!(
+            issues,
+            vec![
+                ("backend".to_string(), ErrorKind::BackendUnsupportedError),
+                ("iterations".to_string(), ErrorKind::IterationsTooFewError),
+                ("lanes".to_string(), ErrorKind::ConfigurationError),
+                ("memory_size".to_string(), ErrorKind::MemorySizeInvalidError),
+                ("salt_len".to_string(), ErrorKind::SaltTooShortError),
+                ("threads".to_string(), ErrorKind::ConfigurationError),
+                ("varient".to_string(), ErrorKind::ConfigurationError),
+            ]
+        );
+
+        let report = Settings::from_json_str("[1, 2]").unwrap_err();
+        assert_eq!(report.len(), 1);
+        assert_eq!(report.iter().next().unwrap().field(), None);
+    }
+
+    #[test]
+    fn test_settings_load() {
+        let path = env::temp_dir().join(format!(
+            "argonautica-settings-{}.toml",
+            ::std::process::id()
+        ));
+        fs::write(&path, "iterations = 256\nhash_len = 16\n").unwrap();
+        let load = |hash_len: &str| {
+            let mut settings = Settings::default();
+            let mut report = ValidationReport::new();
+            settings.merge_file(&path, &mut report);
+            settings.merge_env_from(
+                vec![
+                    ("ARGONAUTICA_HASH_LEN".into(), hash_len.into()),
+                    ("ARGONAUTICA_UNKNOWN".into(), "1".into()),
+                ],
+                &mut report,
+            );
+            settings.finish(report)
+        };
+        let result = load("48");
+        let report = load("not a number").unwrap_err();
+        fs::remove_file(&path).unwrap();
+
+        let settings = result.unwrap();
+        assert_eq!(settings.iterations(), 256);
+        assert_eq!(settings.hash_len(), 48);
+        assert_eq!(
+            report.iter().next().unwrap().field(),
+            Some("ARGONAUTICA_HASH_LEN")
+        );
+
+        let report = Settings::from_file(&path).unwrap_err();
+        assert_eq!(
+            report.iter().next().unwrap().error().kind(),
+            ErrorKind::IoError
+        );
+    }
+
+    #[test]
+    fn test_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<Settings>();
+    }
+
+    #[test]
+    fn test_sync() {
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<Settings>();
+    }
+}
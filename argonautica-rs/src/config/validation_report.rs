@@ -0,0 +1,147 @@
+use std::fmt;
+use std::slice;
+
+use failure::Fail;
+
+use {Error, ErrorKind};
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, issue) in self.issues.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.field {
+            Some(ref field) => write!(f, "{}: {}", field, self.error),
+            None => write!(f, "{}", self.error),
+        }
+    }
+}
+
+impl Fail for ValidationReport {}
+
+impl From<ValidationReport> for Error {
+    /// Collapses the report into a single [`Error`](../struct.Error.html) of kind
+    /// [`ConfigurationError`](../enum.ErrorKind.html#variant.ConfigurationError) whose context
+    /// lists every issue
+    fn from(report: ValidationReport) -> Error {
+        Error::new(ErrorKind::ConfigurationError).add_context(report.to_string())
+    }
+}
+
+/// Struct listing <u>every</u> problem found while loading or validating a configuration, as
+/// opposed to only the first one
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct ValidationReport {
+    issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Returns `true` if no problems were found
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+    /// Iterates over the problems found, in the order they were found
+    pub fn iter(&self) -> slice::Iter<'_, ValidationIssue> {
+        self.issues.iter()
+    }
+    /// Returns the number of problems found
+    pub fn len(&self) -> usize {
+        self.issues.len()
+    }
+}
+
+impl ValidationReport {
+    pub(crate) fn new() -> ValidationReport {
+        ValidationReport::default()
+    }
+    pub(crate) fn push<S>(&mut self, field: Option<S>, error: Error)
+    where
+        S: Into<String>,
+    {
+        self.issues.push(ValidationIssue {
+            error,
+            field: field.map(|field| field.into()),
+        });
+    }
+    pub(crate) fn check<S>(&mut self, field: S, result: Result<(), Error>)
+    where
+        S: Into<String>,
+    {
+        if let Err(error) = result {
+            self.push(Some(field), error);
+        }
+    }
+    pub(crate) fn into_result<T>(self, value: T) -> Result<T, ValidationReport> {
+        if self.is_empty() {
+            Ok(value)
+        } else {
+            Err(self)
+        }
+    }
+}
+
+/// Struct representing a single problem in a [`ValidationReport`](struct.ValidationReport.html)
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ValidationIssue {
+    error: Error,
+    field: Option<String>,
+}
+
+impl ValidationIssue {
+    /// Read-only access to the [`Error`](../struct.Error.html) describing the problem
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+    /// Returns the name of the configuration option the problem concerns, or `None` if it
+    /// does not concern a single option (e.g. a file that could not be read)
+    pub fn field(&self) -> Option<&str> {
+        self.field.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation_report() {
+        let mut report = ValidationReport::new();
+        assert_eq!(report.clone().into_result(1).unwrap(), 1);
+        report.check("iterations", Ok(()));
+        report.check("lanes", Err(Error::new(ErrorKind::LanesTooFewError)));
+        report.push(None::<String>, Error::new(ErrorKind::IoError));
+        assert_eq!(report.len(), 2);
+        assert_eq!(report.iter().next().unwrap().field(), Some("lanes"));
+        assert_eq!(
+            report.to_string(),
+            format!(
+                "lanes: {}; {}",
+                ErrorKind::LanesTooFewError,
+                ErrorKind::IoError
+            )
+        );
+        let err: Error = report.into_result(1).unwrap_err().into();
+        assert_eq!(err.kind(), ErrorKind::ConfigurationError);
+    }
+
+    #[test]
+    fn test_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<ValidationReport>();
+    }
+
+    #[test]
+    fn test_sync() {
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<ValidationReport>();
+    }
+}
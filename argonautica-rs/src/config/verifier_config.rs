@@ -2,7 +2,9 @@ use futures_cpupool::CpuPool;
 
 #[cfg(feature = "serde")]
 use config::defaults::default_cpu_pool_serde;
-use config::Backend;
+#[cfg(feature = "normalization")]
+use config::Normalization;
+use config::{Backend, HasherConfig};
 
 /// Read-only configuration for [`Verifier`](../struct.Verifier.html). Can be obtained by calling
 /// the [`config`](../struct.Verifier.html#method.config) method on an instance of
@@ -21,6 +23,8 @@ pub struct VerifierConfig {
         )
     )]
     pub(crate) cpu_pool: Option<CpuPool>,
+    #[cfg(feature = "normalization")]
+    pub(crate) normalization: Option<Normalization>,
     pub(crate) password_clearing: bool,
     pub(crate) secret_key_clearing: bool,
     pub(crate) threads: u32,
@@ -38,6 +42,11 @@ impl VerifierConfig {
             None => None,
         }
     }
+    #[cfg(feature = "normalization")]
+    #[allow(missing_docs)]
+    pub fn normalization(&self) -> Option<Normalization> {
+        self.normalization
+    }
     #[allow(missing_docs)]
     pub fn password_clearing(&self) -> bool {
         self.password_clearing
@@ -53,19 +62,15 @@ impl VerifierConfig {
 }
 
 impl VerifierConfig {
-    pub(crate) fn new(
-        backend: Backend,
-        cpu_pool: Option<CpuPool>,
-        password_clearing: bool,
-        secret_key_clearing: bool,
-        threads: u32,
-    ) -> VerifierConfig {
+    pub(crate) fn new(hasher_config: &HasherConfig) -> VerifierConfig {
         VerifierConfig {
-            backend,
-            cpu_pool,
-            password_clearing,
-            secret_key_clearing,
-            threads,
+            backend: hasher_config.backend(),
+            cpu_pool: hasher_config.cpu_pool(),
+            #[cfg(feature = "normalization")]
+            normalization: hasher_config.normalization(),
+            password_clearing: hasher_config.password_clearing(),
+            secret_key_clearing: hasher_config.secret_key_clearing(),
+            threads: hasher_config.threads(),
         }
     }
 }
@@ -0,0 +1,306 @@
+use config::{HasherConfig, VerifierConfig};
+use input::{AdditionalData, Password, SecretKey};
+use output::HashRaw;
+use {Error, Hasher, Verifier};
+
+/// Immutable, thread-safe counterpart of [`Hasher`](struct.Hasher.html).
+///
+/// [`Hasher`](struct.Hasher.html) stores the password inside itself and its
+/// [`hash`](struct.Hasher.html#method.hash) method takes `&mut self`, so a configured
+/// [`Hasher`](struct.Hasher.html) cannot be shared between threads without calling
+/// [`to_owned`](struct.Hasher.html#method.to_owned) for every password.
+/// [`HasherContext`](struct.HasherContext.html) holds the configuration, salt, secret key and
+/// additional data of a [`Hasher`](struct.Hasher.html) and instead takes the password as an
+/// argument to [`hash`](struct.HasherContext.html#method.hash), which only needs `&self`.
+/// It is `Send` and `Sync`; so you can put it behind an
+/// [`Arc`](https://doc.rust-lang.org/std/sync/struct.Arc.html) and hash from as many
+/// threads as you like.
+///
+/// ```
+/// extern crate argonautica;
+///
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// use argonautica::{Hasher, HasherContext, Verifier, VerifierContext};
+///
+/// fn main() {
+///     let mut hasher = Hasher::default();
+///     hasher
+///         .configure_iterations(8)
+///         .configure_memory_size(64)
+///         .with_secret_key("\
+///             secret key that you should really store in a .env file \
+///             instead of in code, but this is just an example\
+///         ");
+///     let hasher = Arc::new(HasherContext::new(&hasher).unwrap());
+///
+///     let mut verifier = Verifier::default();
+///     verifier.with_secret_key("\
+///         secret key that you should really store in a .env file \
+///         instead of in code, but this is just an example\
+///     ");
+///     let verifier = Arc::new(VerifierContext::new(&verifier).unwrap());
+///
+///     let handles = (0..4)
+///         .map(|i| {
+///             let hasher = hasher.clone();
+///             let verifier = verifier.clone();
+///             thread::spawn(move || {
+///                 let password = format!("P@ssw0rd{}", i);
+///                 let hash = hasher.hash(password.as_str()).unwrap();
+///                 assert!(verifier.verify(&hash, password.as_str()).unwrap());
+///             })
+///         })
+///         .collect::<Vec<_>>();
+///     for handle in handles {
+///         handle.join().unwrap();
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct HasherContext {
+    hasher: Hasher<'static>,
+}
+
+impl HasherContext {
+    /// Creates a [`HasherContext`](struct.HasherContext.html) from a copy of the configuration,
+    /// salt, secret key and additional data of the provided [`Hasher`](struct.Hasher.html)
+    /// (its password, if any, is ignored). Returns an [`Error`](struct.Error.html) of kind
+    /// [`ConfigurationError`](enum.ErrorKind.html#variant.ConfigurationError) if the
+    /// [`Hasher`](struct.Hasher.html) does not pass
+    /// [`validate`](struct.Hasher.html#method.validate).
+    ///
+    /// Since the secret key is shared by every call, secret key clearing is always turned off
+    /// for a [`HasherContext`](struct.HasherContext.html). Password clearing is kept; if it is
+    /// on, you must pass mutable passwords to [`hash`](struct.HasherContext.html#method.hash)
+    pub fn new(hasher: &Hasher) -> Result<HasherContext, Error> {
+        hasher.validate()?;
+        let mut hasher = hasher.to_owned();
+        hasher.password = None;
+        hasher.config.set_secret_key_clearing(false);
+        Ok(HasherContext { hasher })
+    }
+    /// Hashes the provided password, returning a string-encoded hash (see
+    /// [`Hasher::hash`](struct.Hasher.html#method.hash))
+    pub fn hash<'b, P>(&self, password: P) -> Result<String, Error>
+    where
+        P: Into<Password<'b>>,
+    {
+        let mut hasher = self.hasher.reborrow();
+        hasher.password = Some(password.into());
+        hasher.hash()
+    }
+    /// Hashes the provided password, returning a [`HashRaw`](output/struct.HashRaw.html) (see
+    /// [`Hasher::hash_raw`](struct.Hasher.html#method.hash_raw))
+    pub fn hash_raw<'b, P>(&self, password: P) -> Result<HashRaw, Error>
+    where
+        P: Into<Password<'b>>,
+    {
+        let mut hasher = self.hasher.reborrow();
+        hasher.password = Some(password.into());
+        hasher.hash_raw()
+    }
+    /// See [`Hasher::needs_rehash`](struct.Hasher.html#method.needs_rehash)
+    pub fn needs_rehash(&self, hash: &str) -> Result<bool, Error> {
+        self.hasher.needs_rehash(hash)
+    }
+}
+
+impl HasherContext {
+    #[allow(missing_docs)]
+    pub fn additional_data(&self) -> Option<&AdditionalData> {
+        self.hasher.additional_data()
+    }
+    #[allow(missing_docs)]
+    pub fn config(&self) -> &HasherConfig {
+        self.hasher.config()
+    }
+    #[allow(missing_docs)]
+    pub fn secret_key(&self) -> Option<&SecretKey<'static>> {
+        self.hasher.secret_key()
+    }
+}
+
+/// Immutable, thread-safe counterpart of [`Verifier`](struct.Verifier.html).
+///
+/// Holds the configuration, secret key and additional data of a
+/// [`Verifier`](struct.Verifier.html) and takes the hash and password as arguments to
+/// [`verify`](struct.VerifierContext.html#method.verify), which only needs `&self`. It is
+/// `Send` and `Sync`; see [`HasherContext`](struct.HasherContext.html) for an example
+#[derive(Debug)]
+pub struct VerifierContext {
+    hasher: Hasher<'static>,
+}
+
+impl VerifierContext {
+    /// Creates a [`VerifierContext`](struct.VerifierContext.html) from a copy of the
+    /// configuration, secret key and additional data of the provided
+    /// [`Verifier`](struct.Verifier.html) (its hash and password, if any, are ignored). Returns
+    /// an [`Error`](struct.Error.html) of kind
+    /// [`ConfigurationError`](enum.ErrorKind.html#variant.ConfigurationError) if the
+    /// [`Verifier`](struct.Verifier.html) does not pass
+    /// [`validate`](struct.Verifier.html#method.validate).
+    ///
+    /// As with [`HasherContext`](struct.HasherContext.html), secret key clearing is always
+    /// turned off
+    pub fn new(verifier: &Verifier) -> Result<VerifierContext, Error> {
+        verifier.validate()?;
+        let mut hasher = verifier.hasher.to_owned();
+        hasher.password = None;
+        hasher.config.set_secret_key_clearing(false);
+        Ok(VerifierContext { hasher })
+    }
+    /// Verifies the provided password against the provided string-encoded hash (see
+    /// [`Verifier::verify`](struct.Verifier.html#method.verify))
+    pub fn verify<'b, P>(&self, hash: &str, password: P) -> Result<bool, Error>
+    where
+        P: Into<Password<'b>>,
+    {
+        let mut verifier = Verifier::from_hasher(self.hasher.reborrow());
+        verifier.with_hash(hash);
+        verifier.hasher.password = Some(password.into());
+        verifier.verify()
+    }
+    /// Verifies the provided password against the provided
+    /// [`HashRaw`](output/struct.HashRaw.html) (see
+    /// [`Verifier::verify`](struct.Verifier.html#method.verify))
+    pub fn verify_hash_raw<'b, P>(&self, hash_raw: &HashRaw, password: P) -> Result<bool, Error>
+    where
+        P: Into<Password<'b>>,
+    {
+        let mut verifier = Verifier::from_hasher(self.hasher.reborrow());
+        verifier.with_hash_raw(hash_raw);
+        verifier.hasher.password = Some(password.into());
+        verifier.verify()
+    }
+    /// See [`Verifier::verify_dummy`](struct.Verifier.html#method.verify_dummy)
+    pub fn verify_dummy<'b, P>(&self, config: &HasherConfig, password: P) -> Result<bool, Error>
+    where
+        P: Into<Password<'b>>,
+    {
+        let mut verifier = Verifier::from_hasher(self.hasher.reborrow());
+        verifier.hasher.password = Some(password.into());
+        verifier.verify_dummy(config)
+    }
+}
+
+impl VerifierContext {
+    #[allow(missing_docs)]
+    pub fn additional_data(&self) -> Option<&AdditionalData> {
+        self.hasher.additional_data()
+    }
+    #[allow(missing_docs)]
+    pub fn config(&self) -> VerifierConfig {
+        VerifierConfig::new(&self.hasher.config)
+    }
+    #[allow(missing_docs)]
+    pub fn secret_key(&self) -> Option<&SecretKey<'static>> {
+        self.hasher.secret_key()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+
+    use super::*;
+    use ErrorKind;
+
+    const SECRET_KEY: &str = "\
+        secret key that you should really store in a .env file \
+        instead of in code, but this is just an example\
+    ";
+
+    fn hasher() -> Hasher<'static> {
+        let mut hasher = Hasher::default();
+        hasher
+            .configure_iterations(8)
+            .configure_lanes(2)
+            .configure_memory_size(64)
+            .configure_threads(2)
+            .with_secret_key(SECRET_KEY);
+        hasher
+    }
+
+    #[test]
+    fn test_contexts_across_threads() {
+        let hasher = Arc::new(HasherContext::new(&hasher()).unwrap());
+        let mut verifier = Verifier::default();
+        verifier.with_secret_key(SECRET_KEY);
+        let verifier = Arc::new(VerifierContext::new(&verifier).unwrap());
+        let handles = (0..8)
+            .map(|i| {
+                let hasher = hasher.clone();
+                let verifier = verifier.clone();
+                thread::spawn(move || {
+                    let password = format!("P@ssw0rd{}", i);
+                    let hash = hasher.hash(password.as_str()).unwrap();
+                    assert!(!hasher.needs_rehash(&hash).unwrap());
+                    assert!(verifier.verify(&hash, password.as_str()).unwrap());
+                    assert!(!verifier.verify(&hash, "wrong").unwrap());
+                    let hash_raw = hasher.hash_raw(password.as_bytes()).unwrap();
+                    assert!(verifier
+                        .verify_hash_raw(&hash_raw, password.as_bytes())
+                        .unwrap());
+                    hash
+                })
+            })
+            .collect::<Vec<_>>();
+        let hashes = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Vec<_>>();
+        assert_ne!(hashes[0], hashes[1]);
+        assert_eq!(
+            hasher.secret_key().unwrap().as_bytes(),
+            SECRET_KEY.as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_contexts_clearing() {
+        let mut hasher = hasher();
+        hasher
+            .configure_password_clearing(true)
+            .configure_secret_key_clearing(true)
+            .with_secret_key(SECRET_KEY.to_string());
+        let context = HasherContext::new(&hasher).unwrap();
+        assert!(!context.config().secret_key_clearing());
+        let err = context.hash("P@ssw0rd").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PasswordImmutableError);
+        let mut password = "P@ssw0rd".to_string();
+        let hash = context.hash(&mut password).unwrap();
+        assert!(password.bytes().all(|byte| byte == 0));
+        assert_ne!(context.hash("P@ssw0rd".to_string()).unwrap(), hash);
+        assert_eq!(
+            context.secret_key().unwrap().as_bytes(),
+            SECRET_KEY.as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_contexts_validate() {
+        let err = HasherContext::new(&Hasher::default()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigurationError);
+        let mut verifier = Verifier::default();
+        verifier.configure_threads(0);
+        let err = VerifierContext::new(&verifier).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigurationError);
+    }
+
+    #[test]
+    fn test_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<HasherContext>();
+        assert_send::<VerifierContext>();
+    }
+
+    #[test]
+    fn test_sync() {
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<HasherContext>();
+        assert_sync::<VerifierContext>();
+    }
+}
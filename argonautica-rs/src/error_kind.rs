@@ -1,8 +1,22 @@
+use policy::PolicyViolations;
+
 /// Enum representing the various kinds of errors
 #[derive(Fail, Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub enum ErrorKind {
+    /// Additional data mismatch. The additional data provided is not the additional data the hash was bound to
+    #[fail(
+        display = "Additional data mismatch. The additional data provided is not the additional data the hash was bound to"
+    )]
+    AdditionalDataMismatchError,
+
+    /// Additional data missing. The hash was bound to additional data, but none was provided
+    #[fail(
+        display = "Additional data missing. The hash was bound to additional data, but none was provided"
+    )]
+    AdditionalDataMissingError,
+
     /// Additional data too long. Length in bytes must be less than 2^32
     #[fail(display = "Additional data too long. Length in bytes must be less than 2^32")]
     AdditionalDataTooLongError,
@@ -25,6 +39,18 @@ pub enum ErrorKind {
     )]
     Bug,
 
+    /// Configuration invalid. One or more configuration options are unknown, malformed or invalid
+    #[fail(
+        display = "Configuration invalid. One or more configuration options are unknown, malformed or invalid"
+    )]
+    ConfigurationError,
+
+    /// Environment variable error. The environment variable is not set or is not valid unicode
+    #[fail(
+        display = "Environment variable error. The environment variable is not set or is not valid unicode"
+    )]
+    EnvVarError,
+
     /// Hash decode error. Hash provided was invalid
     #[fail(display = "Hash decode error. Hash provided was invalid")]
     HashDecodeError,
@@ -42,10 +68,30 @@ pub enum ErrorKind {
     #[fail(display = "Hash missing. Attempted to verify without first having provided a hash")]
     HashMissingError,
 
+    /// Hex decode error. Bytes provided were invalid hex
+    #[fail(display = "Hex decode error. Bytes provided were invalid hex")]
+    HexDecodeError,
+
+    /// Inner digest invalid. The password provided is not a valid digest of the configured inner scheme
+    #[fail(
+        display = "Inner digest invalid. The password provided is not a valid digest of the configured inner scheme"
+    )]
+    InnerDigestInvalidError,
+
+    /// I/O error. Failed to read from or write to a file
+    #[fail(display = "I/O error. Failed to read from or write to a file")]
+    IoError,
+
     /// Iterations too few. Iterations must be greater than 0
     #[fail(display = "Iterations must be greater than 0")]
     IterationsTooFewError,
 
+    /// Key file invalid. The key file is malformed, has an unsupported version or duplicates the key ID of another key file
+    #[fail(
+        display = "Key file invalid. The key file is malformed, has an unsupported version or duplicates the key ID of another key file"
+    )]
+    KeyFileInvalidError,
+
     /// Lanes too few. Lanes must be greater than 0
     #[fail(display = "Lanes must be greater than 0")]
     LanesTooFewError,
@@ -72,6 +118,14 @@ pub enum ErrorKind {
     #[fail(display = "Failed to access OS random number generator")]
     OsRngError,
 
+    /// Password breached. The password appears in the configured dataset of breached passwords
+    /// the number of times given
+    #[fail(
+        display = "Password breached. The password appears in the configured dataset of breached passwords this many times: {}",
+        _0
+    )]
+    PasswordBreachedError(u64),
+
     /// Password immutable error. You attempted to hash or verify with an immutable password and password_clearing set to true, which is not possible because with an immutable password argonautica cannot zero out the password bytes. To prevent this error, either pass Hasher or Verifier a mutable password or set password_clearing to false
     #[fail(
         display = "Password immutable error. You attempted to hash or verify with an immutable password and password_clearing set to true, which is not possible because with an immutable password argonautica cannot zero out the password bytes. To prevent this error, either pass Hasher or Verifier a mutable password or set password_clearing to false"
@@ -84,6 +138,20 @@ pub enum ErrorKind {
     )]
     PasswordMissingError,
 
+    /// Password normalization error. The password contains characters that are not allowed by the configured normalization
+    #[fail(
+        display = "Password normalization error. The password contains characters that are not allowed by the configured normalization"
+    )]
+    PasswordNormalizationError,
+
+    /// Password policy error. The password violates the configured password policy; the
+    /// [`PolicyViolations`](policy/struct.PolicyViolations.html) list every rule it failed
+    #[fail(
+        display = "Password policy error. The password violates the configured password policy: {:?}",
+        _0
+    )]
+    PasswordPolicyError(PolicyViolations),
+
     /// Password too long. Length in bytes must be less than 2^32
     #[fail(display = "Password too long. Length in bytes must be less than 2^32")]
     PasswordTooLongError,
@@ -92,6 +160,12 @@ pub enum ErrorKind {
     #[fail(display = "Password too short. Length in bytes must be greater than 0")]
     PasswordTooShortError,
 
+    /// Random number generator error. The configured random number generator failed to generate random bytes
+    #[fail(
+        display = "Random number generator error. The configured random number generator failed to generate random bytes"
+    )]
+    RngError,
+
     /// Salt too long. Length in bytes must be less than 2^32
     #[fail(display = "Salt too long. Length in bytes must be less than 2^32")]
     SaltTooLongError,
@@ -106,16 +180,34 @@ pub enum ErrorKind {
     )]
     SecretKeyImmutableError,
 
+    /// Secret key mismatch. The secret key provided (if any) is not the secret key the hash was bound to
+    #[fail(
+        display = "Secret key mismatch. The secret key provided (if any) is not the secret key the hash was bound to"
+    )]
+    SecretKeyMismatchError,
+
     /// Secret key missing. Attempted to hash without a secret key without having first opted out of using a secret key
     #[fail(
         display = "Secret key missing. Attempted to hash without a secret key without having first opted out of using a secret key"
     )]
     SecretKeyMissingError,
 
+    /// Secret key permissions error. The secret key file can be accessed by users other than its owner
+    #[fail(
+        display = "Secret key permissions error. The secret key file can be accessed by users other than its owner"
+    )]
+    SecretKeyPermissionsError,
+
     /// Secret key too long. Length in bytes must be less than 2^32
     #[fail(display = "Secret key too long. Length in bytes must be less than 2^32")]
     SecretKeyTooLongError,
 
+    /// Secret key too short. Length in bytes must be at least the configured minimum
+    #[fail(
+        display = "Secret key too short. Length in bytes must be at least the configured minimum"
+    )]
+    SecretKeyTooShortError,
+
     /// C code reported a "Threading failure" error
     #[fail(display = "C code reported a \"Threading failure\" error")]
     ThreadError,
@@ -1,11 +1,28 @@
+use std::mem;
+use std::sync::Arc;
+use std::time::Instant;
+
 use futures::Future;
 use futures_cpupool::CpuPool;
 use scopeguard;
 
+#[cfg(feature = "binding")]
+use binding;
+#[cfg(feature = "breach")]
+use breach::{self, BreachLookup};
 use config::defaults::{default_cpu_pool, default_lanes};
-use config::{Backend, HasherConfig, Variant, Version};
+#[cfg(feature = "normalization")]
+use config::Normalization;
+#[cfg(feature = "settings")]
+use config::Settings;
+use config::{
+    Backend, HasherConfig, Inner, InnerScheme, SharedRng, ValidationReport, Variant, Version,
+};
 use input::{AdditionalData, Container, Password, Salt, SecretKey};
+use keys::KeyFile;
+use metrics::{self, Metrics, Operation, Recorder};
 use output::HashRaw;
+use policy::PasswordPolicy;
 use {Error, ErrorKind};
 
 impl<'a> Default for Hasher<'a> {
@@ -14,6 +31,7 @@ impl<'a> Default for Hasher<'a> {
         Hasher {
             additional_data: None,
             config: HasherConfig::default(),
+            key_id: None,
             password: None,
             salt: Salt::default(),
             secret_key: None,
@@ -29,6 +47,8 @@ pub struct Hasher<'a> {
     pub(crate) additional_data: Option<AdditionalData>,
     pub(crate) config: HasherConfig,
     #[cfg_attr(feature = "serde", serde(skip_serializing, skip_deserializing))]
+    pub(crate) key_id: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing, skip_deserializing))]
     pub(crate) password: Option<Password<'a>>,
     pub(crate) salt: Salt,
     #[cfg_attr(feature = "serde", serde(skip_serializing, skip_deserializing))]
@@ -64,8 +84,12 @@ impl<'a> Hasher<'a> {
     /// * `iterations`: `192`
     /// * `lanes`: The number of logical cores on your machine
     /// * `memory_size`: `4096` kibibytes
+    /// * `metrics`: `None`
+    /// * `normalization`: `None`
     /// * `opt_out_of_secret_key`: `false`
     /// * `password_clearing`: `false`
+    /// * `password_policy`: `None`
+    /// * `rng`: `None`, i.e. `OsRng`
     /// * `salt`: random [`Salt`](input/struct.Salt.html) of length 32 bytes that renews with every hash
     /// * `secret_key_clearing`: `false`
     /// * `threads`: The number of logical cores on your machine
@@ -122,6 +146,47 @@ impl<'a> Hasher<'a> {
         self.config.set_backend(backend);
         self
     }
+    /// Allows you to configure [`Hasher`](struct.Hasher.html) to bind every hash to the
+    /// secret key and additional data it was created with (requires the `binding` feature).
+    /// A short fingerprint of the key ID and of the additional data is stored in the `data`
+    /// parameter of the hash, which lets [`Verifier`](struct.Verifier.html) return an
+    /// [`Error`](struct.Error.html) of kind
+    /// [`SecretKeyMismatchError`](enum.ErrorKind.html#variant.SecretKeyMismatchError),
+    /// [`AdditionalDataMissingError`](enum.ErrorKind.html#variant.AdditionalDataMissingError) or
+    /// [`AdditionalDataMismatchError`](enum.ErrorKind.html#variant.AdditionalDataMismatchError)
+    /// when it is given the wrong secret key or additional data, instead of reporting a wrong
+    /// password. Binding requires a secret key provided with its ID, i.e. through
+    /// [`with_key_file`](struct.Hasher.html#method.with_key_file); hashing without one returns
+    /// an [`Error`](struct.Error.html) of kind
+    /// [`SecretKeyMissingError`](enum.ErrorKind.html#variant.SecretKeyMissingError). The key
+    /// fingerprint only reveals the key ID, but the additional data fingerprint is keyed with
+    /// the secret key: someone who has your database and knows the additional data of a hash
+    /// can test guesses of the secret key against it without going through Argon2. So binding
+    /// with a secret key shorter than 32 bytes returns an [`Error`](struct.Error.html) of kind
+    /// [`SecretKeyTooShortError`](enum.ErrorKind.html#variant.SecretKeyTooShortError), and the
+    /// key should be random, such as the ones made by
+    /// [`KeyFile::generate`](keys/struct.KeyFile.html#method.generate). The default is `false`,
+    /// i.e. hashes are not bound
+    #[cfg(feature = "binding")]
+    pub fn configure_binding(&mut self, boolean: bool) -> &mut Hasher<'a> {
+        self.config.set_binding(boolean);
+        self
+    }
+    /// Allows you to configure [`Hasher`](struct.Hasher.html) to look every password up in a
+    /// dataset of breached passwords (see the [`breach`](breach/index.html) module) before
+    /// hashing it (requires the `breach` feature). Hashing a password that appears in the
+    /// dataset returns an [`Error`](struct.Error.html) of kind
+    /// [`PasswordBreachedError`](enum.ErrorKind.html#variant.PasswordBreachedError) carrying the
+    /// number of times it was seen. Like the password policy, the lookup is not performed when
+    /// an inner scheme is configured. The default is `None`, i.e. no lookup
+    #[cfg(feature = "breach")]
+    pub fn configure_breach_lookup(
+        &mut self,
+        breach_lookup: Option<Arc<dyn BreachLookup>>,
+    ) -> &mut Hasher<'a> {
+        self.config.set_breach_lookup(breach_lookup);
+        self
+    }
     /// Allows you to configure [`Hasher`](struct.Hasher.html) with a custom
     /// [`CpuPool`](https://docs.rs/futures-cpupool/0.1.8/futures_cpupool/struct.CpuPool.html).
     /// The default [`Hasher`](struct.Hasher.html) does not have a cpu pool, which is
@@ -144,6 +209,16 @@ impl<'a> Hasher<'a> {
         self.config.set_hash_len(hash_len);
         self
     }
+    /// Allows you to configure [`Hasher`](struct.Hasher.html) for onion hashing, i.e. to hash
+    /// an existing legacy digest of the given [`InnerScheme`](config/enum.InnerScheme.html)
+    /// (provided as the password) instead of a plaintext password. The resulting hash records
+    /// the inner scheme, so that [`Verifier`](struct.Verifier.html) can verify plaintext
+    /// passwords against it. This allows you to upgrade stored legacy hashes offline. The
+    /// default is `None`, i.e. the password is a plaintext password
+    pub fn configure_inner_scheme(&mut self, inner_scheme: Option<InnerScheme>) -> &mut Hasher<'a> {
+        self.config.set_inner_scheme(inner_scheme);
+        self
+    }
     /// Allows you to configure [`Hasher`](struct.Hasher.html) to use a custom number of
     /// iterations. The default is `192`.
     ///
@@ -168,6 +243,30 @@ impl<'a> Hasher<'a> {
         self.config.set_memory_size(memory_size);
         self
     }
+    /// Allows you to configure [`Hasher`](struct.Hasher.html) to report every hash, with its
+    /// duration, outcome and parameters, to an implementation of
+    /// [`Metrics`](metrics/trait.Metrics.html) (see the [`metrics`](metrics/index.html) module).
+    /// The non-blocking methods also report how long they waited for the cpu pool. The default
+    /// is `None`, i.e. nothing is reported
+    pub fn configure_metrics(&mut self, metrics: Option<Arc<dyn Metrics>>) -> &mut Hasher<'a> {
+        self.config.set_metrics(metrics);
+        self
+    }
+    /// Allows you to configure [`Hasher`](struct.Hasher.html) to apply a Unicode
+    /// [`Normalization`](config/enum.Normalization.html) to the password before hashing it,
+    /// so that the same passphrase typed on different platforms produces the same hash
+    /// (requires the `normalization` feature). With a normalization configured, the password
+    /// must be valid utf-8. If you use this option, configure your
+    /// [`Verifier`](struct.Verifier.html) with the same normalization. The default is `None`,
+    /// i.e. the password bytes are hashed as they are
+    #[cfg(feature = "normalization")]
+    pub fn configure_normalization(
+        &mut self,
+        normalization: Option<Normalization>,
+    ) -> &mut Hasher<'a> {
+        self.config.set_normalization(normalization);
+        self
+    }
     /// Allows you to configure [`Hasher`](struct.Hasher.html) to erase the password bytes
     /// after each call to [`hash`](struct.Hasher.html#method.hash),
     /// [`hash_raw`](struct.Hasher#method.hash_raw), or their non-blocking equivalents.
@@ -183,6 +282,31 @@ impl<'a> Hasher<'a> {
         self.config.set_password_clearing(boolean);
         self
     }
+    /// Allows you to configure [`Hasher`](struct.Hasher.html) to check every password against
+    /// a [`PasswordPolicy`](policy/struct.PasswordPolicy.html) before hashing it. Hashing a
+    /// password that violates the policy returns an [`Error`](struct.Error.html) of kind
+    /// [`PasswordPolicyError`](enum.ErrorKind.html#variant.PasswordPolicyError) listing every
+    /// rule it failed. The policy is not applied when an inner scheme is configured (see
+    /// [`configure_inner_scheme`](struct.Hasher.html#method.configure_inner_scheme)), since the
+    /// password is then a legacy digest. The default is `None`, i.e. no policy
+    pub fn configure_password_policy(
+        &mut self,
+        password_policy: Option<PasswordPolicy>,
+    ) -> &mut Hasher<'a> {
+        self.config.set_password_policy(password_policy);
+        self
+    }
+    /// Allows you to configure [`Hasher`](struct.Hasher.html) with a custom
+    /// cryptographically-secure random number generator (see
+    /// [`SharedRng`](config/struct.SharedRng.html)) with which to fill a random
+    /// [`Salt`](input/struct.Salt.html) on every hash, e.g. a seeded generator for reproducible
+    /// hashes in tests. If the generator fails, hashing returns an [`Error`](struct.Error.html)
+    /// of kind [`RngError`](enum.ErrorKind.html#variant.RngError). The default is `None`, i.e.
+    /// random salts are generated using `OsRng`. A deterministic salt is never affected
+    pub fn configure_rng(&mut self, rng: Option<SharedRng>) -> &mut Hasher<'a> {
+        self.config.set_rng(rng);
+        self
+    }
     /// Allows you to configure [`Hasher`](struct.Hasher.html) to erase the secret key bytes
     /// after each call to [`hash`](struct.Hasher.html#method.hash),
     /// [`hash_raw`](struct.Hasher#method.hash_raw), or their non-blocking equivalents.
@@ -198,6 +322,35 @@ impl<'a> Hasher<'a> {
         self.config.set_secret_key_clearing(boolean);
         self
     }
+    /// Allows you to configure [`Hasher`](struct.Hasher.html) wholesale with validated
+    /// [`Settings`](config/struct.Settings.html), e.g. loaded from a TOML file and environment
+    /// variables (requires the `settings` feature). This sets the backend, hash length,
+    /// iterations, lanes, memory size, normalization, secret key opt-out, password and secret
+    /// key clearing, threads, variant and version, and replaces the salt with a random
+    /// [`Salt`](input/struct.Salt.html) of the configured length. Options that are not part
+    /// of [`Settings`](config/struct.Settings.html) (e.g. the cpu pool or password policy) are
+    /// left as they are
+    #[cfg(feature = "settings")]
+    pub fn configure_settings(&mut self, settings: &Settings) -> &mut Hasher<'a> {
+        self.config.set_backend(settings.backend());
+        self.config.set_hash_len(settings.hash_len());
+        self.config.set_iterations(settings.iterations());
+        self.config.set_lanes(settings.lanes());
+        self.config.set_memory_size(settings.memory_size());
+        #[cfg(feature = "normalization")]
+        self.config.set_normalization(settings.normalization());
+        self.config
+            .set_opt_out_of_secret_key(settings.opt_out_of_secret_key());
+        self.config
+            .set_password_clearing(settings.password_clearing());
+        self.config
+            .set_secret_key_clearing(settings.secret_key_clearing());
+        self.config.set_threads(settings.threads());
+        self.config.set_variant(settings.variant());
+        self.config.set_version(settings.version());
+        self.salt = Salt::random(settings.salt_len());
+        self
+    }
     /// Allows you to configure [`Hasher`](struct.Hasher.html) to use a custom number of
     /// threads. The default is the number of physical cores on your machine. If you choose
     /// a number of threads that is greater than the lanes configuration,
@@ -260,16 +413,19 @@ impl<'a> Hasher<'a> {
     /// hash bytes and the raw salt bytes. In general, you should prefer to use the
     /// [`hash`](struct.Hasher.html#method.hash) method instead of this method
     pub fn hash_raw(&mut self) -> Result<HashRaw, Error> {
-        let mut hasher = scopeguard::guard(self, |hasher| {
-            hasher.clear();
-        });
-        hasher.validate()?;
-        hasher.salt.update()?;
-        let hash_raw = match hasher.config.backend() {
-            Backend::C => hasher.hash_raw_c()?,
-            Backend::Rust => return Err(Error::new(ErrorKind::BackendUnsupportedError)),
+        let recorder = Recorder::hash(&self.config);
+        let result = {
+            let _entered = recorder.enter();
+            match self.validate_inputs().and_then(|()| self.check_password()) {
+                Ok(()) => self.hash_raw_unchecked(),
+                Err(e) => {
+                    self.clear();
+                    Err(e)
+                }
+            }
         };
-        Ok(hash_raw)
+        recorder.finish(&result, |_| true);
+        result
     }
     /// Same as [`hash_raw`](struct.Hasher.html#method.hash) except it returns a
     /// [`Future`](https://docs.rs/futures/0.1.21/futures/future/trait.Future.html)
@@ -279,15 +435,36 @@ impl<'a> Hasher<'a> {
             hasher.clear();
         });
         let mut hasher = hasher.to_owned();
+        let queued = Instant::now();
         match hasher.config.cpu_pool() {
-            Some(cpu_pool) => cpu_pool.spawn_fn(move || hasher.hash_raw()),
+            Some(cpu_pool) => cpu_pool.spawn_fn(move || {
+                metrics::record_queue_wait(&hasher.config, Operation::Hash, queued);
+                hasher.hash_raw()
+            }),
             None => {
                 let cpu_pool = default_cpu_pool();
                 hasher.config.set_cpu_pool(cpu_pool.clone());
-                cpu_pool.spawn_fn(move || hasher.hash_raw())
+                cpu_pool.spawn_fn(move || {
+                    metrics::record_queue_wait(&hasher.config, Operation::Hash, queued);
+                    hasher.hash_raw()
+                })
             }
         }
     }
+    /// Returns `true` if the provided string-encoded hash was produced with parameters that
+    /// differ from this [`Hasher`](struct.Hasher.html)'s current configuration, i.e. if you
+    /// should rehash the password (e.g. after successfully verifying it) in order to bring
+    /// the stored hash up to date. The parameters compared are hash length, iterations, lanes,
+    /// memory size, salt length, variant, version and inner scheme (see
+    /// [`configure_inner_scheme`](struct.Hasher.html#method.configure_inner_scheme)), as well as
+    /// whether the hash is bound (see
+    /// [`configure_binding`](struct.Hasher.html#method.configure_binding)). Returns an error if
+    /// the hash cannot be decoded
+    pub fn needs_rehash(&self, hash: &str) -> Result<bool, Error> {
+        let hash_raw = hash.parse::<HashRaw>()?;
+        Ok(self.config.needs_rehash(&hash_raw)
+            || hash_raw.raw_salt_bytes().len() != self.salt.len())
+    }
     /// As an extra security measure, if you want to hash without a secret key, which
     /// is not recommended, you must explicitly declare that this is your intention
     /// by calling this method and setting the `opt_out_of_secret_key` configuration to
@@ -309,11 +486,26 @@ impl<'a> Hasher<'a> {
         Hasher {
             additional_data: self.additional_data.clone(),
             config: self.config.clone(),
+            key_id: self.key_id.clone(),
             password,
             salt: self.salt.clone(),
             secret_key,
         }
     }
+    /// Checks everything about the [`Hasher`](struct.Hasher.html) that can be checked before
+    /// a password is provided, i.e. the configuration (e.g. that the memory size is a power of
+    /// two), the additional data, the salt and the secret key (e.g. that one has been provided,
+    /// unless you opted out of it), and returns a
+    /// [`ValidationReport`](config/struct.ValidationReport.html) listing every problem found.
+    /// Call it at startup so that a misconfiguration fails the deploy instead of the first
+    /// call to [`hash`](struct.Hasher.html#method.hash). Use `?` to turn the report into an
+    /// [`Error`](struct.Error.html) of kind
+    /// [`ConfigurationError`](enum.ErrorKind.html#variant.ConfigurationError)
+    pub fn validate(&self) -> Result<(), ValidationReport> {
+        let mut report = ValidationReport::new();
+        self.report(&mut report);
+        report.into_result(())
+    }
     /// Allows you to add some additional data to the [`Hasher`](struct.Hasher.html)
     /// that will be hashed alongside the [`Password`](input/struct.Password.html) and
     /// other pieces of data you would like to hash (i.e. the [`Salt`](input/struct.Salt.html) and
@@ -331,6 +523,15 @@ impl<'a> Hasher<'a> {
         self.additional_data = Some(additional_data.into());
         self
     }
+    /// Allows you to provide [`Hasher`](struct.Hasher.html) with the secret key of a
+    /// [`KeyFile`](keys/struct.KeyFile.html) along with its ID, which is needed to bind
+    /// hashes (see [`configure_binding`](struct.Hasher.html#method.configure_binding)).
+    /// Otherwise the same as [`with_secret_key`](struct.Hasher.html#method.with_secret_key)
+    pub fn with_key_file(&mut self, key_file: &'a KeyFile) -> &mut Hasher<'a> {
+        self.secret_key = Some(SecretKey::from(key_file.secret_key()));
+        self.key_id = Some(key_file.id().to_string());
+        self
+    }
     /// Allows you to provide a [`Hasher`](struct.Hasher.html) with the password you would like
     /// to hash. Hashing requires a password; so you must call this method before calling
     /// [`hash`](struct.Hasher.html#method.hash), [`hash_raw`](struct.Hasher.html#method.hash_raw),
@@ -367,6 +568,7 @@ impl<'a> Hasher<'a> {
         SK: Into<SecretKey<'a>>,
     {
         self.secret_key = Some(secret_key.into());
+        self.key_id = None;
         self
     }
     /// Read-only access to the [`Hasher`](struct.Hasher.html)'s
@@ -379,6 +581,11 @@ impl<'a> Hasher<'a> {
     pub fn config(&self) -> &HasherConfig {
         &self.config
     }
+    /// Read-only access to the ID of the [`Hasher`](struct.Hasher.html)'s secret key, if it
+    /// was provided through [`with_key_file`](struct.Hasher.html#method.with_key_file)
+    pub fn key_id(&self) -> Option<&str> {
+        self.key_id.as_deref()
+    }
     /// Read-only access to the [`Hasher`](struct.Hasher.html)'s
     /// [`Password`](input/struct.Password.html), if any
     pub fn password(&self) -> Option<&Password<'a>> {
@@ -396,6 +603,95 @@ impl<'a> Hasher<'a> {
 }
 
 impl<'a> Hasher<'a> {
+    /// Same as [`hash_raw`](struct.Hasher.html#method.hash_raw) but neither instrumented nor
+    /// checking the password against the password policy and breach lookup, for hashing done
+    /// on behalf of a verification (whose password was checked when it was first hashed)
+    pub(crate) fn hash_raw_unchecked(&mut self) -> Result<HashRaw, Error> {
+        let mut hasher = scopeguard::guard(self, |hasher| {
+            hasher.clear();
+        });
+        hasher.validate_inputs()?;
+        match hasher.config.rng().cloned() {
+            Some(mut rng) => hasher.salt.update_with_rng(&mut rng)?,
+            None => hasher.salt.update()?,
+        }
+        let hash_raw = match hasher.config.backend() {
+            Backend::C => hasher.hash_raw_normalized()?,
+            Backend::Rust => return Err(Error::new(ErrorKind::BackendUnsupportedError)),
+        };
+        #[cfg(feature = "binding")]
+        let hash_raw = if hasher.config.binding() {
+            let (key_id, secret_key) = hasher.binding_key()?;
+            let data = binding::bind(
+                hash_raw.raw_salt_bytes(),
+                key_id,
+                secret_key,
+                hasher.additional_data.as_ref(),
+            );
+            hash_raw.with_data(Some(data))
+        } else {
+            hash_raw
+        };
+        Ok(hash_raw)
+    }
+    /// Hashes the normalized form of the password in place of the password if a normalization
+    /// is configured, zeroing out the normalized copy afterwards
+    fn hash_raw_normalized(&mut self) -> Result<HashRaw, Error> {
+        #[cfg(feature = "normalization")]
+        {
+            if let Some(normalization) = self.config.normalization() {
+                let normalized = match self.password {
+                    Some(ref password) => normalization.normalize(password.as_bytes())?,
+                    None => return Err(Error::new(ErrorKind::PasswordMissingError)),
+                };
+                let password = self.password.replace(normalized.into());
+                let hash_raw = self.hash_raw_onion();
+                self.restore_password(password);
+                return hash_raw;
+            }
+        }
+        self.hash_raw_onion()
+    }
+    fn hash_raw_onion(&mut self) -> Result<HashRaw, Error> {
+        match self.config.inner_scheme() {
+            Some(inner_scheme) => self.hash_raw_inner(inner_scheme),
+            None => self.hash_raw_c(),
+        }
+    }
+    /// Hashes the legacy digest provided as the password in place of the password, recording
+    /// the inner scheme in the resulting [`HashRaw`](output/struct.HashRaw.html) and zeroing
+    /// out the digest afterwards
+    fn hash_raw_inner(&mut self, inner_scheme: InnerScheme) -> Result<HashRaw, Error> {
+        let (inner, digest) = match self.password {
+            Some(ref password) => Inner::from_digest(inner_scheme, password.as_bytes())?,
+            None => return Err(Error::new(ErrorKind::PasswordMissingError)),
+        };
+        let password = self.password.replace(digest.into());
+        let hash_raw = self.hash_raw_c();
+        self.restore_password(password);
+        Ok(hash_raw?.with_inner(Some(inner)))
+    }
+    /// Puts back a password that was temporarily replaced with a derived copy (e.g. its
+    /// normalized form or a legacy digest), zeroing out the copy
+    pub(crate) fn restore_password(&mut self, password: Option<Password<'a>>) {
+        if let Some(mut derived) = mem::replace(&mut self.password, password) {
+            if let Container::Owned(ref mut bytes) = derived.inner {
+                unsafe { ::std::ptr::write_bytes(bytes.as_mut_ptr(), 0, bytes.len()) };
+            }
+        }
+    }
+    /// Returns a copy of the [`Hasher`](struct.Hasher.html) without a password that borrows
+    /// the secret key instead of copying it
+    pub(crate) fn reborrow<'b>(&'b self) -> Hasher<'b> {
+        Hasher {
+            additional_data: self.additional_data.clone(),
+            config: self.config.clone(),
+            key_id: self.key_id.clone(),
+            password: None,
+            salt: self.salt.clone(),
+            secret_key: self.secret_key.as_ref().map(SecretKey::from),
+        }
+    }
     pub(crate) fn clear(&mut self) {
         if self.password.is_some() && self.config.password_clearing() {
             {
@@ -426,9 +722,102 @@ impl<'a> Hasher<'a> {
                 }
             }
             self.secret_key = None;
+            self.key_id = None;
         }
     }
-    pub(crate) fn validate(&self) -> Result<(), Error> {
+    /// Adds every problem with the configuration, additional data, salt and secret key to the
+    /// report, i.e. everything that can be checked before a password is provided
+    pub(crate) fn report(&self, report: &mut ValidationReport) {
+        self.config.report(report);
+        if let Some(ref additional_data) = self.additional_data {
+            report.check("additional_data", additional_data.validate());
+        }
+        report.check("salt", self.salt.validate());
+        match self.secret_key {
+            Some(ref secret_key) => {
+                report.check("secret_key", secret_key.validate());
+                if self.config.secret_key_clearing() && !secret_key.is_mutable() {
+                    report.push(
+                        Some("secret_key"),
+                        Error::new(ErrorKind::SecretKeyImmutableError),
+                    );
+                }
+            }
+            None => {
+                if !self.config.opt_out_of_secret_key() {
+                    report.push(
+                        Some("secret_key"),
+                        Error::new(ErrorKind::SecretKeyMissingError),
+                    );
+                }
+            }
+        }
+        #[cfg(feature = "binding")]
+        {
+            if self.config.binding() {
+                report.check("secret_key", self.binding_key().map(|_| ()));
+            }
+        }
+    }
+    /// Returns the key ID and secret key that hashes are bound to, or an error if either is
+    /// missing or if the secret key is too short, in which case binding is refused
+    #[cfg(feature = "binding")]
+    fn binding_key(&self) -> Result<(&str, &SecretKey<'a>), Error> {
+        match (self.key_id.as_ref(), self.secret_key.as_ref()) {
+            (Some(_), Some(secret_key)) if secret_key.len() < binding::SECRET_KEY_MIN_LEN => Err(
+                Error::new(ErrorKind::SecretKeyTooShortError).add_context(format!(
+                    "Binding a hash requires a secret key of at least {} bytes. Length: {}",
+                    binding::SECRET_KEY_MIN_LEN,
+                    secret_key.len(),
+                )),
+            ),
+            (Some(key_id), Some(secret_key)) => Ok((key_id, secret_key)),
+            _ => Err(Error::new(ErrorKind::SecretKeyMissingError).add_context(
+                "Binding a hash requires a secret key provided with its ID (see with_key_file)",
+            )),
+        }
+    }
+    /// Checks the password against the password policy and the breach lookup, if any. Only
+    /// done when hashing a password, not when verifying one, and not for legacy digests. If a
+    /// normalization is configured, the normalized password (i.e. the one that is hashed) is
+    /// checked, and the normalized copy is zeroed out afterwards
+    fn check_password(&self) -> Result<(), Error> {
+        let password = match self.password {
+            Some(ref password) if self.config.inner_scheme().is_none() => password,
+            _ => return Ok(()),
+        };
+        #[cfg(feature = "normalization")]
+        let normalized = match self.config.normalization() {
+            Some(normalization) => Some(normalization.normalize(password.as_bytes())?),
+            None => None,
+        };
+        #[cfg(not(feature = "normalization"))]
+        let normalized: Option<Vec<u8>> = None;
+        let normalized = scopeguard::guard(normalized, |normalized| {
+            if let Some(mut normalized) = normalized {
+                unsafe { ::std::ptr::write_bytes(normalized.as_mut_ptr(), 0, normalized.len()) };
+            }
+        });
+        let bytes = match *normalized {
+            Some(ref normalized) => &normalized[..],
+            None => password.as_bytes(),
+        };
+        if let Some(password_policy) = self.config.password_policy() {
+            let password =
+                ::std::str::from_utf8(bytes).map_err(|_| Error::new(ErrorKind::Utf8EncodeError))?;
+            password_policy.check(password)?;
+        }
+        #[cfg(feature = "breach")]
+        {
+            if let Some(breach_lookup) = self.config.breach_lookup() {
+                breach::check(breach_lookup, bytes)?;
+            }
+        }
+        Ok(())
+    }
+    /// Validates the configuration and all inputs, including the password, before hashing,
+    /// returning the first error
+    pub(crate) fn validate_inputs(&self) -> Result<(), Error> {
         self.config.validate()?;
         if let Some(ref additional_data) = self.additional_data {
             additional_data.validate()?;
@@ -456,6 +845,12 @@ impl<'a> Hasher<'a> {
                 }
             }
         }
+        #[cfg(feature = "binding")]
+        {
+            if self.config.binding() {
+                self.binding_key()?;
+            }
+        }
         Ok(())
     }
 }
@@ -464,6 +859,7 @@ impl<'a> Hasher<'a> {
 mod tests {
     use super::*;
     use config::{Variant, Version};
+    use Verifier;
 
     struct Test {
         variant: Variant,
@@ -615,6 +1011,283 @@ mod tests {
         let _ = hasher.with_password("P@ssw0rd").hash().unwrap();
     }
 
+    #[test]
+    fn test_hasher_needs_rehash() {
+        let mut hasher = Hasher::default();
+        hasher
+            .configure_iterations(8)
+            .configure_lanes(2)
+            .configure_memory_size(64)
+            .opt_out_of_secret_key(true);
+        let hash = hasher.with_password("P@ssw0rd").hash().unwrap();
+        assert!(!hasher.needs_rehash(&hash).unwrap());
+
+        hasher.configure_iterations(9);
+        assert!(hasher.needs_rehash(&hash).unwrap());
+        hasher.configure_iterations(8);
+
+        hasher.configure_variant(Variant::Argon2i);
+        assert!(hasher.needs_rehash(&hash).unwrap());
+        hasher.configure_variant(Variant::Argon2id);
+
+        hasher.with_salt(Salt::random(16));
+        assert!(hasher.needs_rehash(&hash).unwrap());
+
+        let err = hasher.needs_rehash("$argon2id$v=19$m=64").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::HashDecodeError);
+    }
+
+    #[test]
+    fn test_hasher_inner_scheme() {
+        let md5_digest = "161ebd7d45089b3446ee4e0d86dbcf92"; // md5("P@ssw0rd")
+        let mut hasher = Hasher::default();
+        hasher
+            .configure_inner_scheme(Some(InnerScheme::Md5))
+            .configure_iterations(8)
+            .configure_lanes(2)
+            .configure_memory_size(64)
+            .configure_threads(2)
+            .opt_out_of_secret_key(true);
+        let hash = hasher.with_password(md5_digest).hash().unwrap();
+        assert!(hash.starts_with("$argon2id$v=19$m=64,t=8,p=2,inner=md5$"));
+        let hash_raw = hash.parse::<HashRaw>().unwrap();
+        assert_eq!(hash_raw.inner(), Some(&Inner::Md5));
+        assert_eq!(hash_raw.to_string(), hash);
+
+        // The outer hash is an Argon2 hash of the legacy digest
+        let mut verifier = Verifier::default();
+        let hash2 = Hasher::default()
+            .configure_iterations(8)
+            .configure_lanes(2)
+            .configure_memory_size(64)
+            .configure_threads(2)
+            .opt_out_of_secret_key(true)
+            .with_password(md5_digest)
+            .with_salt(hash_raw.raw_salt_bytes())
+            .hash_raw()
+            .unwrap();
+        assert_eq!(hash_raw.raw_hash_bytes(), hash2.raw_hash_bytes());
+
+        assert!(!hasher.needs_rehash(&hash).unwrap());
+        hasher.configure_inner_scheme(None);
+        assert!(hasher.needs_rehash(&hash).unwrap());
+
+        let err = Hasher::default()
+            .configure_inner_scheme(Some(InnerScheme::Sha1))
+            .opt_out_of_secret_key(true)
+            .with_password(md5_digest)
+            .hash()
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InnerDigestInvalidError);
+
+        verifier.with_hash(&hash).with_password("P@ssw0rd");
+        if cfg!(feature = "legacy") {
+            assert!(verifier.verify().unwrap());
+            verifier.with_password("wrong");
+            assert!(!verifier.verify().unwrap());
+        } else {
+            let err = verifier.verify().unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::HashDecodeError);
+        }
+    }
+
+    #[cfg(feature = "breach")]
+    #[test]
+    fn test_hasher_breach_lookup() {
+        #[derive(Debug)]
+        struct Breached;
+
+        impl BreachLookup for Breached {
+            fn lookup(&self, sha1: &[u8; 20]) -> Result<u64, Error> {
+                // sha1("P@ssw0rd") starts with 21BD12
+                Ok(if sha1[..3] == [0x21, 0xbd, 0x12] {
+                    42
+                } else {
+                    0
+                })
+            }
+        }
+
+        let mut hasher = Hasher::default();
+        hasher
+            .configure_breach_lookup(Some(Arc::new(Breached)))
+            .configure_iterations(8)
+            .configure_lanes(2)
+            .configure_memory_size(64)
+            .configure_threads(2)
+            .opt_out_of_secret_key(true);
+        let err = hasher.with_password("P@ssw0rd").hash().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PasswordBreachedError(42));
+        assert!(hasher.with_password("correct horse battery").hash().is_ok());
+        #[cfg(feature = "normalization")]
+        {
+            // The normalized password is looked up, i.e. "P@ssw0rd" with a fullwidth "P"
+            assert!(hasher.with_password("\u{ff30}@ssw0rd").hash().is_ok());
+            hasher.configure_normalization(Some(Normalization::Nfkc));
+            let err = hasher.with_password("\u{ff30}@ssw0rd").hash().unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::PasswordBreachedError(42));
+            hasher.configure_normalization(None);
+        }
+        hasher.configure_breach_lookup(None);
+        assert!(hasher.with_password("P@ssw0rd").hash().is_ok());
+    }
+
+    #[test]
+    fn test_hasher_password_policy() {
+        use policy::PolicyViolations;
+
+        let mut policy = PasswordPolicy::default();
+        policy.configure_min_len(12);
+        let mut hasher = Hasher::default();
+        hasher
+            .configure_iterations(8)
+            .configure_lanes(2)
+            .configure_memory_size(64)
+            .configure_password_policy(Some(policy))
+            .configure_threads(2)
+            .opt_out_of_secret_key(true);
+        let err = hasher.with_password("P@ssw0rd").hash().unwrap_err();
+        assert_eq!(
+            err.kind(),
+            ErrorKind::PasswordPolicyError(PolicyViolations::TOO_SHORT)
+        );
+        assert!(hasher.with_password("correct horse battery").hash().is_ok());
+
+        // The policy does not apply to legacy digests
+        hasher.configure_inner_scheme(Some(InnerScheme::Md5));
+        assert!(hasher
+            .with_password("161ebd7d45089b3446ee4e0d86dbcf92")
+            .hash()
+            .is_ok());
+    }
+
+    #[cfg(feature = "settings")]
+    #[test]
+    fn test_hasher_settings() {
+        let settings = Settings::from_toml_str(
+            "hash_len = 16\niterations = 8\nlanes = 2\nmemory_size = 64\nthreads = 2\n\
+             salt_len = 16\nopt_out_of_secret_key = true\n",
+        )
+        .unwrap();
+        let mut hasher = Hasher::default();
+        let hash = hasher
+            .configure_settings(&settings)
+            .with_password("P@ssw0rd")
+            .hash()
+            .unwrap();
+        assert!(hash.starts_with("$argon2id$v=19$m=64,t=8,p=2$"));
+        assert!(!hasher.needs_rehash(&hash).unwrap());
+        assert_eq!(hasher.salt().len(), 16);
+
+        let mut verifier = Verifier::default();
+        let is_valid = verifier
+            .configure_settings(&settings)
+            .with_hash(&hash)
+            .with_password("P@ssw0rd")
+            .verify()
+            .unwrap();
+        assert!(is_valid);
+    }
+
+    #[test]
+    fn test_hasher_rng() {
+        use rand::rngs::StdRng;
+        use rand::{Error as RandError, RngCore, SeedableRng};
+
+        let hash = |rng: SharedRng| {
+            let mut hasher = Hasher::default();
+            hasher
+                .configure_iterations(8)
+                .configure_lanes(2)
+                .configure_memory_size(64)
+                .configure_rng(Some(rng))
+                .configure_threads(2)
+                .opt_out_of_secret_key(true)
+                .with_password("P@ssw0rd");
+            (hasher.hash().unwrap(), hasher.hash().unwrap())
+        };
+        let (hash1, hash2) = hash(SharedRng::new(StdRng::seed_from_u64(1)));
+        assert_ne!(hash1, hash2);
+        assert_eq!(
+            (hash1, hash2),
+            hash(SharedRng::new(StdRng::seed_from_u64(1)))
+        );
+
+        struct Failing;
+        impl ::rand::CryptoRng for Failing {}
+        impl RngCore for Failing {
+            fn next_u32(&mut self) -> u32 {
+                0
+            }
+            fn next_u64(&mut self) -> u64 {
+                0
+            }
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                self.try_fill_bytes(dest).unwrap()
+            }
+            fn try_fill_bytes(&mut self, _: &mut [u8]) -> Result<(), RandError> {
+                Err(RandError::new("no entropy in this sandbox"))
+            }
+        }
+        let err = Hasher::default()
+            .configure_rng(Some(SharedRng::new(Failing)))
+            .opt_out_of_secret_key(true)
+            .with_password("P@ssw0rd")
+            .hash()
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::RngError);
+    }
+
+    #[cfg(feature = "normalization")]
+    #[test]
+    fn test_hasher_normalization() {
+        let composed = "p\u{00E9}ssw\u{00F6}rd";
+        let decomposed = "pe\u{0301}sswo\u{0308}rd";
+        let mut hasher = Hasher::default();
+        hasher
+            .configure_iterations(8)
+            .configure_lanes(2)
+            .configure_memory_size(64)
+            .configure_normalization(Some(Normalization::Nfc))
+            .configure_threads(2)
+            .opt_out_of_secret_key(true);
+        let hash = hasher.with_password(decomposed).hash().unwrap();
+
+        let mut verifier = Verifier::default();
+        verifier.with_hash(&hash).with_password(decomposed);
+        assert!(!verifier.verify().unwrap());
+        verifier.configure_normalization(Some(Normalization::Nfc));
+        assert_eq!(verifier.config().normalization(), Some(Normalization::Nfc));
+        assert!(verifier.verify().unwrap());
+        verifier.with_password(composed);
+        assert!(verifier.verify().unwrap());
+
+        let err = hasher.with_password(&[0xff, 0xfe][..]).hash().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Utf8EncodeError);
+    }
+
+    #[cfg(feature = "legacy")]
+    #[test]
+    fn test_hasher_inner_scheme_bcrypt() {
+        // bcrypt("P@ssw0rd") with cost 4
+        let bcrypt_digest = "$2b$04$5LvzRHSQAsu3ifo92tiMsePX1uGi02L1JW7EXH4HQHt18Zh0I2Xg2";
+        let hash = Hasher::default()
+            .configure_inner_scheme(Some(InnerScheme::Bcrypt))
+            .configure_iterations(8)
+            .configure_memory_size(64)
+            .opt_out_of_secret_key(true)
+            .with_password(bcrypt_digest)
+            .hash()
+            .unwrap();
+        assert!(hash.contains(",inner=bcrypt-04-5LvzRHSQAsu3ifo92tiMse$"));
+
+        let mut verifier = Verifier::default();
+        verifier.with_hash(&hash).with_password("P@ssw0rd");
+        assert!(verifier.verify().unwrap());
+        verifier.with_password("wrong");
+        assert!(!verifier.verify().unwrap());
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn test_hasher_serialization() {
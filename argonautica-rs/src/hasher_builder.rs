@@ -0,0 +1,310 @@
+use std::sync::Arc;
+
+use futures_cpupool::CpuPool;
+
+#[cfg(feature = "breach")]
+use breach::BreachLookup;
+#[cfg(feature = "normalization")]
+use config::Normalization;
+#[cfg(feature = "settings")]
+use config::Settings;
+use config::{Backend, InnerScheme, SharedRng, Variant, Version};
+use input::{AdditionalData, Salt, SecretKey};
+use keys::KeyFile;
+use metrics::Metrics;
+use policy::PasswordPolicy;
+use {Error, Hasher};
+
+impl<'a> Default for HasherBuilder<'a> {
+    /// Same as the [`new`](struct.HasherBuilder.html#method.new) method
+    fn default() -> HasherBuilder<'a> {
+        HasherBuilder {
+            hasher: Hasher::default(),
+        }
+    }
+}
+
+/// Checked builder for [`Hasher`](struct.Hasher.html).
+///
+/// The `configure_*` methods of [`Hasher`](struct.Hasher.html) only log a warning when given
+/// an invalid value (e.g. a memory size that is not a power of two), and the error surfaces
+/// later, when you first hash. [`HasherBuilder`](struct.HasherBuilder.html) offers the same
+/// options without the warnings and checks all of them in
+/// [`build`](struct.HasherBuilder.html#method.build), which fails with an
+/// [`Error`](struct.Error.html) of kind
+/// [`ConfigurationError`](enum.ErrorKind.html#variant.ConfigurationError) listing every problem
+/// found (see [`Hasher::validate`](struct.Hasher.html#method.validate)).
+///
+/// ```
+/// extern crate argonautica;
+///
+/// use argonautica::{ErrorKind, HasherBuilder};
+///
+/// fn main() {
+///     let err = HasherBuilder::new()
+///         .configure_iterations(0)
+///         .configure_memory_size(1000)
+///         .opt_out_of_secret_key(true)
+///         .build()
+///         .unwrap_err();
+///     assert_eq!(err.kind(), ErrorKind::ConfigurationError);
+///
+///     let mut hasher = HasherBuilder::new()
+///         .configure_iterations(8)
+///         .configure_lanes(2)
+///         .configure_memory_size(64)
+///         .configure_threads(2)
+///         .with_secret_key("\
+///             secret key that you should really store in a .env file \
+///             instead of in code, but this is just an example\
+///         ")
+///         .build()
+///         .unwrap();
+///     let hash = hasher.with_password("P@ssw0rd").hash().unwrap();
+///     assert!(hash.starts_with("$argon2id$v=19$m=64,t=8,p=2$"));
+/// }
+/// ```
+#[derive(Debug)]
+pub struct HasherBuilder<'a> {
+    hasher: Hasher<'a>,
+}
+
+impl<'a> HasherBuilder<'a> {
+    /// Creates a new [`HasherBuilder`](struct.HasherBuilder.html) starting from the default
+    /// configuration described in [`Hasher::new`](struct.Hasher.html#method.new)
+    pub fn new() -> HasherBuilder<'a> {
+        HasherBuilder::default()
+    }
+    /// Checks the configuration, additional data, salt and secret key and returns the
+    /// [`Hasher`](struct.Hasher.html), or an [`Error`](struct.Error.html) of kind
+    /// [`ConfigurationError`](enum.ErrorKind.html#variant.ConfigurationError) listing every
+    /// problem found
+    pub fn build(self) -> Result<Hasher<'a>, Error> {
+        self.hasher.validate()?;
+        Ok(self.hasher)
+    }
+    /// See [`Hasher::configure_backend`](struct.Hasher.html#method.configure_backend)
+    pub fn configure_backend(mut self, backend: Backend) -> HasherBuilder<'a> {
+        self.hasher.config.backend = backend;
+        self
+    }
+    /// See [`Hasher::configure_binding`](struct.Hasher.html#method.configure_binding)
+    #[cfg(feature = "binding")]
+    pub fn configure_binding(mut self, boolean: bool) -> HasherBuilder<'a> {
+        self.hasher.config.binding = boolean;
+        self
+    }
+    /// See [`Hasher::configure_breach_lookup`](struct.Hasher.html#method.configure_breach_lookup)
+    #[cfg(feature = "breach")]
+    pub fn configure_breach_lookup(
+        mut self,
+        breach_lookup: Option<Arc<dyn BreachLookup>>,
+    ) -> HasherBuilder<'a> {
+        self.hasher.config.breach_lookup = breach_lookup;
+        self
+    }
+    /// See [`Hasher::configure_cpu_pool`](struct.Hasher.html#method.configure_cpu_pool)
+    pub fn configure_cpu_pool(mut self, cpu_pool: CpuPool) -> HasherBuilder<'a> {
+        self.hasher.config.cpu_pool = Some(cpu_pool);
+        self
+    }
+    /// See [`Hasher::configure_hash_len`](struct.Hasher.html#method.configure_hash_len)
+    pub fn configure_hash_len(mut self, hash_len: u32) -> HasherBuilder<'a> {
+        self.hasher.config.hash_len = hash_len;
+        self
+    }
+    /// See [`Hasher::configure_inner_scheme`](struct.Hasher.html#method.configure_inner_scheme)
+    pub fn configure_inner_scheme(
+        mut self,
+        inner_scheme: Option<InnerScheme>,
+    ) -> HasherBuilder<'a> {
+        self.hasher.config.inner_scheme = inner_scheme;
+        self
+    }
+    /// See [`Hasher::configure_iterations`](struct.Hasher.html#method.configure_iterations)
+    pub fn configure_iterations(mut self, iterations: u32) -> HasherBuilder<'a> {
+        self.hasher.config.iterations = iterations;
+        self
+    }
+    /// See [`Hasher::configure_lanes`](struct.Hasher.html#method.configure_lanes)
+    pub fn configure_lanes(mut self, lanes: u32) -> HasherBuilder<'a> {
+        self.hasher.config.lanes = lanes;
+        self
+    }
+    /// See [`Hasher::configure_memory_size`](struct.Hasher.html#method.configure_memory_size)
+    pub fn configure_memory_size(mut self, memory_size: u32) -> HasherBuilder<'a> {
+        self.hasher.config.memory_size = memory_size;
+        self
+    }
+    /// See [`Hasher::configure_metrics`](struct.Hasher.html#method.configure_metrics)
+    pub fn configure_metrics(mut self, metrics: Option<Arc<dyn Metrics>>) -> HasherBuilder<'a> {
+        self.hasher.config.metrics = metrics;
+        self
+    }
+    /// See [`Hasher::configure_normalization`](struct.Hasher.html#method.configure_normalization)
+    #[cfg(feature = "normalization")]
+    pub fn configure_normalization(
+        mut self,
+        normalization: Option<Normalization>,
+    ) -> HasherBuilder<'a> {
+        self.hasher.config.normalization = normalization;
+        self
+    }
+    /// See [`Hasher::configure_password_clearing`](struct.Hasher.html#method.configure_password_clearing)
+    pub fn configure_password_clearing(mut self, boolean: bool) -> HasherBuilder<'a> {
+        self.hasher.config.password_clearing = boolean;
+        self
+    }
+    /// See [`Hasher::configure_password_policy`](struct.Hasher.html#method.configure_password_policy)
+    pub fn configure_password_policy(
+        mut self,
+        password_policy: Option<PasswordPolicy>,
+    ) -> HasherBuilder<'a> {
+        self.hasher.config.password_policy = password_policy;
+        self
+    }
+    /// See [`Hasher::configure_rng`](struct.Hasher.html#method.configure_rng)
+    pub fn configure_rng(mut self, rng: Option<SharedRng>) -> HasherBuilder<'a> {
+        self.hasher.config.rng = rng;
+        self
+    }
+    /// See [`Hasher::configure_secret_key_clearing`](struct.Hasher.html#method.configure_secret_key_clearing)
+    pub fn configure_secret_key_clearing(mut self, boolean: bool) -> HasherBuilder<'a> {
+        self.hasher.config.secret_key_clearing = boolean;
+        self
+    }
+    /// See [`Hasher::configure_settings`](struct.Hasher.html#method.configure_settings)
+    #[cfg(feature = "settings")]
+    pub fn configure_settings(mut self, settings: &Settings) -> HasherBuilder<'a> {
+        self.hasher.configure_settings(settings);
+        self
+    }
+    /// See [`Hasher::configure_threads`](struct.Hasher.html#method.configure_threads)
+    pub fn configure_threads(mut self, threads: u32) -> HasherBuilder<'a> {
+        self.hasher.config.threads = threads;
+        self
+    }
+    /// See [`Hasher::configure_variant`](struct.Hasher.html#method.configure_variant)
+    pub fn configure_variant(mut self, variant: Variant) -> HasherBuilder<'a> {
+        self.hasher.config.variant = variant;
+        self
+    }
+    /// See [`Hasher::configure_version`](struct.Hasher.html#method.configure_version)
+    pub fn configure_version(mut self, version: Version) -> HasherBuilder<'a> {
+        self.hasher.config.version = version;
+        self
+    }
+    /// See [`Hasher::opt_out_of_secret_key`](struct.Hasher.html#method.opt_out_of_secret_key)
+    pub fn opt_out_of_secret_key(mut self, boolean: bool) -> HasherBuilder<'a> {
+        self.hasher.config.opt_out_of_secret_key = boolean;
+        self
+    }
+    /// See [`Hasher::with_additional_data`](struct.Hasher.html#method.with_additional_data)
+    pub fn with_additional_data<AD>(mut self, additional_data: AD) -> HasherBuilder<'a>
+    where
+        AD: Into<AdditionalData>,
+    {
+        self.hasher.with_additional_data(additional_data);
+        self
+    }
+    /// See [`Hasher::with_key_file`](struct.Hasher.html#method.with_key_file)
+    pub fn with_key_file(mut self, key_file: &'a KeyFile) -> HasherBuilder<'a> {
+        self.hasher.with_key_file(key_file);
+        self
+    }
+    /// See [`Hasher::with_salt`](struct.Hasher.html#method.with_salt)
+    pub fn with_salt<S>(mut self, salt: S) -> HasherBuilder<'a>
+    where
+        S: Into<Salt>,
+    {
+        self.hasher.with_salt(salt);
+        self
+    }
+    /// See [`Hasher::with_secret_key`](struct.Hasher.html#method.with_secret_key)
+    pub fn with_secret_key<SK>(mut self, secret_key: SK) -> HasherBuilder<'a>
+    where
+        SK: Into<SecretKey<'a>>,
+    {
+        self.hasher.with_secret_key(secret_key);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ErrorKind;
+
+    #[test]
+    fn test_hasher_builder_collects_every_error() {
+        let err = HasherBuilder::new()
+            .configure_hash_len(2)
+            .configure_iterations(0)
+            .configure_lanes(2)
+            .configure_memory_size(1000)
+            .configure_threads(0)
+            .with_salt("short")
+            .build()
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigurationError);
+        let display = err.to_string();
+        for expected in &[
+            "hash_len: ",
+            "iterations: ",
+            "memory_size: ",
+            "threads: ",
+            "salt: ",
+            "secret_key: ",
+        ] {
+            assert!(display.contains(expected), "{}", display);
+        }
+
+        let report = Hasher::default().validate().unwrap_err();
+        assert_eq!(report.len(), 1);
+        assert_eq!(
+            report.iter().next().unwrap().error().kind(),
+            ErrorKind::SecretKeyMissingError
+        );
+    }
+
+    #[test]
+    fn test_hasher_builder_build() {
+        let mut hasher = HasherBuilder::new()
+            .configure_hash_len(16)
+            .configure_iterations(8)
+            .configure_lanes(2)
+            .configure_memory_size(64)
+            .configure_threads(2)
+            .configure_variant(Variant::Argon2i)
+            .opt_out_of_secret_key(true)
+            .with_salt("somesalt")
+            .build()
+            .unwrap();
+        assert!(hasher.validate().is_ok());
+        let hash = hasher.with_password("P@ssw0rd").hash().unwrap();
+
+        let mut expected = Hasher::default();
+        expected
+            .configure_hash_len(16)
+            .configure_iterations(8)
+            .configure_lanes(2)
+            .configure_memory_size(64)
+            .configure_threads(2)
+            .configure_variant(Variant::Argon2i)
+            .opt_out_of_secret_key(true)
+            .with_salt("somesalt");
+        assert_eq!(hash, expected.with_password("P@ssw0rd").hash().unwrap());
+    }
+
+    #[test]
+    fn test_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<HasherBuilder>();
+    }
+
+    #[test]
+    fn test_sync() {
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<HasherBuilder>();
+    }
+}
@@ -19,9 +19,12 @@ mod container;
 mod password;
 mod salt;
 mod secret_key;
+mod secret_key_loader;
 
 pub use self::additional_data::AdditionalData;
 pub(crate) use self::container::Container;
 pub use self::password::Password;
 pub use self::salt::Salt;
 pub use self::secret_key::SecretKey;
+pub(crate) use self::secret_key_loader::{check_permissions, zero};
+pub use self::secret_key_loader::{SecretKeyEncoding, SecretKeyLoader};
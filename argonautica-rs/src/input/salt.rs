@@ -1,5 +1,5 @@
 use rand::rngs::OsRng;
-use rand::RngCore;
+use rand::{CryptoRng, RngCore};
 
 use {Error, ErrorKind};
 
@@ -120,13 +120,23 @@ impl Salt {
     /// If you have a <u>random</u> `Salt`, this method will generate new random bytes of the
     /// length of your `Salt`. If you have a <u>deterministic</u> `Salt`, this method does nothing
     pub fn update(&mut self) -> Result<(), Error> {
-        match self.0 {
-            Kind::Random(ref mut bytes) => {
-                OsRng
-                    .try_fill_bytes(bytes)
-                    .map_err(|_| Error::new(ErrorKind::OsRngError))?;
-            }
-            _ => (),
+        if let Kind::Random(ref mut bytes) = self.0 {
+            OsRng
+                .try_fill_bytes(bytes)
+                .map_err(|_| Error::new(ErrorKind::OsRngError))?;
+        }
+        Ok(())
+    }
+    /// Same as [`update`](struct.Salt.html#method.update) except the new random bytes are
+    /// generated using the provided cryptographically-secure random number generator instead
+    /// of `OsRng`
+    pub fn update_with_rng<R>(&mut self, rng: &mut R) -> Result<(), Error>
+    where
+        R: CryptoRng + RngCore + ?Sized,
+    {
+        if let Kind::Random(ref mut bytes) = self.0 {
+            rng.try_fill_bytes(bytes)
+                .map_err(|e| Error::new(ErrorKind::RngError).add_context(format!("{}", e)))?;
         }
         Ok(())
     }
@@ -153,6 +163,27 @@ impl Salt {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_update_with_rng() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut salt1 = Salt::random(16);
+        let mut salt2 = Salt::random(16);
+        salt1
+            .update_with_rng(&mut StdRng::seed_from_u64(1))
+            .unwrap();
+        salt2
+            .update_with_rng(&mut StdRng::seed_from_u64(1))
+            .unwrap();
+        assert_eq!(salt1, salt2);
+        assert_ne!(salt1.as_bytes(), &[0u8; 16]);
+
+        let mut salt = Salt::from("somesalt");
+        salt.update_with_rng(&mut StdRng::seed_from_u64(1)).unwrap();
+        assert_eq!(salt.as_bytes(), b"somesalt");
+    }
+
     #[test]
     fn test_send() {
         fn assert_send<T: Send>() {}
@@ -1,8 +1,9 @@
 use std::fmt;
+use std::path::Path;
 
 use base64;
 
-use input::Container;
+use input::{Container, SecretKeyEncoding, SecretKeyLoader};
 use {Error, ErrorKind};
 
 impl<'a> From<&'a str> for SecretKey<'a> {
@@ -137,7 +138,8 @@ impl<'a> SecretKey<'a> {
         S: AsRef<str>,
     {
         let bytes = base64::decode_config(s.as_ref(), base64::STANDARD).map_err(|_| {
-            Error::new(ErrorKind::Base64DecodeError).add_context(format!("&str: {}", s.as_ref()))
+            Error::new(ErrorKind::Base64DecodeError)
+                .add_context(format!("Length: {}", s.as_ref().len()))
         })?;
         Ok(SecretKey {
             inner: Container::Owned(bytes),
@@ -154,12 +156,47 @@ impl<'a> SecretKey<'a> {
         S: AsRef<str>,
     {
         let bytes = base64::decode_config(s.as_ref(), config).map_err(|_| {
-            Error::new(ErrorKind::Base64DecodeError).add_context(format!("&str: {}", s.as_ref()))
+            Error::new(ErrorKind::Base64DecodeError)
+                .add_context(format!("Length: {}", s.as_ref().len()))
         })?;
         Ok(SecretKey {
             inner: Container::Owned(bytes),
         })
     }
+    /// Constructs a [`SecretKey`](struct.SecretKey.html) from the base64-encoded value of the
+    /// environment variable with the provided name, using a default
+    /// [`SecretKeyLoader`](struct.SecretKeyLoader.html) (i.e. the key must be at least 16 bytes
+    /// long). Use a [`SecretKeyLoader`](struct.SecretKeyLoader.html) directly for other
+    /// encodings or checks
+    pub fn from_env<S>(name: S) -> Result<SecretKey<'static>, Error>
+    where
+        S: AsRef<str>,
+    {
+        SecretKeyLoader::default().load_env(name)
+    }
+    /// Constructs a [`SecretKey`](struct.SecretKey.html) from the base64-encoded contents of
+    /// the file at the provided path, using a default
+    /// [`SecretKeyLoader`](struct.SecretKeyLoader.html) (i.e. a trailing newline is ignored,
+    /// the key must be at least 16 bytes long and, on Unix, the file must not be accessible by
+    /// users other than its owner). Use a [`SecretKeyLoader`](struct.SecretKeyLoader.html)
+    /// directly for other encodings or checks
+    pub fn from_file<P>(path: P) -> Result<SecretKey<'static>, Error>
+    where
+        P: AsRef<Path>,
+    {
+        SecretKeyLoader::default().load_file(path)
+    }
+    /// Constructs a [`SecretKey`](struct.SecretKey.html) from a hex-encoded `&str` (upper or
+    /// lower case), using a default [`SecretKeyLoader`](struct.SecretKeyLoader.html) (i.e. the
+    /// key must be at least 16 bytes long)
+    pub fn from_hex<S>(s: S) -> Result<SecretKey<'static>, Error>
+    where
+        S: AsRef<str>,
+    {
+        SecretKeyLoader::default()
+            .configure_encoding(SecretKeyEncoding::Hex)
+            .load_str(s)
+    }
     /// Read-only access to the underlying byte buffer
     pub fn as_bytes(&self) -> &[u8] {
         match self.inner {
@@ -203,6 +240,13 @@ impl<'a> SecretKey<'a> {
     pub fn to_base64_encoded_config(&self, config: base64::Config) -> String {
         base64::encode_config(self.as_bytes(), config)
     }
+    /// Returns the underlying byte buffer as a lowercase hex-encoded `String`
+    pub fn to_hex(&self) -> String {
+        self.as_bytes()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
     /// Read-only access to the underlying byte buffer as a `&str` if its bytes are valid utf-8
     pub fn to_str(&self) -> Result<&str, Error> {
         let s = ::std::str::from_utf8(self.as_bytes())
@@ -0,0 +1,348 @@
+use std::env;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use base64;
+
+use config::DEFAULT_SECRET_KEY_MIN_LEN;
+use input::{Container, SecretKey};
+use {Error, ErrorKind};
+
+impl Default for SecretKeyEncoding {
+    /// Returns [`SecretKeyEncoding::Base64`](enum.SecretKeyEncoding.html#variant.Base64)
+    fn default() -> SecretKeyEncoding {
+        SecretKeyEncoding::Base64
+    }
+}
+
+/// Enum representing the ways a secret key can be encoded in an environment variable or a file
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum SecretKeyEncoding {
+    /// [Standard base64 encoding](https://docs.rs/base64/0.10.1/base64/constant.STANDARD.html)
+    Base64,
+
+    /// Hexadecimal encoding (upper or lower case)
+    Hex,
+
+    /// No encoding, i.e. the raw bytes of the key
+    Raw,
+}
+
+impl Default for SecretKeyLoader {
+    /// Same as the [`new`](struct.SecretKeyLoader.html#method.new) method
+    fn default() -> SecretKeyLoader {
+        SecretKeyLoader {
+            check_permissions: true,
+            encoding: SecretKeyEncoding::default(),
+            min_len: DEFAULT_SECRET_KEY_MIN_LEN,
+            strip_trailing_newline: true,
+        }
+    }
+}
+
+/// Struct that loads a [`SecretKey`](struct.SecretKey.html) from an environment variable, a
+/// file or a string, checking it along the way.
+///
+/// Keys shorter than the configured minimum length are rejected with an
+/// [`Error`](../struct.Error.html) of kind
+/// [`SecretKeyTooShortError`](../enum.ErrorKind.html#variant.SecretKeyTooShortError), and
+/// (on Unix) key files that can be accessed by users other than their owner are rejected with
+/// an [`Error`](../struct.Error.html) of kind
+/// [`SecretKeyPermissionsError`](../enum.ErrorKind.html#variant.SecretKeyPermissionsError). The
+/// key itself never appears in the context of any error, and the intermediate buffers it was
+/// read into are zeroed out before they are dropped
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SecretKeyLoader {
+    check_permissions: bool,
+    encoding: SecretKeyEncoding,
+    min_len: u32,
+    strip_trailing_newline: bool,
+}
+
+impl SecretKeyLoader {
+    /// Creates a new [`SecretKeyLoader`](struct.SecretKeyLoader.html) with the following
+    /// configuration:
+    /// * `check_permissions`: `true`
+    /// * `encoding`: [`SecretKeyEncoding::Base64`](enum.SecretKeyEncoding.html#variant.Base64)
+    /// * `min_len`: `16` bytes
+    /// * `strip_trailing_newline`: `true`
+    pub fn new() -> SecretKeyLoader {
+        SecretKeyLoader::default()
+    }
+    /// Allows you to configure whether key files must only be accessible by their owner (i.e.
+    /// have no group or other permission bits set). Only has an effect on Unix. The default
+    /// is `true`
+    pub fn configure_check_permissions(&mut self, boolean: bool) -> &mut SecretKeyLoader {
+        self.check_permissions = boolean;
+        self
+    }
+    /// Allows you to configure how the key is encoded. The default is
+    /// [`SecretKeyEncoding::Base64`](enum.SecretKeyEncoding.html#variant.Base64)
+    pub fn configure_encoding(&mut self, encoding: SecretKeyEncoding) -> &mut SecretKeyLoader {
+        self.encoding = encoding;
+        self
+    }
+    /// Allows you to configure the minimum length (in bytes, after decoding) of the key. The
+    /// default is `16`, i.e. 128 bits
+    pub fn configure_min_len(&mut self, min_len: u32) -> &mut SecretKeyLoader {
+        self.min_len = min_len;
+        self
+    }
+    /// Allows you to configure whether a single trailing newline (`\n` or `\r\n`), as added by
+    /// most editors and by `echo`, is removed before the key is decoded. Set this to `false`
+    /// if you use [`SecretKeyEncoding::Raw`](enum.SecretKeyEncoding.html#variant.Raw) and
+    /// your key may legitimately end in a newline byte. The default is `true`
+    pub fn configure_strip_trailing_newline(&mut self, boolean: bool) -> &mut SecretKeyLoader {
+        self.strip_trailing_newline = boolean;
+        self
+    }
+    /// Loads the key from the environment variable with the provided name
+    pub fn load_env<S>(&self, name: S) -> Result<SecretKey<'static>, Error>
+    where
+        S: AsRef<str>,
+    {
+        let name = name.as_ref();
+        let value = env::var_os(name).ok_or_else(|| {
+            Error::new(ErrorKind::EnvVarError).add_context(format!("{} is not set", name))
+        })?;
+        let mut bytes = match value.into_string() {
+            Ok(value) => value.into_bytes(),
+            Err(_) => {
+                return Err(Error::new(ErrorKind::EnvVarError)
+                    .add_context(format!("{} is not valid unicode", name)))
+            }
+        };
+        let secret_key = self.decode(&bytes);
+        zero(&mut bytes);
+        secret_key
+    }
+    /// Loads the key from the file at the provided path
+    pub fn load_file<P>(&self, path: P) -> Result<SecretKey<'static>, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let io_error = |e: ::std::io::Error| {
+            Error::new(ErrorKind::IoError).add_context(format!("{}: {}", path.display(), e))
+        };
+        let mut file = File::open(path).map_err(io_error)?;
+        if self.check_permissions {
+            check_permissions(path, &file)?;
+        }
+        let mut bytes = Vec::new();
+        if let Err(e) = file.read_to_end(&mut bytes) {
+            zero(&mut bytes);
+            return Err(io_error(e));
+        }
+        let secret_key = self.decode(&bytes);
+        zero(&mut bytes);
+        secret_key
+    }
+    /// Loads the key from the provided string
+    pub fn load_str<S>(&self, s: S) -> Result<SecretKey<'static>, Error>
+    where
+        S: AsRef<str>,
+    {
+        self.decode(s.as_ref().as_bytes())
+    }
+    #[allow(missing_docs)]
+    pub fn check_permissions(&self) -> bool {
+        self.check_permissions
+    }
+    #[allow(missing_docs)]
+    pub fn encoding(&self) -> SecretKeyEncoding {
+        self.encoding
+    }
+    #[allow(missing_docs)]
+    pub fn min_len(&self) -> u32 {
+        self.min_len
+    }
+    #[allow(missing_docs)]
+    pub fn strip_trailing_newline(&self) -> bool {
+        self.strip_trailing_newline
+    }
+}
+
+impl SecretKeyLoader {
+    fn decode(&self, mut bytes: &[u8]) -> Result<SecretKey<'static>, Error> {
+        if self.strip_trailing_newline {
+            if bytes.ends_with(b"\r\n") {
+                bytes = &bytes[..bytes.len() - 2];
+            } else if bytes.ends_with(b"\n") {
+                bytes = &bytes[..bytes.len() - 1];
+            }
+        }
+        let mut decoded = match self.encoding {
+            SecretKeyEncoding::Base64 => base64::decode_config(bytes, base64::STANDARD)
+                .map_err(|_| Error::new(ErrorKind::Base64DecodeError))?,
+            SecretKeyEncoding::Hex => decode_hex(bytes)?,
+            SecretKeyEncoding::Raw => bytes.to_vec(),
+        };
+        if decoded.len() < self.min_len as usize {
+            let len = decoded.len();
+            zero(&mut decoded);
+            return Err(Error::new(ErrorKind::SecretKeyTooShortError)
+                .add_context(format!("Length: {}, minimum: {}", len, self.min_len)));
+        }
+        Ok(SecretKey {
+            inner: Container::Owned(decoded),
+        })
+    }
+}
+
+fn decode_hex(bytes: &[u8]) -> Result<Vec<u8>, Error> {
+    fn nibble(c: u8) -> Option<u8> {
+        match c {
+            b'0'..=b'9' => Some(c - b'0'),
+            b'a'..=b'f' => Some(c - b'a' + 10),
+            b'A'..=b'F' => Some(c - b'A' + 10),
+            _ => None,
+        }
+    }
+    let pairs = bytes.chunks_exact(2);
+    if !pairs.remainder().is_empty() {
+        return Err(Error::new(ErrorKind::HexDecodeError).add_context("Odd number of digits"));
+    }
+    let mut decoded = Vec::with_capacity(pairs.len());
+    for pair in pairs {
+        match (nibble(pair[0]), nibble(pair[1])) {
+            (Some(high), Some(low)) => decoded.push(high << 4 | low),
+            _ => {
+                zero(&mut decoded);
+                return Err(Error::new(ErrorKind::HexDecodeError));
+            }
+        }
+    }
+    Ok(decoded)
+}
+
+#[cfg(unix)]
+pub(crate) fn check_permissions(path: &Path, file: &File) -> Result<(), Error> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = file
+        .metadata()
+        .map_err(|e| {
+            Error::new(ErrorKind::IoError).add_context(format!("{}: {}", path.display(), e))
+        })?
+        .permissions()
+        .mode();
+    if mode & 0o077 != 0 {
+        return Err(
+            Error::new(ErrorKind::SecretKeyPermissionsError).add_context(format!(
+                "{}: mode {:o}",
+                path.display(),
+                mode & 0o777
+            )),
+        );
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub(crate) fn check_permissions(_path: &Path, _file: &File) -> Result<(), Error> {
+    Ok(())
+}
+
+pub(crate) fn zero(bytes: &mut Vec<u8>) {
+    unsafe { ::std::ptr::write_bytes(bytes.as_mut_ptr(), 0, bytes.len()) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::Write;
+
+    const KEY: &str = "7h1s-1s-4-s3cr3t-k3y!";
+
+    fn temp_path(name: &str) -> ::std::path::PathBuf {
+        ::std::env::temp_dir().join(format!("argonautica-{}-{}", name, ::std::process::id()))
+    }
+
+    #[test]
+    fn test_secret_key_loader_str() {
+        let base64 = base64::encode(KEY);
+        let secret_key = SecretKey::from_base64_encoded(&base64).unwrap();
+        assert_eq!(secret_key.as_bytes(), KEY.as_bytes());
+        let secret_key = SecretKeyLoader::default()
+            .load_str(format!("{}\n", base64))
+            .unwrap();
+        assert_eq!(secret_key.as_bytes(), KEY.as_bytes());
+
+        let hex = KEY
+            .bytes()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+        let secret_key = SecretKey::from_hex(&hex).unwrap();
+        assert_eq!(secret_key.as_bytes(), KEY.as_bytes());
+        let secret_key = SecretKey::from_hex(hex.to_uppercase()).unwrap();
+        assert_eq!(secret_key.as_bytes(), KEY.as_bytes());
+
+        let err = SecretKey::from_hex("0g").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::HexDecodeError);
+        let err = SecretKey::from_hex(&hex[1..]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::HexDecodeError);
+        let err = SecretKey::from_hex("00112233").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::SecretKeyTooShortError);
+
+        let secret_key = SecretKeyLoader::default()
+            .configure_encoding(SecretKeyEncoding::Raw)
+            .configure_min_len(4)
+            .load_str("abcd")
+            .unwrap();
+        assert_eq!(secret_key.as_bytes(), b"abcd");
+    }
+
+    #[test]
+    fn test_secret_key_loader_env() {
+        let name = format!("ARGONAUTICA_TEST_SECRET_KEY_{}", ::std::process::id());
+        let err = SecretKey::from_env(&name).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::EnvVarError);
+
+        env::set_var(&name, base64::encode(KEY));
+        let secret_key = SecretKey::from_env(&name).unwrap();
+        assert_eq!(secret_key.as_bytes(), KEY.as_bytes());
+
+        env::set_var(&name, "not base64 but secret!");
+        let err = SecretKey::from_env(&name).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Base64DecodeError);
+        assert!(!err.to_string().contains("secret!"));
+        env::remove_var(&name);
+    }
+
+    #[test]
+    fn test_secret_key_loader_file() {
+        let path = temp_path("secret-key.txt");
+        {
+            let mut file = File::create(&path).unwrap();
+            writeln!(file, "{}", base64::encode(KEY)).unwrap();
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+            let err = SecretKey::from_file(&path).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::SecretKeyPermissionsError);
+            assert!(!err.to_string().contains(&base64::encode(KEY)));
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+        }
+        let secret_key = SecretKey::from_file(&path).unwrap();
+        assert_eq!(secret_key.as_bytes(), KEY.as_bytes());
+
+        let secret_key = SecretKeyLoader::default()
+            .configure_encoding(SecretKeyEncoding::Raw)
+            .configure_strip_trailing_newline(false)
+            .load_file(&path)
+            .unwrap();
+        assert_eq!(secret_key.as_bytes().last(), Some(&b'\n'));
+        fs::remove_file(&path).unwrap();
+
+        let err = SecretKey::from_file(&path).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::IoError);
+    }
+}
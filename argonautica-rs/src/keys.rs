@@ -0,0 +1,409 @@
+//! Generation, storage and rotation of secret keys (a.k.a. peppers).
+//!
+//! A [`KeyFile`](struct.KeyFile.html) is a secret key together with a key ID and the time it
+//! was created, stored in a small, versioned text file:
+//!
+//! ```text
+//! argonautica-key-file v1
+//! id: 2019-01
+//! created: 1546300800
+//! key: t9nGEsDxjWtJYdYeExdB6/HU0vg+rT6czv6HSjVjZng=
+//! ```
+//!
+//! Generate a new key file whenever you rotate your secret key, and load all of them into a
+//! [`KeySet`](struct.KeySet.html): hash new passwords with its
+//! [`current`](struct.KeySet.html#method.current) (i.e. newest) key and keep the older keys
+//! around, looked up by ID, to verify existing hashes. The `generate_secret_key` example is a
+//! command-line tool for the same tasks.
+//!
+//! ```
+//! extern crate argonautica;
+//!
+//! use argonautica::keys::{KeyFile, KeySet};
+//!
+//! fn main() {
+//!     let dir = std::env::temp_dir().join(format!("argonautica-keys-doc-{}", std::process::id()));
+//!     std::fs::create_dir_all(&dir).unwrap();
+//!
+//!     KeyFile::generate("2019-01", 32).unwrap().write(dir.join("2019-01.key")).unwrap();
+//!
+//!     let key_set = KeySet::load_dir(&dir).unwrap();
+//!     let current = key_set.current().unwrap();
+//!     assert_eq!(current.id(), "2019-01");
+//!     assert_eq!(current.secret_key().len(), 32);
+//!
+//!     std::fs::remove_dir_all(&dir).unwrap();
+//! }
+//! ```
+use std::ffi::OsStr;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::slice;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use config::DEFAULT_SECRET_KEY_MIN_LEN;
+use input::{check_permissions, zero, SecretKey};
+use utils::generate_random_bytes;
+use {Error, ErrorKind};
+
+const HEADER: &str = "argonautica-key-file v1";
+
+/// Extension of the key files [`KeySet::load_dir`](struct.KeySet.html#method.load_dir) loads
+pub const KEY_FILE_EXTENSION: &str = "key";
+
+/// Struct representing a secret key together with its key ID and creation time, as stored in
+/// a key file
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub struct KeyFile {
+    created: u64,
+    id: String,
+    secret_key: SecretKey<'static>,
+}
+
+impl Clone for KeyFile {
+    fn clone(&self) -> KeyFile {
+        KeyFile {
+            created: self.created,
+            id: self.id.clone(),
+            secret_key: self.secret_key.to_owned(),
+        }
+    }
+}
+
+impl KeyFile {
+    /// Creates a new [`KeyFile`](struct.KeyFile.html) from an existing secret key. The key ID
+    /// must be non-empty and consist only of ASCII letters, digits, `-`, `_` and `.`; `created`
+    /// is the creation time in seconds since the Unix epoch
+    pub fn new<S>(id: S, secret_key: SecretKey, created: u64) -> Result<KeyFile, Error>
+    where
+        S: Into<String>,
+    {
+        let id = id.into();
+        validate_id(&id)?;
+        if secret_key.len() < DEFAULT_SECRET_KEY_MIN_LEN as usize {
+            return Err(
+                Error::new(ErrorKind::SecretKeyTooShortError).add_context(format!(
+                    "Length: {}, minimum: {}",
+                    secret_key.len(),
+                    DEFAULT_SECRET_KEY_MIN_LEN
+                )),
+            );
+        }
+        Ok(KeyFile {
+            created,
+            id,
+            secret_key: secret_key.to_owned(),
+        })
+    }
+    /// Generates a new [`KeyFile`](struct.KeyFile.html) with a cryptographically-secure, random
+    /// secret key of the provided length (in bytes, at least `16`) drawn from the operating
+    /// system's random number generator, created now
+    pub fn generate<S>(id: S, len: u32) -> Result<KeyFile, Error>
+    where
+        S: Into<String>,
+    {
+        let created = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+        let bytes = generate_random_bytes(len)?;
+        KeyFile::new(id, bytes.into(), created)
+    }
+    /// Reads a key file. On Unix, the file must not be accessible by users other than its
+    /// owner
+    pub fn read<P>(path: P) -> Result<KeyFile, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let mut file = File::open(path).map_err(|e| io_error(path, e))?;
+        check_permissions(path, &file)?;
+        let mut bytes = Vec::new();
+        let result = file
+            .read_to_end(&mut bytes)
+            .map_err(|e| io_error(path, e))
+            .and_then(|_| KeyFile::decode(&path.display().to_string(), &bytes));
+        zero(&mut bytes);
+        result
+    }
+    /// Writes the key file to the provided path, which must not exist yet. On Unix, the file
+    /// is created with permissions `0600`
+    pub fn write<P>(&self, path: P) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(path).map_err(|e| io_error(path, e))?;
+        let mut encoded = self.encode().into_bytes();
+        let result = file.write_all(&encoded).map_err(|e| io_error(path, e));
+        zero(&mut encoded);
+        result
+    }
+    /// Returns the creation time of the key in seconds since the Unix epoch
+    pub fn created(&self) -> u64 {
+        self.created
+    }
+    /// Read-only access to the key ID
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+    /// Read-only access to the secret key
+    pub fn secret_key(&self) -> &SecretKey<'static> {
+        &self.secret_key
+    }
+}
+
+impl KeyFile {
+    /// Parses the contents of a key file; `source` (e.g. the path) is only used in errors
+    fn decode(source: &str, bytes: &[u8]) -> Result<KeyFile, Error> {
+        let invalid = |msg: &str| {
+            Error::new(ErrorKind::KeyFileInvalidError).add_context(format!("{}: {}", source, msg))
+        };
+        let s = ::std::str::from_utf8(bytes).map_err(|_| invalid("Not valid utf-8"))?;
+        let mut lines = s.lines();
+        match lines.next() {
+            Some(line) if line.trim_end() == HEADER => (),
+            Some(line) if line.starts_with("argonautica-key-file ") => {
+                return Err(invalid(&format!("Unsupported version: {}", &line[21..])));
+            }
+            _ => return Err(invalid("Missing header")),
+        }
+        let (mut created, mut id, mut secret_key) = (None, None, None);
+        for line in lines {
+            let line = line.trim_end();
+            if line.is_empty() {
+                continue;
+            }
+            let mut parts = line.splitn(2, ": ");
+            match (parts.next(), parts.next()) {
+                (Some("created"), Some(value)) => {
+                    created = Some(
+                        value
+                            .parse::<u64>()
+                            .map_err(|_| invalid("Invalid created"))?,
+                    );
+                }
+                (Some("id"), Some(value)) => id = Some(value.to_string()),
+                (Some("key"), Some(value)) => {
+                    secret_key = Some(
+                        SecretKey::from_base64_encoded(value)
+                            .map_err(|_| invalid("Invalid key"))?,
+                    );
+                }
+                (Some(field), _) => {
+                    return Err(invalid(&format!("Unknown field: {}", field)));
+                }
+                (None, _) => unreachable!(),
+            }
+        }
+        match (id, secret_key, created) {
+            (Some(id), Some(secret_key), Some(created)) => KeyFile::new(id, secret_key, created),
+            _ => Err(invalid("Missing field")),
+        }
+    }
+    fn encode(&self) -> String {
+        format!(
+            "{}\nid: {}\ncreated: {}\nkey: {}\n",
+            HEADER,
+            self.id,
+            self.created,
+            self.secret_key.to_base64_encoded()
+        )
+    }
+}
+
+/// Struct representing a set of [`KeyFile`](struct.KeyFile.html)s for key rotation, i.e. the
+/// current key and all previous keys that may still be needed to verify existing hashes
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct KeySet {
+    keys: Vec<KeyFile>,
+}
+
+impl KeySet {
+    /// Creates a new, empty [`KeySet`](struct.KeySet.html)
+    pub fn new() -> KeySet {
+        KeySet::default()
+    }
+    /// Reads the key files at the provided paths into a [`KeySet`](struct.KeySet.html)
+    pub fn load<I, P>(paths: I) -> Result<KeySet, Error>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let mut key_set = KeySet::new();
+        for path in paths {
+            key_set.insert(KeyFile::read(path)?)?;
+        }
+        Ok(key_set)
+    }
+    /// Reads every key file (i.e. every file with the extension `.key`) in the provided
+    /// directory into a [`KeySet`](struct.KeySet.html)
+    pub fn load_dir<P>(dir: P) -> Result<KeySet, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let dir = dir.as_ref();
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(dir).map_err(|e| io_error(dir, e))? {
+            let path = entry.map_err(|e| io_error(dir, e))?.path();
+            if path.is_file() && path.extension() == Some(OsStr::new(KEY_FILE_EXTENSION)) {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        KeySet::load(paths)
+    }
+    /// Adds a key to the set. Returns an error if the set already contains a key with the same
+    /// key ID
+    pub fn insert(&mut self, key: KeyFile) -> Result<&mut KeySet, Error> {
+        if self.get(key.id()).is_some() {
+            return Err(Error::new(ErrorKind::KeyFileInvalidError)
+                .add_context(format!("Duplicate key id: {}", key.id())));
+        }
+        self.keys.push(key);
+        Ok(self)
+    }
+    /// Returns the current key, i.e. the most recently created one (or, if several keys were
+    /// created at the same time, the one added last), if any
+    pub fn current(&self) -> Option<&KeyFile> {
+        self.keys
+            .iter()
+            .enumerate()
+            .max_by_key(|&(i, key)| (key.created(), i))
+            .map(|(_, key)| key)
+    }
+    /// Returns the key with the provided key ID, if any
+    pub fn get(&self, id: &str) -> Option<&KeyFile> {
+        self.keys.iter().find(|key| key.id() == id)
+    }
+    /// Returns `true` if the set contains no keys
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+    /// Iterates over the keys in the order they were added
+    pub fn iter(&self) -> slice::Iter<'_, KeyFile> {
+        self.keys.iter()
+    }
+    /// Returns the number of keys in the set
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+}
+
+fn io_error(path: &Path, e: ::std::io::Error) -> Error {
+    Error::new(ErrorKind::IoError).add_context(format!("{}: {}", path.display(), e))
+}
+
+fn validate_id(id: &str) -> Result<(), Error> {
+    let valid = !id.is_empty()
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_' || b == b'.');
+    if !valid {
+        return Err(Error::new(ErrorKind::KeyFileInvalidError)
+            .add_context(format!("Invalid key id: {:?}", id)));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> ::std::path::PathBuf {
+        let dir =
+            ::std::env::temp_dir().join(format!("argonautica-{}-{}", name, ::std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_key_file_round_trip() {
+        let dir = temp_dir("key-file");
+        let key = KeyFile::generate("k1", 32).unwrap();
+        assert_eq!(key.secret_key().len(), 32);
+        key.write(dir.join("k1.key")).unwrap();
+        let err = key.write(dir.join("k1.key")).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::IoError);
+        let key2 = KeyFile::read(dir.join("k1.key")).unwrap();
+        assert_eq!(key, key2);
+
+        let secret_key = key.secret_key();
+        let from_base64 = SecretKey::from_base64_encoded(secret_key.to_base64_encoded()).unwrap();
+        assert_eq!(from_base64.as_bytes(), secret_key.as_bytes());
+        let from_hex = SecretKey::from_hex(secret_key.to_hex()).unwrap();
+        assert_eq!(from_hex.as_bytes(), secret_key.as_bytes());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(dir.join("k1.key"))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_key_file_decode() {
+        let encoded = "argonautica-key-file v1\nid: 2019-01\ncreated: 1546300800\nkey: t9nGEsDxjWtJYdYeExdB6/HU0vg+rT6czv6HSjVjZng=\n";
+        let key = KeyFile::decode("<test>", encoded.as_bytes()).unwrap();
+        assert_eq!(key.id(), "2019-01");
+        assert_eq!(key.created(), 1_546_300_800);
+        assert_eq!(key.encode(), encoded);
+
+        for invalid in &[
+            "argonautica-key-file v2\nid: a\ncreated: 1\nkey: t9nGEsDxjWtJYdYeExdB6/HU0vg+rT6czv6HSjVjZng=\n",
+            "id: a\ncreated: 1\nkey: t9nGEsDxjWtJYdYeExdB6/HU0vg+rT6czv6HSjVjZng=\n",
+            "argonautica-key-file v1\nid: a\nkey: t9nGEsDxjWtJYdYeExdB6/HU0vg+rT6czv6HSjVjZng=\n",
+            "argonautica-key-file v1\nid: a b\ncreated: 1\nkey: t9nGEsDxjWtJYdYeExdB6/HU0vg+rT6czv6HSjVjZng=\n",
+            "argonautica-key-file v1\nid: a\ncreated: 1\nkey: not-a-secret\n",
+        ] {
+            let err = KeyFile::decode("<test>", invalid.as_bytes()).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::KeyFileInvalidError);
+            assert!(!err.to_string().contains("t9nGEsDx"));
+            assert!(!err.to_string().contains("not-a-secret"));
+        }
+
+        let err = KeyFile::new("a", "too short".into(), 1).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::SecretKeyTooShortError);
+    }
+
+    #[test]
+    fn test_key_set() {
+        let dir = temp_dir("key-set");
+        KeyFile::new("old", SecretKey::from(&[1u8; 32][..]), 1)
+            .unwrap()
+            .write(dir.join("old.key"))
+            .unwrap();
+        KeyFile::new("new", SecretKey::from(&[2u8; 32][..]), 2)
+            .unwrap()
+            .write(dir.join("new.key"))
+            .unwrap();
+        fs::write(dir.join("README"), "not a key file").unwrap();
+
+        let mut key_set = KeySet::load_dir(&dir).unwrap();
+        assert_eq!(key_set.len(), 2);
+        assert_eq!(key_set.current().unwrap().id(), "new");
+        assert_eq!(
+            key_set.get("old").unwrap().secret_key().as_bytes(),
+            &[1u8; 32]
+        );
+        assert!(key_set.get("missing").is_none());
+
+        let duplicate = KeyFile::new("old", SecretKey::from(&[3u8; 32][..]), 3).unwrap();
+        let err = key_set.insert(duplicate).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::KeyFileInvalidError);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,361 @@
+//! Verification of legacy (non-Argon2) password hashes (requires the `legacy` feature).
+//!
+//! If you are migrating users whose passwords were stored with bcrypt, scrypt or PBKDF2-SHA256,
+//! use a [`MultiVerifier`](struct.MultiVerifier.html) in place of a
+//! [`Verifier`](../struct.Verifier.html). It detects the scheme of each stored hash, verifies
+//! the password with the appropriate algorithm, and always reports that a non-Argon2 hash
+//! needs to be rehashed, so that you can transparently upgrade it with a
+//! [`Hasher`](../struct.Hasher.html) the next time the user logs in.
+//!
+//! ```
+//! extern crate argonautica;
+//!
+//! use argonautica::legacy::MultiVerifier;
+//! use argonautica::Hasher;
+//!
+//! fn main() {
+//!     let mut hasher = Hasher::default();
+//!     hasher
+//!         .configure_iterations(8)
+//!         .configure_memory_size(64)
+//!         .opt_out_of_secret_key(true);
+//!
+//!     // A bcrypt hash of "P@ssw0rd" from before the migration
+//!     let mut stored = "$2b$04$5LvzRHSQAsu3ifo92tiMsePX1uGi02L1JW7EXH4HQHt18Zh0I2Xg2".to_string();
+//!
+//!     let mut verifier = MultiVerifier::default();
+//!     verifier.with_hash(&stored).with_password("P@ssw0rd");
+//!     if verifier.verify().unwrap() && verifier.needs_rehash(&hasher).unwrap() {
+//!         stored = hasher.with_password("P@ssw0rd").hash().unwrap();
+//!     }
+//!     assert!(stored.starts_with("$argon2id$"));
+//! }
+//! ```
+use std::fmt;
+
+use bcrypt;
+use password_hash::{self, PasswordHash, PasswordVerifier};
+use pbkdf2::Pbkdf2;
+use scopeguard;
+use scrypt::Scrypt;
+
+use input::Password;
+use {Error, ErrorKind, Hasher, Verifier};
+
+/// Enum representing the password hashing schemes a
+/// [`MultiVerifier`](struct.MultiVerifier.html) can verify
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Scheme {
+    /// Argon2, i.e. a regular string-encoded hash (e.g. `$argon2id$v=19$...`)
+    Argon2,
+
+    /// bcrypt in the modular crypt format (`$2a$`, `$2b$`, `$2x$` or `$2y$`)
+    Bcrypt,
+
+    /// PBKDF2-SHA256 in the PHC string format (`$pbkdf2-sha256$`)
+    Pbkdf2Sha256,
+
+    /// scrypt in the PHC string format (`$scrypt$`)
+    Scrypt,
+}
+
+impl fmt::Display for Scheme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Scheme {
+    /// Detects the scheme of a hash from its prefix, returning `None` if the scheme is not
+    /// one of the supported schemes
+    pub fn detect(hash: &str) -> Option<Scheme> {
+        if hash.starts_with("$argon2") {
+            Some(Scheme::Argon2)
+        } else if hash.starts_with("$2a$")
+            || hash.starts_with("$2b$")
+            || hash.starts_with("$2x$")
+            || hash.starts_with("$2y$")
+        {
+            Some(Scheme::Bcrypt)
+        } else if hash.starts_with("$pbkdf2-sha256$") {
+            Some(Scheme::Pbkdf2Sha256)
+        } else if hash.starts_with("$scrypt$") {
+            Some(Scheme::Scrypt)
+        } else {
+            None
+        }
+    }
+
+    /// Performs the following mapping:
+    /// * `Scheme::Argon2` => `"argon2"`<br/>
+    /// * `Scheme::Bcrypt` => `"bcrypt"`<br/>
+    /// * `Scheme::Pbkdf2Sha256` => `"pbkdf2-sha256"`<br/>
+    /// * `Scheme::Scrypt` => `"scrypt"`
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Scheme::Argon2 => "argon2",
+            Scheme::Bcrypt => "bcrypt",
+            Scheme::Pbkdf2Sha256 => "pbkdf2-sha256",
+            Scheme::Scrypt => "scrypt",
+        }
+    }
+}
+
+impl<'a> Default for MultiVerifier<'a> {
+    /// Same as the [`new`](struct.MultiVerifier.html#method.new) method
+    fn default() -> MultiVerifier<'a> {
+        MultiVerifier {
+            verifier: Verifier::default(),
+        }
+    }
+}
+
+impl<'a> From<Verifier<'a>> for MultiVerifier<'a> {
+    /// Wraps an already configured [`Verifier`](../struct.Verifier.html), which will be used
+    /// to verify Argon2 hashes
+    fn from(verifier: Verifier<'a>) -> MultiVerifier<'a> {
+        MultiVerifier { verifier }
+    }
+}
+
+/// Struct that verifies passwords against Argon2 hashes as well as legacy bcrypt, scrypt and
+/// PBKDF2-SHA256 hashes
+///
+/// Argon2 hashes are verified with the wrapped [`Verifier`](../struct.Verifier.html), so
+/// its configuration, secret key and additional data apply to them as usual (use
+/// [`verifier_mut`](struct.MultiVerifier.html#method.verifier_mut) to provide those). Legacy
+/// hashes are verified with the password alone, honoring the `password_clearing`
+/// configuration of the [`Verifier`](../struct.Verifier.html)
+#[derive(Debug)]
+pub struct MultiVerifier<'a> {
+    verifier: Verifier<'a>,
+}
+
+impl<'a> MultiVerifier<'a> {
+    /// Creates a new [`MultiVerifier`](struct.MultiVerifier.html) wrapping a default
+    /// [`Verifier`](../struct.Verifier.html)
+    pub fn new() -> MultiVerifier<'a> {
+        MultiVerifier::default()
+    }
+    /// Returns `true` if the hash was produced with a scheme other than Argon2 or with
+    /// parameters that differ from the provided [`Hasher`](../struct.Hasher.html)'s
+    /// configuration (see [`needs_rehash`](../struct.Hasher.html#method.needs_rehash)), i.e. if
+    /// you should rehash the password with the [`Hasher`](../struct.Hasher.html) after
+    /// successfully verifying it. Returns an error if there is no hash or its scheme is not
+    /// supported
+    pub fn needs_rehash(&self, hasher: &Hasher) -> Result<bool, Error> {
+        let hash = self.encoded_hash()?;
+        match self.scheme_of(&hash)? {
+            Scheme::Argon2 => hasher.needs_rehash(&hash),
+            _ => Ok(true),
+        }
+    }
+    /// Returns the [`Scheme`](enum.Scheme.html) of the hash, or `None` if there is no hash
+    /// or its scheme is not supported
+    pub fn scheme(&self) -> Option<Scheme> {
+        self.verifier.hash().and_then(|hash| Scheme::detect(&hash))
+    }
+    /// Verifies the password against the hash with the algorithm of the hash's
+    /// [`Scheme`](enum.Scheme.html). Returns an error if there is no hash, if its scheme is
+    /// not supported, or if it cannot be decoded
+    pub fn verify(&mut self) -> Result<bool, Error> {
+        let hash = self.encoded_hash()?;
+        let scheme = self.scheme_of(&hash)?;
+        if scheme == Scheme::Argon2 {
+            return self.verifier.verify();
+        }
+        let verifier = scopeguard::guard(&mut self.verifier, |verifier| {
+            verifier.hasher.clear();
+        });
+        let password = validate_password(&verifier)?;
+        match scheme {
+            Scheme::Argon2 => unreachable!(),
+            Scheme::Bcrypt => bcrypt::verify(password.as_bytes(), &hash).map_err(|e| {
+                Error::new(ErrorKind::HashDecodeError).add_context(format!("bcrypt: {}", e))
+            }),
+            Scheme::Pbkdf2Sha256 => verify_phc(&Pbkdf2, password, &hash),
+            Scheme::Scrypt => verify_phc(&Scrypt, password, &hash),
+        }
+    }
+    /// Allows you to provide [`MultiVerifier`](struct.MultiVerifier.html) with the hash to
+    /// verify against, in any of the supported schemes
+    pub fn with_hash<S>(&mut self, hash: S) -> &mut MultiVerifier<'a>
+    where
+        S: AsRef<str>,
+    {
+        self.verifier.with_hash(hash);
+        self
+    }
+    /// Allows you to provide [`MultiVerifier`](struct.MultiVerifier.html) with the password
+    /// to verify against
+    pub fn with_password<P>(&mut self, password: P) -> &mut MultiVerifier<'a>
+    where
+        P: Into<Password<'a>>,
+    {
+        self.verifier.with_password(password);
+        self
+    }
+    /// Read-only access to the wrapped [`Verifier`](../struct.Verifier.html)
+    pub fn verifier(&self) -> &Verifier<'a> {
+        &self.verifier
+    }
+    /// Mutable access to the wrapped [`Verifier`](../struct.Verifier.html), e.g. to configure
+    /// it or to provide it with a secret key
+    pub fn verifier_mut(&mut self) -> &mut Verifier<'a> {
+        &mut self.verifier
+    }
+}
+
+impl<'a> MultiVerifier<'a> {
+    fn encoded_hash(&self) -> Result<String, Error> {
+        self.verifier
+            .hash()
+            .ok_or_else(|| Error::new(ErrorKind::HashMissingError))
+    }
+    fn scheme_of(&self, hash: &str) -> Result<Scheme, Error> {
+        Scheme::detect(hash).ok_or_else(|| {
+            Error::new(ErrorKind::HashDecodeError).add_context("Unsupported hash scheme")
+        })
+    }
+}
+
+fn validate_password<'a, 'b>(verifier: &'b Verifier<'a>) -> Result<&'b Password<'a>, Error> {
+    let password = match verifier.password() {
+        Some(password) => password,
+        None => return Err(Error::new(ErrorKind::PasswordMissingError)),
+    };
+    password.validate()?;
+    if verifier.hasher.config.password_clearing() && !password.is_mutable() {
+        return Err(Error::new(ErrorKind::PasswordImmutableError));
+    }
+    Ok(password)
+}
+
+fn verify_phc<V: PasswordVerifier>(
+    algorithm: &V,
+    password: &Password,
+    hash: &str,
+) -> Result<bool, Error> {
+    let parsed = PasswordHash::new(hash)
+        .map_err(|e| Error::new(ErrorKind::HashDecodeError).add_context(format!("{}", e)))?;
+    match algorithm.verify_password(password.as_bytes(), &parsed) {
+        Ok(()) => Ok(true),
+        Err(password_hash::Error::Password) => Ok(false),
+        Err(e) => Err(Error::new(ErrorKind::HashDecodeError).add_context(format!("{}", e))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use password_hash::{PasswordHasher, SaltString};
+    use pbkdf2;
+    use scrypt;
+
+    use super::*;
+
+    fn hasher() -> Hasher<'static> {
+        let mut hasher = Hasher::default();
+        hasher
+            .configure_iterations(8)
+            .configure_lanes(2)
+            .configure_memory_size(64)
+            .configure_threads(2)
+            .opt_out_of_secret_key(true);
+        hasher
+    }
+
+    fn legacy_hashes() -> Vec<(Scheme, String)> {
+        let salt = SaltString::from_b64("c29tZXNhbHQ").unwrap();
+        let pbkdf2_params = pbkdf2::Params {
+            rounds: 1000,
+            output_length: 32,
+        };
+        let pbkdf2_hash = Pbkdf2
+            .hash_password_customized(b"P@ssw0rd", None, None, pbkdf2_params, &salt)
+            .unwrap()
+            .to_string();
+        let scrypt_params = scrypt::Params::new(4, 8, 1, 32).unwrap();
+        let scrypt_hash = Scrypt
+            .hash_password_customized(b"P@ssw0rd", None, None, scrypt_params, &salt)
+            .unwrap()
+            .to_string();
+        vec![
+            (Scheme::Bcrypt, bcrypt::hash("P@ssw0rd", 4).unwrap()),
+            (Scheme::Pbkdf2Sha256, pbkdf2_hash),
+            (Scheme::Scrypt, scrypt_hash),
+        ]
+    }
+
+    #[test]
+    fn test_multi_verifier_legacy() {
+        let hasher = hasher();
+        for (scheme, hash) in legacy_hashes() {
+            assert_eq!(Scheme::detect(&hash), Some(scheme));
+
+            let mut verifier = MultiVerifier::new();
+            verifier.with_hash(&hash).with_password("P@ssw0rd");
+            assert_eq!(verifier.scheme(), Some(scheme));
+            assert!(verifier.verify().unwrap(), "{}", scheme);
+            assert!(verifier.needs_rehash(&hasher).unwrap());
+
+            verifier.with_password("wrong");
+            assert!(!verifier.verify().unwrap(), "{}", scheme);
+        }
+    }
+
+    #[test]
+    fn test_multi_verifier_argon2() {
+        let mut hasher = hasher();
+        hasher.with_secret_key("somesecret");
+        let hash = hasher.to_owned().with_password("P@ssw0rd").hash().unwrap();
+
+        let mut verifier = MultiVerifier::new();
+        verifier.verifier_mut().with_secret_key("somesecret");
+        verifier.with_hash(&hash).with_password("P@ssw0rd");
+        assert_eq!(verifier.scheme(), Some(Scheme::Argon2));
+        assert!(verifier.verify().unwrap());
+        assert!(!verifier.needs_rehash(&hasher).unwrap());
+
+        hasher.configure_iterations(16);
+        assert!(verifier.needs_rehash(&hasher).unwrap());
+    }
+
+    #[test]
+    fn test_multi_verifier_errors() {
+        let mut verifier = MultiVerifier::new();
+        verifier.with_password("P@ssw0rd");
+        assert_eq!(
+            verifier.verify().unwrap_err().kind(),
+            ErrorKind::HashMissingError
+        );
+
+        verifier.with_hash("$1$somesalt$somehash");
+        assert_eq!(verifier.scheme(), None);
+        assert_eq!(
+            verifier.verify().unwrap_err().kind(),
+            ErrorKind::HashDecodeError
+        );
+        assert_eq!(
+            verifier.needs_rehash(&hasher()).unwrap_err().kind(),
+            ErrorKind::HashDecodeError
+        );
+
+        verifier.with_hash("$scrypt$ln=4,r=8$c29tZXNhbHQ$c29tZWhhc2g");
+        assert_eq!(
+            verifier.verify().unwrap_err().kind(),
+            ErrorKind::HashDecodeError
+        );
+
+        let mut password = "P@ssw0rd".to_string();
+        let (_, hash) = legacy_hashes().remove(0);
+        let mut verifier = MultiVerifier::new();
+        verifier.verifier_mut().configure_password_clearing(true);
+        verifier.with_hash(&hash).with_password(&mut password);
+        assert!(verifier.verify().unwrap());
+        assert!(verifier.verifier().password().is_none());
+        assert_eq!(
+            verifier.verify().unwrap_err().kind(),
+            ErrorKind::PasswordMissingError
+        );
+    }
+}
@@ -246,6 +246,29 @@
 //!     * ... if you're building for a different machine ...
 //!         * `argonautica = "0.2"`, or
 //!         * `argonautica = { version = "0.2", features = ["serde"] }`
+//!     * ... and, if you want argonautica to implement the RustCrypto
+//!       [password-hash](https://docs.rs/password-hash) traits (e.g. to use it in place of the
+//!       `argon2` crate), add `"password-hash"` to the list of features (see the `phc` module)
+//!     * ... and, if you need to verify legacy bcrypt, scrypt or PBKDF2-SHA256 hashes while
+//!       migrating users to Argon2, add `"legacy"` to the list of features (see the `legacy` module);
+//!       this feature is also required to verify onion hashes, i.e. legacy MD5, SHA-1
+//!       or bcrypt digests wrapped in Argon2 (see `Hasher::configure_inner_scheme`)
+//!     * ... and, if you would like passwords to be Unicode-normalized before they are hashed
+//!       or verified, so that the same passphrase typed on different platforms verifies, add
+//!       `"normalization"` to the list of features (see `Hasher::configure_normalization`)
+//!     * ... and, if you would like to reject passwords that appear in a local copy of the
+//!       Have I Been Pwned dataset before hashing them, add `"breach"` to the list of features
+//!       (see the `breach` module)
+//!     * ... and, if you would like to load your configuration from a TOML or JSON file and
+//!       `ARGONAUTICA_*` environment variables, add `"settings"` to the list of features (see
+//!       `config::Settings`)
+//!     * ... and, if you would like your hashes to record fingerprints of the secret key and
+//!       additional data they were created with, so that verifying with the wrong ones is
+//!       reported as such, add `"binding"` to the list of features (see
+//!       `Hasher::configure_binding`)
+//!     * ... and, if you would like every hash and verification to run inside a
+//!       [tracing](https://docs.rs/tracing) span with its parameters as fields, add
+//!       `"tracing"` to the list of features (see the `metrics` module)
 //!
 //! That said, <b>argonautica</b> uses [cc](https://github.com/alexcrichton/cc-rs) and
 //! [bindgen](https://github.com/rust-lang-nursery/rust-bindgen) to compile the canonical
@@ -258,7 +281,7 @@
 //!     * Arch linux: `pacman -S clang`
 //!     * Windows: Download a pre-built binary [here](http://releases.llvm.org/download.html)
 //!
-//! <b>argonautica</b> runs on stable Rust version 1.32.0 or greater.
+//! <b>argonautica</b> runs on stable Rust version 1.40.0 or greater.
 //!
 //! # License
 //!
@@ -278,9 +301,11 @@
 #![doc(html_root_url = "https://docs.rs/argonautica/0.2.1")]
 
 extern crate base64;
+#[cfg(feature = "legacy")]
+extern crate bcrypt;
 #[macro_use]
 extern crate bitflags;
-#[cfg(feature = "development")]
+#[cfg(any(feature = "binding", feature = "development"))]
 extern crate blake2_rfc;
 #[macro_use]
 extern crate failure;
@@ -289,29 +314,60 @@ extern crate futures_cpupool;
 extern crate libc;
 #[macro_use]
 extern crate log;
+#[cfg(feature = "legacy")]
+extern crate md5;
 #[macro_use]
 extern crate nom;
 extern crate num_cpus;
+#[cfg(feature = "password-hash")]
+extern crate password_hash;
+#[cfg(feature = "legacy")]
+extern crate pbkdf2;
 extern crate rand;
 extern crate scopeguard;
+#[cfg(feature = "legacy")]
+extern crate scrypt;
 #[cfg(feature = "serde")]
 #[macro_use]
 extern crate serde;
-#[cfg(all(test, feature = "serde"))]
+#[cfg(any(feature = "settings", all(test, feature = "serde")))]
 extern crate serde_json;
+#[cfg(any(feature = "breach", feature = "legacy"))]
+extern crate sha1;
+#[cfg(feature = "settings")]
+extern crate toml;
+#[cfg(feature = "tracing")]
+extern crate tracing;
+#[cfg(feature = "normalization")]
+extern crate unicode_normalization;
 
 mod backend;
+#[cfg(feature = "binding")]
+mod binding;
+mod context;
 mod error;
 mod error_kind;
 mod ffi;
 mod hasher;
+mod hasher_builder;
 mod verifier;
 
+#[cfg(feature = "breach")]
+pub mod breach;
 pub mod config;
+pub use context::{HasherContext, VerifierContext};
 pub use error::Error;
 pub use error_kind::ErrorKind;
 pub use hasher::Hasher;
+pub use hasher_builder::HasherBuilder;
 pub mod input;
+pub mod keys;
+#[cfg(feature = "legacy")]
+pub mod legacy;
+pub mod metrics;
 pub mod output;
+#[cfg(feature = "password-hash")]
+pub mod phc;
+pub mod policy;
 pub mod utils;
 pub use verifier::Verifier;
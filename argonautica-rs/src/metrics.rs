@@ -0,0 +1,425 @@
+//! Instrumentation of hashing and verifying, e.g. to chart login latency per parameter set.
+//!
+//! If you configure a [`Hasher`](../struct.Hasher.html) or [`Verifier`](../struct.Verifier.html)
+//! with an implementation of [`Metrics`](trait.Metrics.html) (see
+//! [`configure_metrics`](../struct.Hasher.html#method.configure_metrics)), every hash and
+//! verification is reported to it as a [`Sample`](struct.Sample.html), which carries:
+//! * the [`Operation`](enum.Operation.html) (hash or verify),
+//! * the [`Outcome`](enum.Outcome.html) (success, mismatch or error),
+//! * how long it took, and
+//! * the Argon2 [`Parameters`](struct.Parameters.html) it was performed with, including the
+//!   amount of memory used.
+//!
+//! The non-blocking methods additionally report how long each hash or verification waited for
+//! a thread of the `CpuPool`. What to do with the samples (e.g. increment counters and feed
+//! histograms of your metrics library of choice) is up to you; argonautica does not depend on
+//! one. Samples never contain passwords, secret keys, additional data, salts or hashes.
+//!
+//! Independently, if the `tracing` feature is enabled, every hash and verification runs inside
+//! a [`tracing`](https://docs.rs/tracing/0.1) span (`argonautica.hash` or
+//! `argonautica.verify`) with the parameters and the outcome as fields
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "tracing")]
+use tracing::field::Empty;
+#[cfg(feature = "tracing")]
+use tracing::span::EnteredSpan;
+#[cfg(feature = "tracing")]
+use tracing::Span;
+
+use config::{HasherConfig, Variant, Version};
+use output::HashRaw;
+use {Error, ErrorKind};
+
+/// Trait for sinks of [`Sample`](struct.Sample.html)s, e.g. adapters to a metrics library
+pub trait Metrics: fmt::Debug + Send + Sync {
+    /// Called once for every hash and verification, after it completes
+    fn record(&self, sample: &Sample);
+
+    /// Called by the non-blocking methods with the time a hash or verification waited for a
+    /// thread of the `CpuPool` before it started. Does nothing by default
+    fn record_queue_wait(&self, operation: Operation, wait: Duration) {
+        let _ = (operation, wait);
+    }
+}
+
+/// Enum representing what was instrumented
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Operation {
+    /// Hashing a password with [`Hasher`](../struct.Hasher.html)
+    Hash,
+    /// Verifying a password with [`Verifier`](../struct.Verifier.html). Calls to
+    /// [`verify_dummy`](../struct.Verifier.html#method.verify_dummy) are reported as
+    /// verifications as well, with an outcome of
+    /// [`Mismatch`](enum.Outcome.html#variant.Mismatch), so that they cannot be told apart
+    Verify,
+}
+
+impl Operation {
+    /// Returns a string representation of the operation, i.e. `"hash"` or `"verify"`
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Operation::Hash => "hash",
+            Operation::Verify => "verify",
+        }
+    }
+}
+
+/// Enum representing how a hash or verification ended
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Outcome {
+    /// The password was hashed, or it matched the hash it was verified against
+    Success,
+    /// The password did not match the hash it was verified against
+    Mismatch,
+    /// An error of the contained kind was returned
+    Error(ErrorKind),
+}
+
+impl Outcome {
+    /// Returns a string representation of the outcome, i.e. `"success"`, `"mismatch"` or
+    /// `"error"`
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Outcome::Success => "success",
+            Outcome::Mismatch => "mismatch",
+            Outcome::Error(_) => "error",
+        }
+    }
+}
+
+/// Struct representing the Argon2 parameters a hash or verification was performed with
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Parameters {
+    hash_len: u32,
+    iterations: u32,
+    lanes: u32,
+    memory_size: u32,
+    variant: Variant,
+    version: Version,
+}
+
+impl Parameters {
+    /// Returns the number of bytes of memory used, i.e. the memory size in bytes
+    pub fn memory_used(&self) -> u64 {
+        u64::from(self.memory_size) * 1024
+    }
+}
+
+impl Parameters {
+    #[allow(missing_docs)]
+    pub fn hash_len(&self) -> u32 {
+        self.hash_len
+    }
+    #[allow(missing_docs)]
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+    #[allow(missing_docs)]
+    pub fn lanes(&self) -> u32 {
+        self.lanes
+    }
+    #[allow(missing_docs)]
+    pub fn memory_size(&self) -> u32 {
+        self.memory_size
+    }
+    #[allow(missing_docs)]
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+    #[allow(missing_docs)]
+    pub fn version(&self) -> Version {
+        self.version
+    }
+}
+
+impl From<&HasherConfig> for Parameters {
+    fn from(config: &HasherConfig) -> Parameters {
+        Parameters {
+            hash_len: config.hash_len(),
+            iterations: config.iterations(),
+            lanes: config.lanes(),
+            memory_size: config.memory_size(),
+            variant: config.variant(),
+            version: config.version(),
+        }
+    }
+}
+
+impl From<&HashRaw> for Parameters {
+    fn from(hash_raw: &HashRaw) -> Parameters {
+        Parameters {
+            hash_len: hash_raw.raw_hash_bytes().len() as u32,
+            iterations: hash_raw.iterations(),
+            lanes: hash_raw.lanes(),
+            memory_size: hash_raw.memory_size(),
+            variant: hash_raw.variant(),
+            version: hash_raw.version(),
+        }
+    }
+}
+
+/// Struct representing a single instrumented hash or verification
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Sample {
+    duration: Duration,
+    operation: Operation,
+    outcome: Outcome,
+    parameters: Option<Parameters>,
+}
+
+impl Sample {
+    /// Returns how long the hash or verification took, not including any time spent waiting
+    /// for a thread of the `CpuPool`
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+    #[allow(missing_docs)]
+    pub fn operation(&self) -> Operation {
+        self.operation
+    }
+    #[allow(missing_docs)]
+    pub fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+    /// Returns the parameters the hash or verification was performed with, which are those
+    /// of the [`Hasher`](../struct.Hasher.html) for hashes and those decoded from the hash for
+    /// verifications. Returns `None` if a verification failed before its hash was decoded
+    pub fn parameters(&self) -> Option<&Parameters> {
+        self.parameters.as_ref()
+    }
+}
+
+/// Instruments a single hash or verification: reports it to the configured
+/// [`Metrics`](trait.Metrics.html), if any, and (with the `tracing` feature) keeps its span
+pub(crate) struct Recorder {
+    metrics: Option<Arc<dyn Metrics>>,
+    operation: Operation,
+    parameters: Option<Parameters>,
+    #[cfg(feature = "tracing")]
+    span: Span,
+    start: Instant,
+}
+
+/// Guard returned by `Recorder::enter`
+pub(crate) struct Entered {
+    #[cfg(feature = "tracing")]
+    _span: EnteredSpan,
+}
+
+impl Recorder {
+    /// Starts instrumenting a hash with the parameters of the provided configuration
+    pub(crate) fn hash(config: &HasherConfig) -> Recorder {
+        let mut recorder = Recorder::new(Operation::Hash, config);
+        recorder.set_parameters(Parameters::from(config));
+        recorder
+    }
+    /// Starts instrumenting a verification; its parameters are set once the hash is decoded
+    pub(crate) fn verify(config: &HasherConfig) -> Recorder {
+        Recorder::new(Operation::Verify, config)
+    }
+    /// Enters the span of the hash or verification until the returned guard is dropped
+    pub(crate) fn enter(&self) -> Entered {
+        Entered {
+            #[cfg(feature = "tracing")]
+            _span: self.span.clone().entered(),
+        }
+    }
+    /// Stops instrumenting, reporting the outcome of the provided result; `is_match` tells
+    /// whether a successful result is a match
+    pub(crate) fn finish<T, F>(self, result: &Result<T, Error>, is_match: F)
+    where
+        F: FnOnce(&T) -> bool,
+    {
+        let outcome = match *result {
+            Ok(ref value) if is_match(value) => Outcome::Success,
+            Ok(_) => Outcome::Mismatch,
+            Err(ref e) => Outcome::Error(e.kind()),
+        };
+        let duration = self.start.elapsed();
+        #[cfg(feature = "tracing")]
+        {
+            self.span.record("outcome", outcome.as_str());
+            if let Outcome::Error(kind) = outcome {
+                self.span.record("error", ::tracing::field::debug(kind));
+            }
+        }
+        if let Some(ref metrics) = self.metrics {
+            metrics.record(&Sample {
+                duration,
+                operation: self.operation,
+                outcome,
+                parameters: self.parameters,
+            });
+        }
+    }
+    /// Sets the parameters the hash or verification is performed with
+    pub(crate) fn set_parameters(&mut self, parameters: Parameters) {
+        #[cfg(feature = "tracing")]
+        {
+            self.span.record("hash_len", parameters.hash_len);
+            self.span.record("iterations", parameters.iterations);
+            self.span.record("lanes", parameters.lanes);
+            self.span.record("memory_size", parameters.memory_size);
+            self.span.record("variant", parameters.variant.as_str());
+            self.span.record("version", parameters.version.as_str());
+        }
+        self.parameters = Some(parameters);
+    }
+    fn new(operation: Operation, config: &HasherConfig) -> Recorder {
+        Recorder {
+            metrics: config.metrics.clone(),
+            operation,
+            parameters: None,
+            #[cfg(feature = "tracing")]
+            span: info_span(config, operation),
+            start: Instant::now(),
+        }
+    }
+}
+
+/// Reports the time a hash or verification waited for a thread of the `CpuPool` to the
+/// configured [`Metrics`](trait.Metrics.html), if any
+pub(crate) fn record_queue_wait(config: &HasherConfig, operation: Operation, queued: Instant) {
+    if let Some(ref metrics) = config.metrics {
+        metrics.record_queue_wait(operation, queued.elapsed());
+    }
+}
+
+#[cfg(feature = "tracing")]
+fn info_span(config: &HasherConfig, operation: Operation) -> Span {
+    // Span names must be literals, hence one invocation per operation
+    macro_rules! info_span {
+        ($name:expr) => {
+            ::tracing::info_span!(
+                $name,
+                backend = ?config.backend(),
+                threads = config.threads(),
+                hash_len = Empty,
+                iterations = Empty,
+                lanes = Empty,
+                memory_size = Empty,
+                variant = Empty,
+                version = Empty,
+                outcome = Empty,
+                error = Empty,
+            )
+        };
+    }
+    match operation {
+        Operation::Hash => info_span!("argonautica.hash"),
+        Operation::Verify => info_span!("argonautica.verify"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use {Hasher, Verifier};
+
+    #[derive(Debug, Default)]
+    struct Collector {
+        queue_waits: Mutex<Vec<Operation>>,
+        samples: Mutex<Vec<Sample>>,
+    }
+
+    impl Metrics for Collector {
+        fn record(&self, sample: &Sample) {
+            self.samples.lock().unwrap().push(*sample);
+        }
+        fn record_queue_wait(&self, operation: Operation, _wait: Duration) {
+            self.queue_waits.lock().unwrap().push(operation);
+        }
+    }
+
+    #[test]
+    fn test_metrics() {
+        use futures::Future;
+
+        let collector = Arc::new(Collector::default());
+        let mut hasher = Hasher::default();
+        hasher
+            .configure_iterations(8)
+            .configure_lanes(2)
+            .configure_memory_size(64)
+            .configure_metrics(Some(collector.clone()))
+            .configure_threads(2)
+            .with_secret_key("secret key");
+        let hash = hasher.with_password("P@ssw0rd").hash().unwrap();
+        let _ = hasher.with_password("").hash().unwrap_err();
+        let _ = hasher
+            .with_password("P@ssw0rd")
+            .hash_non_blocking()
+            .wait()
+            .unwrap();
+
+        let mut verifier = Verifier::default();
+        verifier
+            .configure_metrics(Some(collector.clone()))
+            .with_hash(&hash)
+            .with_secret_key("secret key");
+        assert!(verifier.with_password("P@ssw0rd").verify().unwrap());
+        assert!(!verifier.with_password("wrong").verify().unwrap());
+        assert!(verifier
+            .with_password("P@ssw0rd")
+            .verify_detailed()
+            .unwrap()
+            .is_valid());
+        let _ = verifier
+            .with_hash("invalid")
+            .with_password("P@ssw0rd")
+            .verify()
+            .unwrap_err();
+
+        let samples = collector.samples.lock().unwrap();
+        let summary = samples
+            .iter()
+            .map(|sample| (sample.operation(), sample.outcome()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (Operation::Hash, Outcome::Success),
+                (
+                    Operation::Hash,
+                    Outcome::Error(ErrorKind::PasswordTooShortError)
+                ),
+                (Operation::Hash, Outcome::Success),
+                (Operation::Verify, Outcome::Success),
+                (Operation::Verify, Outcome::Mismatch),
+                (Operation::Verify, Outcome::Success),
+                (
+                    Operation::Verify,
+                    Outcome::Error(ErrorKind::HashDecodeError)
+                ),
+            ]
+        );
+        let parameters = samples[3].parameters().unwrap();
+        assert_eq!(parameters, samples[0].parameters().unwrap());
+        assert_eq!(parameters.iterations(), 8);
+        assert_eq!(parameters.memory_used(), 64 * 1024);
+        assert!(samples[6].parameters().is_none());
+        assert_eq!(
+            *collector.queue_waits.lock().unwrap(),
+            vec![Operation::Hash]
+        );
+    }
+
+    #[test]
+    fn test_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<Parameters>();
+        assert_send::<Sample>();
+    }
+
+    #[test]
+    fn test_sync() {
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<Parameters>();
+        assert_sync::<Sample>();
+    }
+}
@@ -1,7 +1,7 @@
 use std::str::FromStr;
 
 use backend::decode_rust;
-use config::{Variant, Version};
+use config::{Inner, Variant, Version};
 use Error;
 
 impl FromStr for HashRaw {
@@ -32,6 +32,10 @@ impl FromStr for HashRaw {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct HashRaw {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) data: Option<Vec<u8>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) inner: Option<Inner>,
     pub(crate) iterations: u32,
     pub(crate) lanes: u32,
     pub(crate) memory_size: u32,
@@ -42,10 +46,63 @@ pub struct HashRaw {
 }
 
 impl HashRaw {
+    /// Assembles a [`HashRaw`](struct.HashRaw.html) from its individual components, e.g. so that
+    /// raw hash bytes and raw salt bytes stored separately can be converted back into a
+    /// string-encoded hash via [`to_string`](struct.HashRaw.html#method.to_string).
+    /// No validation of the components is performed
+    pub fn new(
+        iterations: u32,
+        lanes: u32,
+        memory_size: u32,
+        raw_hash_bytes: Vec<u8>,
+        raw_salt_bytes: Vec<u8>,
+        variant: Variant,
+        version: Version,
+    ) -> HashRaw {
+        HashRaw {
+            data: None,
+            inner: None,
+            iterations,
+            lanes,
+            memory_size,
+            raw_hash_bytes,
+            raw_salt_bytes,
+            variant,
+            version,
+        }
+    }
+    /// Records an inner legacy scheme on the [`HashRaw`](struct.HashRaw.html), e.g. to convert
+    /// the raw bytes of an onion hash stored separately back into a string-encoded hash
+    pub fn with_inner(mut self, inner: Option<Inner>) -> HashRaw {
+        self.inner = inner;
+        self
+    }
+    /// Records the raw bytes of the `data` parameter on the [`HashRaw`](struct.HashRaw.html),
+    /// e.g. the fingerprints of a hash bound to its secret key and additional data (see
+    /// [`configure_binding`](../struct.Hasher.html#method.configure_binding))
+    pub fn with_data(mut self, data: Option<Vec<u8>>) -> HashRaw {
+        self.data = data;
+        self
+    }
     /// Converts the [`HashRaw`](struct.HashRaw.html) to a string-encoded hash
     pub fn to_string(&self) -> String {
         self.encode_rust()
     }
+    /// Read-only access to the raw bytes of the `data` parameter, if any, which holds the
+    /// fingerprints of a hash bound to its secret key and additional data (see
+    /// [`configure_binding`](../struct.Hasher.html#method.configure_binding))
+    pub fn data(&self) -> Option<&[u8]> {
+        match self.data {
+            Some(ref data) => Some(&data[..]),
+            None => None,
+        }
+    }
+    /// Obtain the inner legacy scheme (and its parameters) of an onion hash, i.e. of a hash
+    /// whose password was a legacy digest (see [`InnerScheme`](../config/enum.InnerScheme.html)),
+    /// if any
+    pub fn inner(&self) -> Option<&Inner> {
+        self.inner.as_ref()
+    }
     /// Obtain the iterations configuration that was used to produce this hash
     pub fn iterations(&self) -> u32 {
         self.iterations
@@ -92,6 +149,23 @@ mod tests {
         assert_sync::<HashRaw>();
     }
 
+    #[test]
+    fn test_new() {
+        let s = "$argon2id$v=19$m=4096,t=128,p=2$c29tZXNhbHQ$iWh06vD8Fy27wf9npn6FXWiCX4K6pW6Ue1Bnzz07Z8A";
+        let hash_raw = s.parse::<HashRaw>().unwrap();
+        let hash_raw2 = HashRaw::new(
+            /* iterations */ 128,
+            /* lanes */ 2,
+            /* memory_size */ 4096,
+            /* raw_hash_bytes */ hash_raw.raw_hash_bytes().to_vec(),
+            /* raw_salt_bytes */ b"somesalt".to_vec(),
+            /* variant */ Variant::Argon2id,
+            /* version */ Version::_0x13,
+        );
+        assert_eq!(hash_raw, hash_raw2);
+        assert_eq!(&hash_raw2.to_string(), s);
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn test_serialize() {
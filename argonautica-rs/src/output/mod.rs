@@ -1,4 +1,6 @@
-//! Struct representing raw hash output.
+//! Structs representing raw hash output and the outcome of verifying a password.
 mod hash_raw;
+mod verification;
 
 pub use self::hash_raw::HashRaw;
+pub use self::verification::Verification;
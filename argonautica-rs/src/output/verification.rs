@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use output::HashRaw;
+
+impl From<Verification> for bool {
+    /// Collapses the [`Verification`](struct.Verification.html) into whether or not the
+    /// password matched
+    fn from(verification: Verification) -> bool {
+        verification.is_valid
+    }
+}
+
+/// Struct representing the detailed outcome of verifying a password, as returned by
+/// [`verify_detailed`](../struct.Verifier.html#method.verify_detailed) and
+/// [`verify_with_key_set`](../struct.Verifier.html#method.verify_with_key_set) on
+/// [`Verifier`](../struct.Verifier.html).
+///
+/// Besides whether or not the password matched (see
+/// [`is_valid`](struct.Verification.html#method.is_valid), or convert it into a `bool`), it
+/// reports the parameters decoded from the hash, whether you should rehash the password, which
+/// key of a [`KeySet`](../keys/struct.KeySet.html) matched and how long verifying took
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Verification {
+    pub(crate) duration: Duration,
+    pub(crate) hash_raw: HashRaw,
+    pub(crate) is_valid: bool,
+    pub(crate) key_id: Option<String>,
+    pub(crate) rehash_recommended: bool,
+}
+
+impl Verification {
+    /// Returns how long verifying took, including decoding the hash and (for
+    /// [`verify_with_key_set`](../struct.Verifier.html#method.verify_with_key_set)) trying
+    /// every candidate key
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+    /// Read-only access to the hash that was verified against, decoded into a
+    /// [`HashRaw`](struct.HashRaw.html), e.g. to inspect the parameters it was created with
+    pub fn hash_raw(&self) -> &HashRaw {
+        &self.hash_raw
+    }
+    /// Returns `true` if the password matched the hash
+    pub fn is_valid(&self) -> bool {
+        self.is_valid
+    }
+    /// Returns the ID of the key (see [`KeyFile`](../keys/struct.KeyFile.html)) that matched,
+    /// if the password was verified with a key file, either provided with
+    /// [`with_key_file`](../struct.Verifier.html#method.with_key_file) or as part of
+    /// [`verify_with_key_set`](../struct.Verifier.html#method.verify_with_key_set), and matched
+    pub fn key_id(&self) -> Option<&str> {
+        self.key_id.as_deref()
+    }
+    /// Returns `true` if the password matched and the hash should be replaced with a new one,
+    /// i.e. if it was created with parameters that differ from the configuration set with
+    /// [`configure_rehash_config`](../struct.Verifier.html#method.configure_rehash_config)
+    /// (if any), or with a key other than the current key of the
+    /// [`KeySet`](../keys/struct.KeySet.html) it was verified with
+    pub fn rehash_recommended(&self) -> bool {
+        self.rehash_recommended
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<Verification>();
+    }
+
+    #[test]
+    fn test_sync() {
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<Verification>();
+    }
+}
@@ -0,0 +1,639 @@
+//! Interoperability with the [RustCrypto](https://github.com/RustCrypto) `password-hash`
+//! traits (requires the `password-hash` feature).
+//!
+//! With this feature enabled, [`Hasher`](../struct.Hasher.html) implements
+//! [`PasswordHasher`](trait.PasswordHasher.html) and [`Verifier`](../struct.Verifier.html)
+//! implements [`PasswordVerifier`](trait.PasswordVerifier.html), so argonautica can be dropped
+//! into code that is generic over those traits, e.g. code written for the `argon2` crate. Their
+//! secret key and additional data (if any) are used, and the `inner` parameter of an onion hash
+//! (see [`InnerScheme`](../config/enum.InnerScheme.html)) and the `data` parameter of a bound
+//! hash (see [`configure_binding`](../struct.Hasher.html#method.configure_binding)) are written
+//! when hashing and honored when verifying.
+//!
+//! Through a blanket implementation in the `password-hash` crate,
+//! [`Hasher`](../struct.Hasher.html) also implements
+//! [`PasswordVerifier`](trait.PasswordVerifier.html), by hashing the password again with the
+//! parameters of the hash and comparing the outputs. That is plain hashing, i.e. the password
+//! is checked against the password policy and breach lookup, and for an onion hash it must be
+//! a legacy digest, so verify with a [`Verifier`](../struct.Verifier.html) instead.
+//!
+//! ```
+//! extern crate argonautica;
+//!
+//! use argonautica::phc::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+//! use argonautica::{Hasher, Verifier};
+//!
+//! fn main() {
+//!     let mut hasher = Hasher::default();
+//!     hasher
+//!         .configure_iterations(8)
+//!         .configure_memory_size(64)
+//!         .with_secret_key("somesecret");
+//!
+//!     let salt = SaltString::from_b64("c29tZXNhbHQ").unwrap();
+//!     let hash = hasher.hash_password(b"P@ssw0rd", &salt).unwrap().to_string();
+//!
+//!     let parsed = PasswordHash::new(&hash).unwrap();
+//!     let mut verifier = Verifier::default();
+//!     verifier.with_secret_key("somesecret");
+//!     assert!(verifier.verify_password(b"P@ssw0rd", &parsed).is_ok());
+//!     assert!(verifier.verify_password(b"wrong", &parsed).is_err());
+//! }
+//! ```
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+
+use password_hash;
+use password_hash::errors::{B64Error, InvalidValue};
+use password_hash::{Decimal, Ident, Output, ParamsString};
+
+pub use password_hash::{
+    PasswordHash, PasswordHashString, PasswordHasher, PasswordVerifier, Salt, SaltString,
+};
+
+use config::{Inner, Variant, Version};
+use output::HashRaw;
+use {Error, ErrorKind, Hasher, Verifier};
+
+/// Struct representing the Argon2 parameters of a [`PasswordHash`](struct.PasswordHash.html),
+/// i.e. the [`Params`](trait.PasswordHasher.html#associatedtype.Params) of the
+/// [`PasswordHasher`](trait.PasswordHasher.html) implementation for
+/// [`Hasher`](../struct.Hasher.html).
+///
+/// Parameters that are `None` are taken from the [`Hasher`](../struct.Hasher.html)'s
+/// configuration, so the default [`Params`](struct.Params.html) (which is what
+/// [`hash_password`](trait.PasswordHasher.html#method.hash_password) uses) hash with the
+/// [`Hasher`](../struct.Hasher.html) exactly as it has been configured. To customize them,
+/// start from [`Params::default()`](struct.Params.html#impl-Default) and set the fields you
+/// need
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Params {
+    /// The `data` parameter of the hash the parameters were read from, if any, e.g. the
+    /// fingerprints of a bound hash (see
+    /// [`configure_binding`](../struct.Hasher.html#method.configure_binding))
+    pub(crate) data: Option<Vec<u8>>,
+
+    /// Hash length in bytes (the length of the hash output)
+    pub hash_len: Option<u32>,
+
+    /// The inner legacy scheme of an onion hash (the `inner` parameter, see
+    /// [`configure_inner_scheme`](../struct.Hasher.html#method.configure_inner_scheme)). If
+    /// set, the password is hashed as a legacy digest of its scheme; the cost and salt of a
+    /// bcrypt [`Inner`](../config/enum.Inner.html) are taken from the digest itself
+    pub inner: Option<Inner>,
+
+    /// Iterations (the `t` parameter)
+    pub iterations: Option<u32>,
+
+    /// Lanes (the `p` parameter)
+    pub lanes: Option<u32>,
+
+    /// Memory size in kibibytes (the `m` parameter)
+    pub memory_size: Option<u32>,
+}
+
+impl<'a> TryFrom<&'a PasswordHash<'a>> for Params {
+    type Error = password_hash::Error;
+
+    fn try_from(hash: &'a PasswordHash<'a>) -> Result<Params, password_hash::Error> {
+        let mut params = Params::default();
+        for (name, value) in hash.params.iter() {
+            match name.as_str() {
+                "m" => params.memory_size = Some(value.decimal()?),
+                "t" => params.iterations = Some(value.decimal()?),
+                "p" => params.lanes = Some(value.decimal()?),
+                "data" => {
+                    let mut buf = [0u8; 64];
+                    params.data = Some(value.b64_decode(&mut buf)?.to_vec());
+                }
+                "inner" => {
+                    params.inner = Some(value.as_str().parse::<Inner>().map_err(|_| {
+                        password_hash::Error::ParamValueInvalid(InvalidValue::Malformed)
+                    })?)
+                }
+                _ => return Err(password_hash::Error::ParamNameInvalid),
+            }
+        }
+        if params.memory_size.is_none() || params.iterations.is_none() || params.lanes.is_none() {
+            return Err(password_hash::Error::PhcStringField);
+        }
+        params.hash_len = hash.hash.as_ref().map(|output| output.len() as u32);
+        Ok(params)
+    }
+}
+
+impl TryFrom<Params> for ParamsString {
+    type Error = password_hash::Error;
+
+    fn try_from(params: Params) -> Result<ParamsString, password_hash::Error> {
+        let mut params_string = ParamsString::new();
+        if let Some(memory_size) = params.memory_size {
+            params_string.add_decimal("m", memory_size)?;
+        }
+        if let Some(iterations) = params.iterations {
+            params_string.add_decimal("t", iterations)?;
+        }
+        if let Some(lanes) = params.lanes {
+            params_string.add_decimal("p", lanes)?;
+        }
+        if let Some(ref inner) = params.inner {
+            params_string.add_str("inner", inner.to_string().as_str())?;
+        }
+        if let Some(ref data) = params.data {
+            params_string.add_b64_bytes("data", data)?;
+        }
+        Ok(params_string)
+    }
+}
+
+impl<'a> PasswordHasher for Hasher<'a> {
+    type Params = Params;
+
+    fn hash_password_customized<'b>(
+        &self,
+        password: &[u8],
+        algorithm: Option<Ident<'b>>,
+        version: Option<Decimal>,
+        params: Params,
+        salt: impl Into<Salt<'b>>,
+    ) -> Result<PasswordHash<'b>, password_hash::Error> {
+        let salt = salt.into();
+        let mut salt_buf = [0u8; Salt::MAX_LENGTH];
+        let salt_bytes = salt.decode_b64(&mut salt_buf)?;
+
+        let mut hasher: Hasher = self.to_owned();
+        if let Some(algorithm) = algorithm {
+            let variant = algorithm
+                .as_str()
+                .parse::<Variant>()
+                .map_err(|_| password_hash::Error::Algorithm)?;
+            hasher.configure_variant(variant);
+        }
+        if let Some(version) = version {
+            let version = Version::from_u32(version).map_err(|_| password_hash::Error::Version)?;
+            hasher.configure_version(version);
+        }
+        if let Some(hash_len) = params.hash_len {
+            hasher.configure_hash_len(hash_len);
+        }
+        if let Some(iterations) = params.iterations {
+            hasher.configure_iterations(iterations);
+        }
+        if let Some(lanes) = params.lanes {
+            hasher.configure_lanes(lanes);
+        }
+        if let Some(memory_size) = params.memory_size {
+            hasher.configure_memory_size(memory_size);
+        }
+        if let Some(ref inner) = params.inner {
+            hasher.configure_inner_scheme(Some(inner.scheme()));
+        }
+        let hash_raw = hasher
+            .configure_password_clearing(false)
+            .with_password(password)
+            .with_salt(salt_bytes)
+            .hash_raw()?;
+
+        let params = Params {
+            data: hash_raw.data().map(<[u8]>::to_vec),
+            hash_len: None,
+            inner: hash_raw.inner().cloned(),
+            iterations: Some(hash_raw.iterations()),
+            lanes: Some(hash_raw.lanes()),
+            memory_size: Some(hash_raw.memory_size()),
+        };
+        Ok(PasswordHash {
+            algorithm: Ident::new(hash_raw.variant().as_str())?,
+            version: Some(hash_raw.version() as Decimal),
+            params: ParamsString::try_from(params)?,
+            salt: Some(salt),
+            hash: Some(Output::new(hash_raw.raw_hash_bytes())?),
+        })
+    }
+}
+
+impl<'a> PasswordVerifier for Verifier<'a> {
+    /// Verifies the password against the hash the same way
+    /// [`verify`](../struct.Verifier.html#method.verify) does, i.e. with the parameters, inner
+    /// legacy scheme and binding recorded in the hash, and without checking the password
+    /// against a password policy or breach lookup
+    fn verify_password(
+        &self,
+        password: &[u8],
+        hash: &PasswordHash,
+    ) -> Result<(), password_hash::Error> {
+        let hash_raw = HashRaw::try_from(hash)?;
+        let mut verifier: Verifier = self.to_owned();
+        let is_valid = verifier
+            .configure_password_clearing(false)
+            .with_hash_raw(&hash_raw)
+            .with_password(password)
+            .verify()?;
+        if is_valid {
+            Ok(())
+        } else {
+            Err(password_hash::Error::Password)
+        }
+    }
+}
+
+impl<'a, 'b> TryFrom<&'b PasswordHash<'a>> for HashRaw {
+    type Error = Error;
+
+    /// Converts a [`PasswordHash`](../phc/struct.PasswordHash.html) into a
+    /// [`HashRaw`](struct.HashRaw.html). Fails if the
+    /// [`PasswordHash`](../phc/struct.PasswordHash.html) is not an Argon2 hash with a salt
+    /// and a hash output
+    fn try_from(hash: &'b PasswordHash<'a>) -> Result<HashRaw, Error> {
+        hash.to_string().parse::<HashRaw>()
+    }
+}
+
+impl<'a> TryFrom<&'a HashRaw> for PasswordHashString {
+    type Error = password_hash::Error;
+
+    /// Converts a [`HashRaw`](../output/struct.HashRaw.html) into a
+    /// [`PasswordHashString`](struct.PasswordHashString.html). Fails if the salt or the hash
+    /// are outside of the lengths allowed by the PHC string format
+    fn try_from(hash_raw: &'a HashRaw) -> Result<PasswordHashString, password_hash::Error> {
+        PasswordHashString::new(&hash_raw.to_string())
+    }
+}
+
+impl From<Error> for password_hash::Error {
+    fn from(e: Error) -> password_hash::Error {
+        match e.kind() {
+            ErrorKind::Base64DecodeError => {
+                password_hash::Error::B64Encoding(B64Error::InvalidEncoding)
+            }
+            ErrorKind::HashDecodeError => password_hash::Error::PhcStringField,
+            ErrorKind::HashLenTooShortError => password_hash::Error::OutputSize {
+                provided: Ordering::Less,
+                expected: 4,
+            },
+            ErrorKind::IterationsTooFewError
+            | ErrorKind::LanesTooFewError
+            | ErrorKind::MemorySizeTooSmallError
+            | ErrorKind::ThreadsTooFewError => {
+                password_hash::Error::ParamValueInvalid(InvalidValue::TooShort)
+            }
+            ErrorKind::LanesTooManyError | ErrorKind::ThreadsTooManyError => {
+                password_hash::Error::ParamValueInvalid(InvalidValue::TooLong)
+            }
+            ErrorKind::MemorySizeInvalidError => {
+                password_hash::Error::ParamValueInvalid(InvalidValue::Malformed)
+            }
+            ErrorKind::SaltTooShortError => {
+                password_hash::Error::SaltInvalid(InvalidValue::TooShort)
+            }
+            ErrorKind::SaltTooLongError => password_hash::Error::SaltInvalid(InvalidValue::TooLong),
+            ErrorKind::VariantEncodeError => password_hash::Error::Algorithm,
+            ErrorKind::VersionEncodeError => password_hash::Error::Version,
+            _ => password_hash::Error::Crypto,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hasher() -> Hasher<'static> {
+        let mut hasher = Hasher::default();
+        hasher
+            .configure_iterations(8)
+            .configure_lanes(2)
+            .configure_memory_size(64)
+            .configure_threads(2)
+            .with_secret_key("somesecret");
+        hasher
+    }
+
+    fn verifier() -> Verifier<'static> {
+        let mut verifier = Verifier::default();
+        verifier.with_secret_key("somesecret");
+        verifier
+    }
+
+    #[test]
+    fn test_password_hasher() {
+        let hasher = hasher();
+        let salt = SaltString::from_b64("c29tZXNhbHQ").unwrap();
+        let hash = hasher
+            .hash_password(b"P@ssw0rd", &salt)
+            .unwrap()
+            .to_string();
+
+        let expected = hasher
+            .to_owned()
+            .with_password("P@ssw0rd")
+            .with_salt("somesalt")
+            .hash()
+            .unwrap();
+        assert_eq!(hash, expected);
+
+        let parsed = PasswordHash::new(&hash).unwrap();
+        let verifier = verifier();
+        assert!(verifier.verify_password(b"P@ssw0rd", &parsed).is_ok());
+        assert_eq!(
+            verifier.verify_password(b"wrong", &parsed),
+            Err(password_hash::Error::Password)
+        );
+        assert!(parsed
+            .verify_password(&[&verifier as &dyn PasswordVerifier], "P@ssw0rd")
+            .is_ok());
+        // The blanket implementation for the hasher hashes again and compares
+        assert!(hasher.verify_password(b"P@ssw0rd", &parsed).is_ok());
+
+        // The parameters of the hash (not the hasher's) are used for verification
+        let mut other = hasher.to_owned();
+        other
+            .configure_iterations(16)
+            .configure_variant(Variant::Argon2i);
+        assert!(other.verify_password(b"P@ssw0rd", &parsed).is_ok());
+        let other_hash = other.hash_password(b"P@ssw0rd", &salt).unwrap();
+        assert_eq!(other_hash.algorithm.as_str(), "argon2i");
+        assert_eq!(other_hash.params.get_decimal("t"), Some(16));
+
+        // The secret key is used for verification
+        let mut other = verifier.to_owned();
+        other.with_secret_key("othersecret");
+        assert!(other.verify_password(b"P@ssw0rd", &parsed).is_err());
+    }
+
+    #[test]
+    fn test_password_hasher_customized() {
+        let hasher = hasher();
+        let salt = SaltString::from_b64("c29tZXNhbHQ").unwrap();
+        let params = Params {
+            hash_len: Some(24),
+            iterations: Some(4),
+            lanes: Some(1),
+            memory_size: Some(32),
+            ..Params::default()
+        };
+        let hash = hasher
+            .hash_password_customized(
+                b"P@ssw0rd",
+                Some(Ident::new("argon2d").unwrap()),
+                Some(0x10),
+                params.clone(),
+                &salt,
+            )
+            .unwrap();
+        assert!(hash
+            .to_string()
+            .starts_with("$argon2d$v=16$m=32,t=4,p=1$c29tZXNhbHQ$"));
+        assert_eq!(Params::try_from(&hash).unwrap(), params);
+
+        let err = hasher
+            .hash_password_customized(
+                b"P@ssw0rd",
+                Some(Ident::new("pbkdf2").unwrap()),
+                None,
+                Params::default(),
+                &salt,
+            )
+            .unwrap_err();
+        assert_eq!(err, password_hash::Error::Algorithm);
+
+        let params = Params {
+            iterations: Some(0),
+            ..Params::default()
+        };
+        let err = hasher
+            .hash_password_customized(b"P@ssw0rd", None, None, params, &salt)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            password_hash::Error::ParamValueInvalid(InvalidValue::TooShort)
+        );
+    }
+
+    #[test]
+    fn test_password_hasher_with_password_policy() {
+        use policy::PasswordPolicy;
+
+        let mut hasher = hasher();
+        let salt = SaltString::from_b64("c29tZXNhbHQ").unwrap();
+        let hash = hasher.hash_password(b"short", &salt).unwrap().to_string();
+        let parsed = PasswordHash::new(&hash).unwrap();
+
+        let mut password_policy = PasswordPolicy::new();
+        password_policy.configure_min_len(10);
+        hasher.configure_password_policy(Some(password_policy));
+        assert!(hasher.hash_password(b"short", &salt).is_err());
+        // Parameters read from an existing hash do not bypass the policy
+        let new_salt = SaltString::from_b64("b3RoZXJzYWx0").unwrap();
+        let params = Params::try_from(&parsed).unwrap();
+        assert!(hasher
+            .hash_password_customized(b"short", None, None, params, &new_salt)
+            .is_err());
+
+        // Verifying does not check the policy
+        let verifier = verifier();
+        assert!(verifier.verify_password(b"short", &parsed).is_ok());
+        assert_eq!(
+            verifier.verify_password(b"wrong", &parsed),
+            Err(password_hash::Error::Password)
+        );
+    }
+
+    #[test]
+    fn test_password_verifier_metrics() {
+        use std::sync::{Arc, Mutex};
+
+        use metrics::{Metrics, Operation, Outcome, Sample};
+
+        #[derive(Debug, Default)]
+        struct Collector(Mutex<Vec<Sample>>);
+
+        impl Metrics for Collector {
+            fn record(&self, sample: &Sample) {
+                self.0.lock().unwrap().push(*sample);
+            }
+        }
+
+        let collector = Arc::new(Collector::default());
+        let mut hasher = hasher();
+        hasher.configure_metrics(Some(collector.clone()));
+        let mut verifier = verifier();
+        verifier.configure_metrics(Some(collector.clone()));
+        let salt = SaltString::from_b64("c29tZXNhbHQ").unwrap();
+        let hash = hasher
+            .hash_password(b"P@ssw0rd", &salt)
+            .unwrap()
+            .to_string();
+        let parsed = PasswordHash::new(&hash).unwrap();
+        assert!(verifier.verify_password(b"P@ssw0rd", &parsed).is_ok());
+        assert!(verifier.verify_password(b"wrong", &parsed).is_err());
+
+        let samples = collector.0.lock().unwrap();
+        let summary = samples
+            .iter()
+            .map(|sample| (sample.operation(), sample.outcome()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (Operation::Hash, Outcome::Success),
+                (Operation::Verify, Outcome::Success),
+                (Operation::Verify, Outcome::Mismatch),
+            ]
+        );
+        assert_eq!(samples[1].parameters(), samples[0].parameters());
+    }
+
+    #[test]
+    fn test_password_hasher_data() {
+        let salt = SaltString::from_b64("c29tZXNhbHQ").unwrap();
+        let hash = "$argon2id$v=19$m=64,t=8,p=2,data=c29tZWRhdGE$c29tZXNhbHQ$\
+                    rYR0S4u1lTvIvtrt9IDtzCAplumWAwyYrbwb7hNnbl0";
+        let parsed = PasswordHash::new(hash).unwrap();
+        let params = Params::try_from(&parsed).unwrap();
+        assert_eq!(params.data, Some(b"somedata".to_vec()));
+        assert_eq!(
+            ParamsString::try_from(params.clone()).unwrap(),
+            parsed.params
+        );
+
+        // Hashing creates the data parameter anew rather than copying it
+        let hash = hasher()
+            .hash_password_customized(
+                b"P@ssw0rd",
+                Some(parsed.algorithm),
+                parsed.version,
+                params,
+                &salt,
+            )
+            .unwrap();
+        assert_eq!(hash.params.get("data"), None);
+    }
+
+    #[cfg(feature = "legacy")]
+    #[test]
+    fn test_password_verifier_inner() {
+        use config::InnerScheme;
+        use md5;
+
+        let md5_digest = format!("{:x}", md5::compute(b"P@ssw0rd"));
+        let mut hasher = hasher();
+        hasher.configure_inner_scheme(Some(InnerScheme::Md5));
+        let salt = SaltString::from_b64("c29tZXNhbHQ").unwrap();
+        let hash = hasher
+            .hash_password(md5_digest.as_bytes(), &salt)
+            .unwrap()
+            .to_string();
+        assert!(hash.starts_with("$argon2id$v=19$m=64,t=8,p=2,inner=md5$"));
+        let expected = hasher
+            .to_owned()
+            .with_password(md5_digest.as_str())
+            .with_salt("somesalt")
+            .hash()
+            .unwrap();
+        assert_eq!(hash, expected);
+
+        let parsed = PasswordHash::new(&hash).unwrap();
+        let params = Params::try_from(&parsed).unwrap();
+        assert_eq!(params.inner, Some(Inner::Md5));
+        assert_eq!(
+            ParamsString::try_from(params.clone()).unwrap(),
+            parsed.params
+        );
+        let verifier = verifier();
+        assert!(verifier.verify_password(b"P@ssw0rd", &parsed).is_ok());
+        assert_eq!(
+            verifier.verify_password(b"wrong", &parsed),
+            Err(password_hash::Error::Password)
+        );
+        let hash = Hasher::default()
+            .configure_iterations(8)
+            .configure_lanes(2)
+            .configure_memory_size(64)
+            .configure_threads(2)
+            .with_secret_key("somesecret")
+            .hash_password_customized(md5_digest.as_bytes(), None, None, params, &salt)
+            .unwrap();
+        assert_eq!(hash, parsed);
+
+        let bcrypt = "$argon2id$v=19$m=64,t=8,p=2,inner=bcrypt-04-5LvzRHSQAsu3ifo92tiMse\
+                      $c29tZXNhbHQ$rYR0S4u1lTvIvtrt9IDtzCAplumWAwyYrbwb7hNnbl0";
+        let parsed = PasswordHash::new(bcrypt).unwrap();
+        let params = Params::try_from(&parsed).unwrap();
+        assert_eq!(
+            ParamsString::try_from(params).unwrap().get_str("inner"),
+            Some("bcrypt-04-5LvzRHSQAsu3ifo92tiMse")
+        );
+    }
+
+    #[cfg(feature = "binding")]
+    #[test]
+    fn test_password_hasher_binding() {
+        use keys::KeyFile;
+
+        let key = KeyFile::new("key", "secret key, long enough for binding".into(), 1).unwrap();
+        let mut hasher = hasher();
+        hasher
+            .configure_binding(true)
+            .with_additional_data("additional data")
+            .with_key_file(&key);
+        let salt = SaltString::from_b64("c29tZXNhbHQ").unwrap();
+        let hash = hasher
+            .hash_password(b"P@ssw0rd", &salt)
+            .unwrap()
+            .to_string();
+        assert!(hash.contains(",data="));
+        let expected = hasher
+            .to_owned()
+            .with_password("P@ssw0rd")
+            .with_salt("somesalt")
+            .hash()
+            .unwrap();
+        assert_eq!(hash, expected);
+
+        let parsed = PasswordHash::new(&hash).unwrap();
+        let mut verifier = Verifier::default();
+        verifier
+            .with_additional_data("additional data")
+            .with_key_file(&key);
+        assert!(verifier.verify_password(b"P@ssw0rd", &parsed).is_ok());
+        assert_eq!(
+            verifier.verify_password(b"wrong", &parsed),
+            Err(password_hash::Error::Password)
+        );
+        verifier.with_additional_data("wrong additional data");
+        assert!(verifier.verify_password(b"P@ssw0rd", &parsed).is_err());
+
+        // Hashing with the parameters of an existing hash binds the new hash to its own salt
+        let new_salt = SaltString::from_b64("b3RoZXJzYWx0").unwrap();
+        let rehashed = hasher
+            .hash_password_customized(
+                b"P@ssw0rd",
+                None,
+                None,
+                Params::try_from(&parsed).unwrap(),
+                &new_salt,
+            )
+            .unwrap();
+        assert_ne!(rehashed.params.get("data"), parsed.params.get("data"));
+        verifier.with_additional_data("additional data");
+        assert!(verifier.verify_password(b"P@ssw0rd", &rehashed).is_ok());
+    }
+
+    #[test]
+    fn test_hash_raw_conversions() {
+        let hash = "$argon2id$v=19$m=4096,t=128,p=2$c29tZXNhbHQ$WwD2/wGGTuw7u4BW8sLM0Q";
+        let hash_raw = hash.parse::<HashRaw>().unwrap();
+
+        let password_hash_string = PasswordHashString::try_from(&hash_raw).unwrap();
+        assert_eq!(password_hash_string.as_str(), hash);
+
+        let password_hash = password_hash_string.password_hash();
+        assert_eq!(HashRaw::try_from(&password_hash).unwrap(), hash_raw);
+
+        let password_hash =
+            PasswordHash::new("$pbkdf2-sha256$i=1000$c29tZXNhbHQ$c29tZWxvbmdoYXNo").unwrap();
+        let err = HashRaw::try_from(&password_hash).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::HashDecodeError);
+    }
+}
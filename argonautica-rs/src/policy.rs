@@ -0,0 +1,373 @@
+//! Password policy and strength checking.
+//!
+//! A [`PasswordPolicy`](struct.PasswordPolicy.html) describes which passwords you are willing
+//! to accept, e.g. a minimum and maximum length, required character classes, a blocklist of
+//! common passwords and a minimum entropy estimate. If you configure a
+//! [`Hasher`](../struct.Hasher.html) with a policy (see
+//! [`configure_password_policy`](../struct.Hasher.html#method.configure_password_policy)), every
+//! password is checked against it before it is hashed, and hashing a password that violates the
+//! policy returns an [`Error`](../struct.Error.html) of kind
+//! [`PasswordPolicyError`](../enum.ErrorKind.html#variant.PasswordPolicyError), which lists
+//! every rule the password failed. Hashers without a policy are not affected.
+//!
+//! ```
+//! extern crate argonautica;
+//!
+//! use argonautica::policy::{CharacterClasses, PasswordPolicy, PolicyViolations};
+//! use argonautica::{ErrorKind, Hasher};
+//!
+//! fn main() {
+//!     let mut policy = PasswordPolicy::default();
+//!     policy
+//!         .configure_min_len(10)
+//!         .configure_required_classes(CharacterClasses::LOWERCASE | CharacterClasses::DIGIT)
+//!         .configure_blocklist(vec!["password123"]);
+//!
+//!     let mut hasher = Hasher::default();
+//!     hasher
+//!         .configure_password_policy(Some(policy))
+//!         .opt_out_of_secret_key(true);
+//!
+//!     let err = hasher.with_password("Password123").hash().unwrap_err();
+//!     assert_eq!(
+//!         err.kind(),
+//!         ErrorKind::PasswordPolicyError(PolicyViolations::BLOCKLISTED)
+//!     );
+//! }
+//! ```
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::sync::Arc;
+
+use {Error, ErrorKind};
+
+const DEFAULT_MIN_LEN: u32 = 8;
+
+bitflags! {
+    /// Set of character classes, used to configure which classes a password must contain
+    /// (see [`configure_required_classes`](struct.PasswordPolicy.html#method.configure_required_classes))
+    #[derive(Default)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub struct CharacterClasses: u32 {
+        /// ASCII lowercase letters (`a` to `z`)
+        const LOWERCASE = 0b0001;
+        /// ASCII uppercase letters (`A` to `Z`)
+        const UPPERCASE = 0b0010;
+        /// ASCII digits (`0` to `9`)
+        const DIGIT = 0b0100;
+        /// ASCII punctuation and the space character
+        const SYMBOL = 0b1000;
+    }
+}
+
+bitflags! {
+    /// Set of the rules of a [`PasswordPolicy`](struct.PasswordPolicy.html) that a password
+    /// failed. Carried by [`ErrorKind::PasswordPolicyError`](../enum.ErrorKind.html#variant.PasswordPolicyError)
+    #[derive(Default)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub struct PolicyViolations: u32 {
+        /// The password has fewer characters than the minimum length
+        const TOO_SHORT = 0b0000_0001;
+        /// The password has more characters than the maximum length
+        const TOO_LONG = 0b0000_0010;
+        /// The password contains no lowercase letter, but one is required
+        const MISSING_LOWERCASE = 0b0000_0100;
+        /// The password contains no uppercase letter, but one is required
+        const MISSING_UPPERCASE = 0b0000_1000;
+        /// The password contains no digit, but one is required
+        const MISSING_DIGIT = 0b0001_0000;
+        /// The password contains no symbol, but one is required
+        const MISSING_SYMBOL = 0b0010_0000;
+        /// The password is on the blocklist
+        const BLOCKLISTED = 0b0100_0000;
+        /// The estimated entropy of the password is below the minimum
+        const LOW_ENTROPY = 0b1000_0000;
+    }
+}
+
+impl Default for PasswordPolicy {
+    /// Same as the [`new`](struct.PasswordPolicy.html#method.new) method
+    fn default() -> PasswordPolicy {
+        PasswordPolicy {
+            blocklist: Arc::new(HashSet::new()),
+            max_len: None,
+            min_entropy: None,
+            min_len: DEFAULT_MIN_LEN,
+            required_classes: CharacterClasses::empty(),
+        }
+    }
+}
+
+/// Struct describing the passwords a [`Hasher`](../struct.Hasher.html) accepts
+#[derive(Clone, Debug)]
+pub struct PasswordPolicy {
+    blocklist: Arc<HashSet<String>>,
+    max_len: Option<u32>,
+    min_entropy: Option<f64>,
+    min_len: u32,
+    required_classes: CharacterClasses,
+}
+
+impl PasswordPolicy {
+    /// Creates a new [`PasswordPolicy`](struct.PasswordPolicy.html) with the following rules:
+    /// * `blocklist`: empty
+    /// * `max_len`: `None`
+    /// * `min_entropy`: `None`
+    /// * `min_len`: `8` characters
+    /// * `required_classes`: none
+    pub fn new() -> PasswordPolicy {
+        PasswordPolicy::default()
+    }
+    /// Adds the provided passwords to the blocklist. Passwords are compared case-insensitively
+    pub fn configure_blocklist<I, S>(&mut self, passwords: I) -> &mut PasswordPolicy
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        {
+            let blocklist = Arc::make_mut(&mut self.blocklist);
+            for password in passwords {
+                blocklist.insert(password.as_ref().to_lowercase());
+            }
+        }
+        self
+    }
+    /// Allows you to configure a maximum length (in characters). The default is `None`
+    pub fn configure_max_len(&mut self, max_len: Option<u32>) -> &mut PasswordPolicy {
+        self.max_len = max_len;
+        self
+    }
+    /// Allows you to configure a minimum estimated entropy (in bits, see
+    /// [`estimate_entropy`](struct.PasswordPolicy.html#method.estimate_entropy)). The default
+    /// is `None`
+    pub fn configure_min_entropy(&mut self, min_entropy: Option<f64>) -> &mut PasswordPolicy {
+        self.min_entropy = min_entropy;
+        self
+    }
+    /// Allows you to configure a minimum length (in characters). The default is `8`
+    pub fn configure_min_len(&mut self, min_len: u32) -> &mut PasswordPolicy {
+        self.min_len = min_len;
+        self
+    }
+    /// Allows you to configure the character classes a password must contain at least one
+    /// character of. The default is none
+    pub fn configure_required_classes(&mut self, classes: CharacterClasses) -> &mut PasswordPolicy {
+        self.required_classes = classes;
+        self
+    }
+    /// Adds the passwords listed in a local file (one per line) to the blocklist. Empty lines
+    /// and lines starting with `#` are ignored
+    pub fn load_blocklist<P>(&mut self, path: P) -> Result<&mut PasswordPolicy, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| {
+            Error::new(ErrorKind::IoError).add_context(format!("{}: {}", path.display(), e))
+        })?;
+        let mut passwords = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(|e| {
+                Error::new(ErrorKind::IoError).add_context(format!("{}: {}", path.display(), e))
+            })?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            passwords.push(line.to_string());
+        }
+        Ok(self.configure_blocklist(passwords))
+    }
+    /// Checks the password against every rule, returning an [`Error`](../struct.Error.html)
+    /// of kind [`PasswordPolicyError`](../enum.ErrorKind.html#variant.PasswordPolicyError)
+    /// listing the rules it failed, if any
+    pub fn check(&self, password: &str) -> Result<(), Error> {
+        let violations = self.violations(password);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::new(ErrorKind::PasswordPolicyError(violations)))
+        }
+    }
+    /// Returns a crude estimate of the entropy of the password (in bits), computed as the
+    /// number of characters times the base 2 logarithm of the size of the character pool the
+    /// password draws from (26 for lowercase letters, 26 for uppercase letters, 10 for digits,
+    /// 33 for symbols and 100 for non-ASCII characters). The estimate does not detect
+    /// patterns or dictionary words; use the blocklist for those
+    pub fn estimate_entropy(password: &str) -> f64 {
+        let mut pool = 0u32;
+        let classes = classes_of(password);
+        if classes.contains(CharacterClasses::LOWERCASE) {
+            pool += 26;
+        }
+        if classes.contains(CharacterClasses::UPPERCASE) {
+            pool += 26;
+        }
+        if classes.contains(CharacterClasses::DIGIT) {
+            pool += 10;
+        }
+        if classes.contains(CharacterClasses::SYMBOL) {
+            pool += 33;
+        }
+        if !password.is_ascii() {
+            pool += 100;
+        }
+        if pool == 0 {
+            return 0.0;
+        }
+        password.chars().count() as f64 * f64::from(pool).log2()
+    }
+    /// Returns the set of rules the password failed (empty if it satisfies the policy)
+    pub fn violations(&self, password: &str) -> PolicyViolations {
+        let mut violations = PolicyViolations::empty();
+        let len = password.chars().count();
+        if len < self.min_len as usize {
+            violations |= PolicyViolations::TOO_SHORT;
+        }
+        if let Some(max_len) = self.max_len {
+            if len > max_len as usize {
+                violations |= PolicyViolations::TOO_LONG;
+            }
+        }
+        let missing = self.required_classes - classes_of(password);
+        if missing.contains(CharacterClasses::LOWERCASE) {
+            violations |= PolicyViolations::MISSING_LOWERCASE;
+        }
+        if missing.contains(CharacterClasses::UPPERCASE) {
+            violations |= PolicyViolations::MISSING_UPPERCASE;
+        }
+        if missing.contains(CharacterClasses::DIGIT) {
+            violations |= PolicyViolations::MISSING_DIGIT;
+        }
+        if missing.contains(CharacterClasses::SYMBOL) {
+            violations |= PolicyViolations::MISSING_SYMBOL;
+        }
+        if self.blocklist.contains(&password.to_lowercase()) {
+            violations |= PolicyViolations::BLOCKLISTED;
+        }
+        if let Some(min_entropy) = self.min_entropy {
+            if PasswordPolicy::estimate_entropy(password) < min_entropy {
+                violations |= PolicyViolations::LOW_ENTROPY;
+            }
+        }
+        violations
+    }
+    #[allow(missing_docs)]
+    pub fn max_len(&self) -> Option<u32> {
+        self.max_len
+    }
+    #[allow(missing_docs)]
+    pub fn min_entropy(&self) -> Option<f64> {
+        self.min_entropy
+    }
+    #[allow(missing_docs)]
+    pub fn min_len(&self) -> u32 {
+        self.min_len
+    }
+    #[allow(missing_docs)]
+    pub fn required_classes(&self) -> CharacterClasses {
+        self.required_classes
+    }
+}
+
+fn classes_of(password: &str) -> CharacterClasses {
+    let mut classes = CharacterClasses::empty();
+    for c in password.chars() {
+        if c.is_ascii_lowercase() {
+            classes |= CharacterClasses::LOWERCASE;
+        } else if c.is_ascii_uppercase() {
+            classes |= CharacterClasses::UPPERCASE;
+        } else if c.is_ascii_digit() {
+            classes |= CharacterClasses::DIGIT;
+        } else if c.is_ascii_punctuation() || c == ' ' {
+            classes |= CharacterClasses::SYMBOL;
+        }
+    }
+    classes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_policy_violations() {
+        let mut policy = PasswordPolicy::default();
+        policy
+            .configure_max_len(Some(16))
+            .configure_min_entropy(Some(40.0))
+            .configure_required_classes(CharacterClasses::all())
+            .configure_blocklist(vec!["Summer2018!"]);
+
+        assert_eq!(
+            policy.violations("abc"),
+            PolicyViolations::TOO_SHORT
+                | PolicyViolations::MISSING_UPPERCASE
+                | PolicyViolations::MISSING_DIGIT
+                | PolicyViolations::MISSING_SYMBOL
+                | PolicyViolations::LOW_ENTROPY
+        );
+        assert_eq!(
+            policy.violations("summer2018!"),
+            PolicyViolations::MISSING_UPPERCASE | PolicyViolations::BLOCKLISTED
+        );
+        assert_eq!(
+            policy.violations("Tr0ub4dor&3-Tr0ub4dor&3"),
+            PolicyViolations::TOO_LONG
+        );
+        assert!(policy.violations("Tr0ub4dor&3").is_empty());
+        assert!(policy.check("Tr0ub4dor&3").is_ok());
+
+        let err = policy.check("summer2018!").unwrap_err();
+        assert_eq!(
+            err.kind(),
+            ErrorKind::PasswordPolicyError(
+                PolicyViolations::MISSING_UPPERCASE | PolicyViolations::BLOCKLISTED
+            )
+        );
+        assert!(!err.to_string().contains("summer2018!"));
+    }
+
+    #[test]
+    fn test_policy_entropy() {
+        assert_eq!(PasswordPolicy::estimate_entropy(""), 0.0);
+        assert_eq!(PasswordPolicy::estimate_entropy("aaaa"), 4.0 * 26f64.log2());
+        assert_eq!(PasswordPolicy::estimate_entropy("aA1!"), 4.0 * 95f64.log2());
+    }
+
+    #[test]
+    fn test_policy_load_blocklist() {
+        let path = ::std::env::temp_dir().join(format!(
+            "argonautica-blocklist-{}.txt",
+            ::std::process::id()
+        ));
+        {
+            let mut file = File::create(&path).unwrap();
+            writeln!(file, "# common passwords\n\n123456\nQwerty\n").unwrap();
+        }
+        let mut policy = PasswordPolicy::default();
+        policy.configure_min_len(1).load_blocklist(&path).unwrap();
+        ::std::fs::remove_file(&path).unwrap();
+        assert_eq!(policy.violations("qwerty"), PolicyViolations::BLOCKLISTED);
+        assert_eq!(policy.violations("123456"), PolicyViolations::BLOCKLISTED);
+        assert!(policy.violations("# common passwords").is_empty());
+
+        let err = policy.load_blocklist(&path).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::IoError);
+    }
+
+    #[test]
+    fn test_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<PasswordPolicy>();
+    }
+
+    #[test]
+    fn test_sync() {
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<PasswordPolicy>();
+    }
+}
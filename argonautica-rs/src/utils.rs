@@ -2,7 +2,7 @@
 //! [`SecretKey`](input/struct.SecretKey.html)s, for example.
 use base64;
 use rand::rngs::OsRng;
-use rand::RngCore;
+use rand::{CryptoRng, RngCore};
 
 use {Error, ErrorKind};
 
@@ -16,6 +16,19 @@ pub fn generate_random_bytes(len: u32) -> Result<Vec<u8>, Error> {
     Ok(bytes)
 }
 
+/// Same as [`generate_random_bytes`](fn.generate_random_bytes.html) except the bytes are
+/// generated using the provided cryptographically-secure random number generator instead of
+/// `OsRng`
+pub fn generate_random_bytes_with_rng<R>(rng: &mut R, len: u32) -> Result<Vec<u8>, Error>
+where
+    R: CryptoRng + RngCore + ?Sized,
+{
+    let mut bytes = vec![0u8; len as usize];
+    rng.try_fill_bytes(&mut bytes)
+        .map_err(|e| Error::new(ErrorKind::RngError).add_context(format!("{}", e)))?;
+    Ok(bytes)
+}
+
 /// A utility function for generating a cryptographically-secure, random, base64-encoded string
 /// based on
 /// [standard base64 encoding](https://docs.rs/base64/0.9.1/base64/constant.STANDARD.html).
@@ -1,10 +1,25 @@
+use std::sync::Arc;
+use std::time::Instant;
+
 use futures::Future;
 use futures_cpupool::CpuPool;
+use scopeguard;
 
 use backend::decode_rust;
-use config::{default_cpu_pool, Backend, VerifierConfig};
-use input::{AdditionalData, Password, SecretKey};
-use output::HashRaw;
+#[cfg(feature = "binding")]
+use binding;
+#[cfg(feature = "normalization")]
+use config::Normalization;
+#[cfg(feature = "settings")]
+use config::Settings;
+use config::{
+    default_cpu_pool, validate_backend, validate_threads, Backend, HasherConfig, Inner,
+    ValidationReport, VerifierConfig,
+};
+use input::{AdditionalData, Password, Salt, SecretKey};
+use keys::{KeyFile, KeySet};
+use metrics::{self, Metrics, Operation, Parameters, Recorder};
+use output::{HashRaw, Verification};
 use {Error, ErrorKind, Hasher};
 
 impl Default for Hash {
@@ -28,6 +43,7 @@ impl<'a> Default for Verifier<'a> {
         Verifier {
             hash: Hash::default(),
             hasher: Hasher::default(),
+            rehash_config: None,
         }
     }
 }
@@ -38,7 +54,9 @@ impl<'a> Default for Verifier<'a> {
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Verifier<'a> {
     hash: Hash,
-    hasher: Hasher<'a>,
+    pub(crate) hasher: Hasher<'a>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing, skip_deserializing))]
+    rehash_config: Option<HasherConfig>,
 }
 
 impl<'a> Verifier<'a> {
@@ -48,6 +66,8 @@ impl<'a> Verifier<'a> {
     ///     * with threads equal to the number of logical cores on your machine
     ///     * that is lazily created, i.e. created only if / when you call the method that
     ///       needs it ([`verify_non_blocking`](struct.Verifier.html#method.verify_non_blocking))
+    /// * `metrics`: `None`
+    /// * `normalization`: `None`
     /// * `password_clearing`: `false`
     /// * `secret_key_clearing`: `false`
     /// * `threads`: The number of logical cores on your machine
@@ -76,6 +96,28 @@ impl<'a> Verifier<'a> {
         self.hasher.config.set_cpu_pool(cpu_pool);
         self
     }
+    /// Allows you to configure [`Verifier`](struct.Verifier.html) to report every verification,
+    /// with its duration, outcome and the parameters decoded from the hash, to an
+    /// implementation of [`Metrics`](metrics/trait.Metrics.html) (see the
+    /// [`metrics`](metrics/index.html) module). The default is `None`, i.e. nothing is reported
+    pub fn configure_metrics(&mut self, metrics: Option<Arc<dyn Metrics>>) -> &mut Verifier<'a> {
+        self.hasher.config.set_metrics(metrics);
+        self
+    }
+    /// Allows you to configure [`Verifier`](struct.Verifier.html) to apply a Unicode
+    /// [`Normalization`](config/enum.Normalization.html) to the password before verifying it
+    /// (requires the `normalization` feature). Use the same normalization that the
+    /// [`Hasher`](struct.Hasher.html) that created your hashes was configured with (see
+    /// [`configure_normalization`](struct.Hasher.html#method.configure_normalization)). The
+    /// default is `None`, i.e. the password bytes are verified as they are
+    #[cfg(feature = "normalization")]
+    pub fn configure_normalization(
+        &mut self,
+        normalization: Option<Normalization>,
+    ) -> &mut Verifier<'a> {
+        self.hasher.config.set_normalization(normalization);
+        self
+    }
     /// Allows you to configure [`Verifier`](struct.Verifier.html) to erase the password bytes
     /// after each call to [`verify`](struct.Verifier.html#method.verify)
     /// or its non-blocking equivalent. The default is to <b>not</b> clear out the password
@@ -88,6 +130,18 @@ impl<'a> Verifier<'a> {
         self.hasher.config.set_password_clearing(boolean);
         self
     }
+    /// Allows you to configure [`Verifier`](struct.Verifier.html) with the
+    /// [`HasherConfig`](config/struct.HasherConfig.html) your [`Hasher`](struct.Hasher.html)
+    /// currently uses (see [`Hasher::config`](struct.Hasher.html#method.config)), so that
+    /// [`verify_detailed`](struct.Verifier.html#method.verify_detailed) and
+    /// [`verify_with_key_set`](struct.Verifier.html#method.verify_with_key_set) can recommend
+    /// rehashing passwords whose hashes were created with different parameters (see
+    /// [`Hasher::needs_rehash`](struct.Hasher.html#method.needs_rehash); the salt length is not
+    /// compared). The default is `None`, i.e. parameters never cause a rehash to be recommended
+    pub fn configure_rehash_config(&mut self, config: Option<HasherConfig>) -> &mut Verifier<'a> {
+        self.rehash_config = config;
+        self
+    }
     /// Allows you to configure [`Verifier`](struct.Verifier.html) to erase the secret key bytes
     /// after each call to [`verify`](struct.Verifier.html#method.verify)
     /// or its non-blocking equivalent. The default is to <b>not</b> clear out the secret key
@@ -108,6 +162,28 @@ impl<'a> Verifier<'a> {
         self.hasher.config.set_threads(threads);
         self
     }
+    /// Allows you to configure [`Verifier`](struct.Verifier.html) wholesale with validated
+    /// [`Settings`](config/struct.Settings.html), e.g. the same ones your
+    /// [`Hasher`](struct.Hasher.html) is configured with (requires the `settings` feature).
+    /// Only the options that apply to verifying are used, i.e. the backend, normalization,
+    /// password and secret key clearing and threads; the Argon2 parameters are always read
+    /// from the hash being verified
+    #[cfg(feature = "settings")]
+    pub fn configure_settings(&mut self, settings: &Settings) -> &mut Verifier<'a> {
+        self.hasher.config.set_backend(settings.backend());
+        #[cfg(feature = "normalization")]
+        self.hasher
+            .config
+            .set_normalization(settings.normalization());
+        self.hasher
+            .config
+            .set_password_clearing(settings.password_clearing());
+        self.hasher
+            .config
+            .set_secret_key_clearing(settings.secret_key_clearing());
+        self.hasher.config.set_threads(settings.threads());
+        self
+    }
     /// Clones the [`Verifier`](struct.Verifier.html), returning a new
     /// [`Verifier`](struct.Verifier.html) with a `static` lifetime. Use this method if you
     /// would like to move a [`Verifier`](struct.Verifier.html) to another thread
@@ -115,8 +191,35 @@ impl<'a> Verifier<'a> {
         Verifier {
             hash: self.hash.clone(),
             hasher: self.hasher.to_owned(),
+            rehash_config: self.rehash_config.clone(),
         }
     }
+    /// Checks everything about the [`Verifier`](struct.Verifier.html) that can be checked
+    /// before a hash and password are provided, i.e. the backend, threads, additional data and
+    /// secret key (e.g. that it is mutable if secret key clearing is on), and returns a
+    /// [`ValidationReport`](config/struct.ValidationReport.html) listing every problem found.
+    /// Call it at startup so that a misconfiguration fails the deploy instead of the first
+    /// call to [`verify`](struct.Verifier.html#method.verify). The Argon2 parameters are not
+    /// checked, since they are read from each hash
+    pub fn validate(&self) -> Result<(), ValidationReport> {
+        let hasher = &self.hasher;
+        let mut report = ValidationReport::new();
+        report.check("backend", validate_backend(hasher.config.backend()));
+        report.check("threads", validate_threads(hasher.config.threads()));
+        if let Some(ref additional_data) = hasher.additional_data {
+            report.check("additional_data", additional_data.validate());
+        }
+        if let Some(ref secret_key) = hasher.secret_key {
+            report.check("secret_key", secret_key.validate());
+            if hasher.config.secret_key_clearing() && !secret_key.is_mutable() {
+                report.push(
+                    Some("secret_key"),
+                    Error::new(ErrorKind::SecretKeyImmutableError),
+                );
+            }
+        }
+        report.into_result(())
+    }
     /// <b><u>The primary method (blocking version)</u></b>
     ///
     /// After you have configured [`Verifier`](struct.Verifier.html) to your liking and provided
@@ -128,49 +231,73 @@ impl<'a> Verifier<'a> {
     ///
     /// call this method to verify that the password matches the hash or
     /// [`HashRaw`](output/struct.HashRaw.html)
+    ///
+    /// If the hash is an onion hash (see [`InnerScheme`](config/enum.InnerScheme.html)), the
+    /// legacy digest of the password is computed first and then verified against the hash.
+    /// If the hash is bound to its secret key and additional data (see
+    /// [`configure_binding`](struct.Hasher.html#method.configure_binding)) and the `binding`
+    /// feature is enabled, a wrong or missing secret key or additional data is reported as an
+    /// [`Error`](struct.Error.html) before any hashing is done, rather than as `false`. A wrong
+    /// secret key is only told apart from wrong additional data if it was provided with its ID
+    /// (see [`with_key_file`](struct.Verifier.html#method.with_key_file))
     pub fn verify(&mut self) -> Result<bool, Error> {
-        match self.hash {
-            Hash::Encoded(ref s) => {
-                let hash_raw = decode_rust(s)?;
-                self.hasher
-                    .config
-                    .set_hash_len(hash_raw.raw_hash_bytes().len() as u32);
-                self.hasher.config.set_iterations(hash_raw.iterations());
-                self.hasher.config.set_lanes(hash_raw.lanes());
-                self.hasher.config.set_memory_size(hash_raw.memory_size());
-                self.hasher.config.set_opt_out_of_secret_key(true);
-                self.hasher.config.set_variant(hash_raw.variant());
-                self.hasher.config.set_version(hash_raw.version());
-                self.hasher.salt = hash_raw.raw_salt_bytes().into();
-                let hash_raw2 = self.hasher.hash_raw()?;
-                let is_valid = if hash_raw.raw_hash_bytes() == hash_raw2.raw_hash_bytes() {
-                    true
-                } else {
-                    false
-                };
-                Ok(is_valid)
-            }
-            Hash::Raw(ref hash_raw) => {
-                self.hasher
-                    .config
-                    .set_hash_len(hash_raw.raw_hash_bytes().len() as u32);
-                self.hasher.config.set_iterations(hash_raw.iterations());
-                self.hasher.config.set_lanes(hash_raw.lanes());
-                self.hasher.config.set_memory_size(hash_raw.memory_size());
-                self.hasher.config.set_opt_out_of_secret_key(true);
-                self.hasher.config.set_variant(hash_raw.variant());
-                self.hasher.config.set_version(hash_raw.version());
-                self.hasher.salt = hash_raw.raw_salt_bytes().into();
-                let hash_raw2 = self.hasher.hash_raw()?;
-                let is_valid = if hash_raw.raw_hash_bytes() == hash_raw2.raw_hash_bytes() {
-                    true
-                } else {
-                    false
-                };
-                Ok(is_valid)
-            }
-            Hash::None => return Err(Error::new(ErrorKind::HashMissingError)),
-        }
+        self.instrumented(
+            |verifier, recorder| {
+                let hash_raw = verifier.decode_hash(recorder)?;
+                verifier.verify_decoded(&hash_raw)
+            },
+            |is_valid| *is_valid,
+        )
+    }
+    /// Same as [`verify`](struct.Verifier.html#method.verify) except it returns a
+    /// [`Verification`](output/struct.Verification.html) instead of a `bool`, which also
+    /// reports the parameters decoded from the hash, whether you should rehash the password
+    /// (see [`configure_rehash_config`](struct.Verifier.html#method.configure_rehash_config))
+    /// and how long verifying took. Convert it into a `bool` if all you need is whether or not
+    /// the password matched
+    pub fn verify_detailed(&mut self) -> Result<Verification, Error> {
+        self.instrumented(
+            |verifier, recorder| {
+                let start = Instant::now();
+                let hash_raw = verifier.decode_hash(recorder)?;
+                let is_valid = verifier.verify_decoded(&hash_raw)?;
+                let rehash_recommended = is_valid && verifier.needs_rehash(&hash_raw);
+                Ok(Verification {
+                    duration: start.elapsed(),
+                    hash_raw,
+                    is_valid,
+                    key_id: if is_valid {
+                        verifier.hasher.key_id.clone()
+                    } else {
+                        None
+                    },
+                    rehash_recommended,
+                })
+            },
+            Verification::is_valid,
+        )
+    }
+    /// Same as [`verify_detailed`](struct.Verifier.html#method.verify_detailed) except that,
+    /// instead of the secret key provided to the [`Verifier`](struct.Verifier.html), it tries
+    /// each key of the provided [`KeySet`](keys/struct.KeySet.html), starting with the current
+    /// one, and reports the ID of the key that matched (see
+    /// [`Verification::key_id`](output/struct.Verification.html#method.key_id)). Rehashing is
+    /// also recommended if the key that matched is not the current one, i.e. after a key
+    /// rotation.
+    ///
+    /// If the hash is bound to its secret key (see
+    /// [`configure_binding`](struct.Hasher.html#method.configure_binding)) and the `binding`
+    /// feature is enabled, only the key the hash was bound to is tried, and a
+    /// [`KeySet`](keys/struct.KeySet.html) without it returns an [`Error`](struct.Error.html) of
+    /// kind [`SecretKeyMismatchError`](enum.ErrorKind.html#variant.SecretKeyMismatchError);
+    /// otherwise a wrong password costs one hash per key. An empty
+    /// [`KeySet`](keys/struct.KeySet.html) returns an [`Error`](struct.Error.html) of kind
+    /// [`SecretKeyMissingError`](enum.ErrorKind.html#variant.SecretKeyMissingError)
+    pub fn verify_with_key_set(&mut self, key_set: &KeySet) -> Result<Verification, Error> {
+        self.instrumented(
+            |verifier, recorder| verifier.verify_with_key_set_recorded(key_set, recorder),
+            Verification::is_valid,
+        )
     }
     /// <b><u>The primary method (non-blocking version)</u></b>
     ///
@@ -179,12 +306,74 @@ impl<'a> Verifier<'a> {
     /// instead of a [`Result`](https://doc.rust-lang.org/std/result/enum.Result.html)
     pub fn verify_non_blocking(&mut self) -> impl Future<Item = bool, Error = Error> {
         let mut verifier = self.to_owned();
+        let queued = Instant::now();
         match verifier.hasher.config.cpu_pool() {
-            Some(cpu_pool) => cpu_pool.spawn_fn(move || verifier.verify()),
+            Some(cpu_pool) => cpu_pool.spawn_fn(move || {
+                metrics::record_queue_wait(&verifier.hasher.config, Operation::Verify, queued);
+                verifier.verify()
+            }),
             None => {
                 let cpu_pool = default_cpu_pool();
                 verifier.hasher.config.set_cpu_pool(cpu_pool.clone());
-                cpu_pool.spawn_fn(move || verifier.verify())
+                cpu_pool.spawn_fn(move || {
+                    metrics::record_queue_wait(&verifier.hasher.config, Operation::Verify, queued);
+                    verifier.verify()
+                })
+            }
+        }
+    }
+    /// Performs the same amount of work as [`verify`](struct.Verifier.html#method.verify)
+    /// would for a hash created with the provided [`HasherConfig`](config/struct.HasherConfig.html),
+    /// but against a random salt instead of a stored hash, and always returns `false` (unless
+    /// it errors). Call this method, with the password the user submitted, when a login
+    /// attempt names a user that does not exist, so that the response takes as long as it
+    /// would have for a real user and does not leak whether the account exists.
+    ///
+    /// The hash length, iterations, lanes, memory size, variant and version are taken from
+    /// `config`; everything else (e.g. threads, password clearing, the secret key and
+    /// additional data) is taken from the [`Verifier`](struct.Verifier.html) itself. The
+    /// inner scheme of `config`, if any, is ignored, i.e. the cost of computing a legacy digest
+    /// is not reproduced
+    pub fn verify_dummy(&mut self, config: &HasherConfig) -> Result<bool, Error> {
+        self.instrumented(
+            |verifier, recorder| {
+                recorder.set_parameters(Parameters::from(config));
+                verifier.hasher.config.set_hash_len(config.hash_len());
+                verifier.hasher.config.set_iterations(config.iterations());
+                verifier.hasher.config.set_lanes(config.lanes());
+                verifier.hasher.config.set_memory_size(config.memory_size());
+                verifier.hasher.config.set_opt_out_of_secret_key(true);
+                verifier.hasher.config.set_variant(config.variant());
+                verifier.hasher.config.set_version(config.version());
+                verifier.hasher.salt = Salt::default();
+                let _ = verifier.hasher.hash_raw_unchecked()?;
+                Ok(false)
+            },
+            |_| false,
+        )
+    }
+    /// Same as [`verify_dummy`](struct.Verifier.html#method.verify_dummy) except it returns a
+    /// [`Future`](https://docs.rs/futures/0.1.21/futures/future/trait.Future.html)
+    /// instead of a [`Result`](https://doc.rust-lang.org/std/result/enum.Result.html)
+    pub fn verify_dummy_non_blocking(
+        &mut self,
+        config: &HasherConfig,
+    ) -> impl Future<Item = bool, Error = Error> {
+        let config = config.clone();
+        let mut verifier = self.to_owned();
+        let queued = Instant::now();
+        match verifier.hasher.config.cpu_pool() {
+            Some(cpu_pool) => cpu_pool.spawn_fn(move || {
+                metrics::record_queue_wait(&verifier.hasher.config, Operation::Verify, queued);
+                verifier.verify_dummy(&config)
+            }),
+            None => {
+                let cpu_pool = default_cpu_pool();
+                verifier.hasher.config.set_cpu_pool(cpu_pool.clone());
+                cpu_pool.spawn_fn(move || {
+                    metrics::record_queue_wait(&verifier.hasher.config, Operation::Verify, queued);
+                    verifier.verify_dummy(&config)
+                })
             }
         }
     }
@@ -219,6 +408,16 @@ impl<'a> Verifier<'a> {
         self.hash = Hash::Raw(hash_raw.clone());
         self
     }
+    /// Allows you to provide [`Verifier`](struct.Verifier.html) with the secret key of the
+    /// [`KeyFile`](keys/struct.KeyFile.html) that was initially used to create the hash, along
+    /// with its ID. For bound hashes (see
+    /// [`configure_binding`](struct.Hasher.html#method.configure_binding)), the ID lets
+    /// [`verify`](struct.Verifier.html#method.verify) tell a wrong secret key apart from wrong
+    /// additional data
+    pub fn with_key_file(&mut self, key_file: &'a KeyFile) -> &mut Verifier<'a> {
+        self.hasher.with_key_file(key_file);
+        self
+    }
     /// Allows you to provide [`Verifier`](struct.Verifier.html) with the password
     /// to verify against
     pub fn with_password<P>(&mut self, password: P) -> &mut Verifier<'a>
@@ -234,7 +433,7 @@ impl<'a> Verifier<'a> {
     where
         SK: Into<SecretKey<'a>>,
     {
-        self.hasher.secret_key = Some(secret_key.into());
+        self.hasher.with_secret_key(secret_key);
         self
     }
     /// Read-only access to the [`Verifier`](struct.Verifier.html)'s
@@ -245,13 +444,7 @@ impl<'a> Verifier<'a> {
     /// Read-only access to the [`Verifier`](struct.Verifier.html)'s
     /// [`VerifierConfig`](config/struct.VerifierConfig.html)
     pub fn config(&self) -> VerifierConfig {
-        VerifierConfig::new(
-            /* backend */ self.hasher.config.backend(),
-            /* cpu_pool */ self.hasher.config.cpu_pool(),
-            /* password_clearing */ self.hasher.config.password_clearing(),
-            /* secret_key_clearing */ self.hasher.config.secret_key_clearing(),
-            /* threads */ self.hasher.config.threads(),
-        )
+        VerifierConfig::new(&self.hasher.config)
     }
     /// Returns the [`Verifier`](struct.Verifier.html)'s string-encoded hash, if any
     pub fn hash(&self) -> Option<String> {
@@ -270,6 +463,11 @@ impl<'a> Verifier<'a> {
             Hash::None => Ok(None),
         }
     }
+    /// Read-only access to the ID of the [`Verifier`](struct.Verifier.html)'s secret key, if it
+    /// was provided through [`with_key_file`](struct.Verifier.html#method.with_key_file)
+    pub fn key_id(&self) -> Option<&str> {
+        self.hasher.key_id()
+    }
     /// Read-only access to the [`Verifier`](struct.Verifier.html)'s
     /// [`Password`](input/struct.Password.html), if any
     pub fn password(&self) -> Option<&Password<'a>> {
@@ -282,6 +480,168 @@ impl<'a> Verifier<'a> {
     }
 }
 
+impl<'a> Verifier<'a> {
+    pub(crate) fn from_hasher(hasher: Hasher<'a>) -> Verifier<'a> {
+        Verifier {
+            hash: Hash::None,
+            hasher,
+            rehash_config: None,
+        }
+    }
+    /// Decodes the hash and sets the parameters of the verification being recorded
+    fn decode_hash(&self, recorder: &mut Recorder) -> Result<HashRaw, Error> {
+        let hash_raw = match self.hash {
+            Hash::Encoded(ref s) => decode_rust(s)?,
+            Hash::Raw(ref hash_raw) => hash_raw.clone(),
+            Hash::None => return Err(Error::new(ErrorKind::HashMissingError)),
+        };
+        recorder.set_parameters(Parameters::from(&hash_raw));
+        Ok(hash_raw)
+    }
+    /// Runs a verification under a `Recorder`; `is_match` tells whether a successful result is
+    /// a match
+    fn instrumented<T, F, M>(&mut self, verify: F, is_match: M) -> Result<T, Error>
+    where
+        F: FnOnce(&mut Verifier<'a>, &mut Recorder) -> Result<T, Error>,
+        M: FnOnce(&T) -> bool,
+    {
+        let mut recorder = Recorder::verify(&self.hasher.config);
+        let result = {
+            let _entered = recorder.enter();
+            verify(self, &mut recorder)
+        };
+        recorder.finish(&result, is_match);
+        result
+    }
+    fn needs_rehash(&self, hash_raw: &HashRaw) -> bool {
+        self.rehash_config
+            .as_ref()
+            .map(|config| config.needs_rehash(hash_raw))
+            .unwrap_or(false)
+    }
+    /// Body of [`verify_with_key_set`](struct.Verifier.html#method.verify_with_key_set)
+    fn verify_with_key_set_recorded(
+        &mut self,
+        key_set: &KeySet,
+        recorder: &mut Recorder,
+    ) -> Result<Verification, Error> {
+        let start = Instant::now();
+        let verifier = scopeguard::guard(self, |verifier| {
+            verifier.hasher.clear();
+        });
+        let hash_raw = verifier.decode_hash(recorder)?;
+        if let Some(ref password) = verifier.hasher.password {
+            if verifier.hasher.config.password_clearing() && !password.is_mutable() {
+                return Err(Error::new(ErrorKind::PasswordImmutableError));
+            }
+        }
+        let current = key_set.current();
+        #[allow(unused_mut)]
+        let mut candidates = current
+            .into_iter()
+            .chain(
+                key_set
+                    .iter()
+                    .filter(|key| Some(key.id()) != current.map(KeyFile::id)),
+            )
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            return Err(
+                Error::new(ErrorKind::SecretKeyMissingError).add_context("The key set is empty")
+            );
+        }
+        #[cfg(feature = "binding")]
+        {
+            if let Some(data) = hash_raw.data() {
+                let salt = hash_raw.raw_salt_bytes();
+                candidates.retain(|key| binding::matches_key_id(data, salt, key.id()));
+                if candidates.is_empty() {
+                    return Err(Error::new(ErrorKind::SecretKeyMismatchError));
+                }
+            }
+        }
+        let mut matched = None;
+        for key in candidates {
+            // The password is cleared once, by the guard above, rather than after each attempt
+            let mut attempt = Verifier::from_hasher(verifier.hasher.reborrow());
+            attempt.hasher.password = verifier.hasher.password.as_ref().map(Password::from);
+            attempt.hasher.with_key_file(key);
+            attempt.hasher.config.set_password_clearing(false);
+            attempt.hasher.config.set_secret_key_clearing(false);
+            if attempt.verify_decoded(&hash_raw)? {
+                matched = Some(key);
+                break;
+            }
+        }
+        let is_valid = matched.is_some();
+        let rehash_recommended = is_valid
+            && (verifier.needs_rehash(&hash_raw)
+                || matched.map(KeyFile::id) != current.map(KeyFile::id));
+        Ok(Verification {
+            duration: start.elapsed(),
+            hash_raw,
+            is_valid,
+            key_id: matched.map(|key| key.id().to_string()),
+            rehash_recommended,
+        })
+    }
+    fn verify_decoded(&mut self, hash_raw: &HashRaw) -> Result<bool, Error> {
+        #[cfg(feature = "binding")]
+        {
+            if let Some(data) = hash_raw.data() {
+                binding::check(
+                    data,
+                    hash_raw.raw_salt_bytes(),
+                    self.hasher.key_id.as_deref(),
+                    self.hasher.secret_key.as_ref(),
+                    self.hasher.additional_data.as_ref(),
+                )?;
+            }
+        }
+        self.hasher
+            .config
+            .set_hash_len(hash_raw.raw_hash_bytes().len() as u32);
+        self.hasher.config.set_iterations(hash_raw.iterations());
+        self.hasher.config.set_lanes(hash_raw.lanes());
+        self.hasher.config.set_memory_size(hash_raw.memory_size());
+        self.hasher.config.set_opt_out_of_secret_key(true);
+        self.hasher.config.set_variant(hash_raw.variant());
+        self.hasher.config.set_version(hash_raw.version());
+        self.hasher.salt = hash_raw.raw_salt_bytes().into();
+        let hash_raw2 = match hash_raw.inner() {
+            Some(inner) => self.hash_raw_inner(inner)?,
+            None => self.hasher.hash_raw_unchecked()?,
+        };
+        let is_valid = if hash_raw.raw_hash_bytes() == hash_raw2.raw_hash_bytes() {
+            true
+        } else {
+            false
+        };
+        Ok(is_valid)
+    }
+    /// Hashes the legacy digest of the password in place of the password, zeroing out the
+    /// digest afterwards
+    fn hash_raw_inner(&mut self, inner: &Inner) -> Result<HashRaw, Error> {
+        let mut verifier = scopeguard::guard(self, |verifier| {
+            verifier.hasher.clear();
+        });
+        let digest = match verifier.hasher.password {
+            Some(ref password) => {
+                password.validate()?;
+                if verifier.hasher.config.password_clearing() && !password.is_mutable() {
+                    return Err(Error::new(ErrorKind::PasswordImmutableError));
+                }
+                inner.digest(password.as_bytes())?
+            }
+            None => return Err(Error::new(ErrorKind::PasswordMissingError)),
+        };
+        let password = verifier.hasher.password.replace(digest.into());
+        let hash_raw = verifier.hasher.hash_raw_unchecked();
+        verifier.hasher.restore_password(password);
+        hash_raw
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -344,6 +704,232 @@ mod tests {
         };
     }
 
+    #[cfg(feature = "binding")]
+    #[test]
+    fn test_verifier_binding() {
+        let key = KeyFile::new("key", "secret key, long enough for binding".into(), 1).unwrap();
+        let other = KeyFile::new(
+            "other",
+            "other secret key, long enough for binding".into(),
+            2,
+        )
+        .unwrap();
+        let mut hasher = Hasher::fast_but_insecure();
+        hasher.with_key_file(&key);
+        let unbound = hasher.with_password("P@ssw0rd").hash().unwrap();
+        assert!(!unbound.contains(",data="));
+        let hash = hasher
+            .configure_binding(true)
+            .with_additional_data("additional data")
+            .with_password("P@ssw0rd")
+            .hash()
+            .unwrap();
+        assert!(hash.contains(",data="));
+        assert!(!hasher.needs_rehash(&hash).unwrap());
+        assert!(hasher.needs_rehash(&unbound).unwrap());
+
+        // Binding is refused without a secret key and its ID
+        hasher.with_secret_key("secret key, long enough for binding");
+        let err = hasher.with_password("P@ssw0rd").hash().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::SecretKeyMissingError);
+        assert!(hasher.validate().is_err());
+
+        // ... and with a secret key that is too short
+        let short = KeyFile::new("short", "short secret key".into(), 3).unwrap();
+        hasher.with_key_file(&short);
+        let err = hasher.with_password("P@ssw0rd").hash().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::SecretKeyTooShortError);
+        assert!(hasher.validate().is_err());
+
+        let verify = |key: Option<&KeyFile>, additional_data: Option<&str>, password| {
+            let mut verifier = Verifier::default();
+            verifier.with_hash(&hash).with_password(password);
+            if let Some(key) = key {
+                verifier.with_key_file(key);
+            }
+            if let Some(additional_data) = additional_data {
+                verifier.with_additional_data(additional_data);
+            }
+            verifier.verify().map_err(|e| e.kind())
+        };
+        let ad = Some("additional data");
+        assert_eq!(verify(Some(&key), ad, "P@ssw0rd"), Ok(true));
+        assert_eq!(verify(Some(&key), ad, "wrong"), Ok(false));
+        assert_eq!(
+            verify(Some(&other), ad, "P@ssw0rd"),
+            Err(ErrorKind::SecretKeyMismatchError)
+        );
+        assert_eq!(
+            verify(None, ad, "P@ssw0rd"),
+            Err(ErrorKind::SecretKeyMissingError)
+        );
+        assert_eq!(
+            verify(Some(&key), Some("wrong"), "P@ssw0rd"),
+            Err(ErrorKind::AdditionalDataMismatchError)
+        );
+        assert_eq!(
+            verify(Some(&key), None, "P@ssw0rd"),
+            Err(ErrorKind::AdditionalDataMissingError)
+        );
+
+        // Without the key ID, only the additional data fingerprint can be checked
+        let mut verifier = Verifier::default();
+        verifier
+            .with_hash(&hash)
+            .with_password("P@ssw0rd")
+            .with_additional_data("additional data");
+        verifier.with_secret_key(key.secret_key());
+        assert!(verifier.verify().unwrap());
+        verifier
+            .with_password("P@ssw0rd")
+            .with_secret_key(other.secret_key());
+        let err = verifier.verify().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::AdditionalDataMismatchError);
+    }
+
+    #[test]
+    fn test_verify_detailed() {
+        let mut hasher = Hasher::fast_but_insecure();
+        hasher.configure_iterations(2);
+        let hash = hasher.with_password("P@ssw0rd").hash().unwrap();
+
+        let mut verifier = Verifier::default();
+        verifier.with_hash(&hash).with_password("P@ssw0rd");
+        let verification = verifier.verify_detailed().unwrap();
+        assert!(verification.is_valid());
+        assert!(!verification.rehash_recommended());
+        assert_eq!(verification.key_id(), None);
+        assert_eq!(verification.hash_raw(), &hash.parse::<HashRaw>().unwrap());
+
+        hasher.configure_iterations(3);
+        verifier
+            .configure_rehash_config(Some(hasher.config().clone()))
+            .with_password("P@ssw0rd");
+        let verification = verifier.verify_detailed().unwrap();
+        assert!(verification.rehash_recommended());
+        assert!(bool::from(verification));
+
+        verifier.with_password("wrong");
+        let verification = verifier.verify_detailed().unwrap();
+        assert!(!verification.is_valid());
+        assert!(!verification.rehash_recommended());
+
+        let key = KeyFile::new("current", "some secret key, long enough".into(), 1).unwrap();
+        let hash = Hasher::fast_but_insecure()
+            .with_key_file(&key)
+            .with_password("P@ssw0rd")
+            .hash()
+            .unwrap();
+        let mut verifier = Verifier::default();
+        verifier
+            .with_hash(&hash)
+            .with_key_file(&key)
+            .with_password("P@ssw0rd");
+        let verification = verifier.verify_detailed().unwrap();
+        assert!(verification.is_valid());
+        assert_eq!(verification.key_id(), Some("current"));
+        verifier.with_password("wrong");
+        let verification = verifier.verify_detailed().unwrap();
+        assert_eq!(verification.key_id(), None);
+    }
+
+    #[test]
+    fn test_verify_with_key_set() {
+        let old = KeyFile::new("old", "old secret key, long enough for binding".into(), 1).unwrap();
+        let new = KeyFile::new("new", "new secret key, long enough for binding".into(), 2).unwrap();
+        let mut hasher = Hasher::fast_but_insecure();
+        #[cfg(feature = "binding")]
+        hasher.configure_binding(true);
+        let hash = hasher
+            .with_password("P@ssw0rd")
+            .with_key_file(&old)
+            .hash()
+            .unwrap();
+
+        let mut key_set = KeySet::new();
+        let mut verifier = Verifier::default();
+        verifier.with_hash(&hash).with_password("P@ssw0rd");
+        let err = verifier.verify_with_key_set(&key_set).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::SecretKeyMissingError);
+
+        key_set.insert(old.clone()).unwrap();
+        let verification = verifier.verify_with_key_set(&key_set).unwrap();
+        assert!(verification.is_valid());
+        assert_eq!(verification.key_id(), Some("old"));
+        assert!(!verification.rehash_recommended());
+
+        key_set.insert(new.clone()).unwrap();
+        verifier
+            .configure_password_clearing(true)
+            .with_password("P@ssw0rd".to_string());
+        let verification = verifier.verify_with_key_set(&key_set).unwrap();
+        assert!(verification.is_valid());
+        assert_eq!(verification.key_id(), Some("old"));
+        assert!(verification.rehash_recommended());
+        assert!(verifier.password().is_none());
+
+        verifier
+            .configure_password_clearing(false)
+            .with_password("wrong");
+        let verification = verifier.verify_with_key_set(&key_set).unwrap();
+        assert!(!verification.is_valid());
+        assert_eq!(verification.key_id(), None);
+
+        #[cfg(feature = "binding")]
+        {
+            let mut key_set = KeySet::new();
+            key_set.insert(new).unwrap();
+            verifier.with_password("P@ssw0rd");
+            let err = verifier.verify_with_key_set(&key_set).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::SecretKeyMismatchError);
+        }
+    }
+
+    #[test]
+    fn test_verifier_validate() {
+        let mut verifier = Verifier::default();
+        assert!(verifier.validate().is_ok());
+        verifier
+            .configure_secret_key_clearing(true)
+            .configure_threads(0)
+            .with_secret_key("secret");
+        let report = verifier.validate().unwrap_err();
+        let kinds = report
+            .iter()
+            .map(|issue| issue.error().kind())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                ErrorKind::ThreadsTooFewError,
+                ErrorKind::SecretKeyImmutableError,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_verify_dummy() {
+        let mut hasher = Hasher::fast_but_insecure();
+        hasher
+            .configure_iterations(2)
+            .configure_lanes(2)
+            .configure_memory_size(64);
+        let config = hasher.config().clone();
+
+        let mut verifier = Verifier::default();
+        verifier.with_password("P@ssw0rd");
+        assert!(!verifier.verify_dummy(&config).unwrap());
+        assert_eq!(verifier.hasher.config.iterations(), 2);
+        assert_eq!(verifier.hasher.config.memory_size(), 64);
+        assert!(!verifier.verify_dummy_non_blocking(&config).wait().unwrap());
+
+        let mut verifier = Verifier::default();
+        match verifier.verify_dummy(&config) {
+            Err(e) => assert_eq!(e.kind(), ErrorKind::PasswordMissingError),
+            Ok(_) => panic!("verify_dummy should fail without a password"),
+        }
+    }
+
     #[test]
     fn test_send() {
         fn assert_send<T: Send>() {}
@@ -37,7 +37,6 @@ setup(
         "Operating System :: POSIX :: Linux",
         "Programming Language :: Python :: 3",
         "Programming Language :: Python :: 3 :: Only",
-        "Programming Language :: Python :: 3.4",
         "Programming Language :: Python :: 3.5",
         "Programming Language :: Python :: 3.6",
         "Programming Language :: Python :: 3.7",
@@ -48,10 +47,10 @@ setup(
     ],
 
     install_requires=['cffi>=1.11.5', 'typing>=3.6.4'],
-    packages=['argonautica', 'argonautica.core'],
+    packages=['argonautica', 'argonautica.contrib', 'argonautica.core'],
     package_dir={'': 'argonautica-py'},
     package_data={'argonautica': ['*.h']},
-    python_requires='>=3.4',
+    python_requires='>=3.5',
     rust_extensions=[RustExtension(
         'argonautica.rust',
         'argonautica-c/Cargo.toml',